@@ -0,0 +1,248 @@
+//
+//  █████╗ ███████╗███╗   ███╗
+// ██╔══██╗██╔════╝████╗ ████║
+// ███████║█████╗  ██╔████╔██║
+// ██╔══██║██╔══╝  ██║╚██╔╝██║
+// ██║  ██║██║     ██║ ╚═╝ ██║
+// ╚═╝  ╚═╝╚═╝     ╚═╝     ╚═╝
+//              app/src/afm.rs
+
+// Metrics of the PDF Standard 14 fonts from Adobe's Core 14 AFM files, so text is
+// measured the same in every browser, in native builds and in exported PDFs.
+//
+// Widths are indexed by WinAnsiEncoding code minus 32, in 1/1000 em. Kerning tables
+// are written by `scripts/afm-kerning.js`, which keeps every KPX pair of the AFM whose
+// glyphs are in WinAnsiEncoding; PDF export applies the same pairs in `TJ` arrays.
+
+/// WinAnsiEncoding code for a character, if the Standard 14 fonts can show it
+pub fn win_ansi_byte(c: char) -> Option<u8> {
+    let code = c as u32;
+    match code {
+        0x20..=0x7E | 0xA0..=0xFF => Some(code as u8),
+        _ => Some(match c {
+            '€' => 0x80,
+            '‚' => 0x82,
+            'ƒ' => 0x83,
+            '„' => 0x84,
+            '…' => 0x85,
+            '†' => 0x86,
+            '‡' => 0x87,
+            'ˆ' => 0x88,
+            '‰' => 0x89,
+            'Š' => 0x8A,
+            '‹' => 0x8B,
+            'Œ' => 0x8C,
+            'Ž' => 0x8E,
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201C}' => 0x93,
+            '\u{201D}' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            '˜' => 0x98,
+            '™' => 0x99,
+            'š' => 0x9A,
+            '›' => 0x9B,
+            'œ' => 0x9C,
+            'ž' => 0x9E,
+            'Ÿ' => 0x9F,
+            _ => return None,
+        }),
+    }
+}
+
+/// Helvetica advance widths for WinAnsi codes 32-255
+#[rustfmt::skip]
+pub const HELVETICA_WIDTHS: [u16; 224] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // 0x20
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // 0x30
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // 0x40
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // 0x50
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // 0x60
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, 350, // 0x70
+    556, 350, 222, 556, 333, 1000, 556, 556, 333, 1000, 667, 333, 1000, 350, 611, 350, // 0x80
+    350, 222, 222, 333, 333, 350, 556, 1000, 333, 1000, 500, 333, 944, 350, 500, 667, // 0x90
+    278, 333, 556, 556, 556, 556, 260, 556, 333, 737, 370, 556, 584, 333, 737, 333, // 0xa0
+    400, 584, 333, 333, 333, 556, 537, 278, 333, 333, 365, 556, 834, 834, 834, 611, // 0xb0
+    667, 667, 667, 667, 667, 667, 1000, 722, 667, 667, 667, 667, 278, 278, 278, 278, // 0xc0
+    722, 722, 778, 778, 778, 778, 778, 584, 778, 722, 722, 722, 722, 667, 667, 611, // 0xd0
+    556, 556, 556, 556, 556, 556, 889, 500, 556, 556, 556, 556, 278, 278, 278, 278, // 0xe0
+    556, 556, 556, 556, 556, 556, 556, 584, 611, 556, 556, 556, 556, 500, 556, 500, // 0xf0
+];
+
+/// Helvetica-Bold advance widths for WinAnsi codes 32-255
+#[rustfmt::skip]
+pub const HELVETICA_BOLD_WIDTHS: [u16; 224] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, // 0x20
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, // 0x30
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778, // 0x40
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556, // 0x50
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611, // 0x60
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584, 350, // 0x70
+    556, 350, 278, 556, 500, 1000, 556, 556, 333, 1000, 667, 333, 1000, 350, 611, 350, // 0x80
+    350, 278, 278, 500, 500, 350, 556, 1000, 333, 1000, 556, 333, 944, 350, 500, 667, // 0x90
+    278, 333, 556, 556, 556, 556, 280, 556, 333, 737, 370, 556, 584, 333, 737, 333, // 0xa0
+    400, 584, 333, 333, 333, 611, 556, 278, 333, 333, 365, 556, 834, 834, 834, 611, // 0xb0
+    722, 722, 722, 722, 722, 722, 1000, 722, 667, 667, 667, 667, 278, 278, 278, 278, // 0xc0
+    722, 722, 778, 778, 778, 778, 778, 584, 778, 722, 722, 722, 722, 667, 667, 611, // 0xd0
+    556, 556, 556, 556, 556, 556, 889, 556, 556, 556, 556, 556, 278, 278, 278, 278, // 0xe0
+    611, 611, 611, 611, 611, 611, 611, 584, 611, 611, 611, 611, 611, 556, 611, 556, // 0xf0
+];
+
+/// Times-Roman advance widths for WinAnsi codes 32-255
+#[rustfmt::skip]
+pub const TIMES_ROMAN_WIDTHS: [u16; 224] = [
+    250, 333, 408, 500, 500, 833, 778, 180, 333, 333, 500, 564, 250, 333, 250, 278, // 0x20
+    500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 278, 278, 564, 564, 564, 444, // 0x30
+    921, 722, 667, 667, 722, 611, 556, 722, 722, 333, 389, 722, 611, 889, 722, 722, // 0x40
+    556, 722, 667, 556, 611, 722, 722, 944, 722, 722, 611, 333, 278, 333, 469, 500, // 0x50
+    333, 444, 500, 444, 500, 444, 333, 500, 500, 278, 278, 500, 278, 778, 500, 500, // 0x60
+    500, 500, 333, 389, 278, 500, 500, 722, 500, 500, 444, 480, 200, 480, 541, 350, // 0x70
+    500, 350, 333, 500, 444, 1000, 500, 500, 333, 1000, 556, 333, 889, 350, 611, 350, // 0x80
+    350, 333, 333, 444, 444, 350, 500, 1000, 333, 980, 389, 333, 722, 350, 444, 722, // 0x90
+    250, 333, 500, 500, 500, 500, 200, 500, 333, 760, 276, 500, 564, 333, 760, 333, // 0xa0
+    400, 564, 300, 300, 333, 500, 453, 250, 333, 300, 310, 500, 750, 750, 750, 444, // 0xb0
+    722, 722, 722, 722, 722, 722, 889, 667, 611, 611, 611, 611, 333, 333, 333, 333, // 0xc0
+    722, 722, 722, 722, 722, 722, 722, 564, 722, 722, 722, 722, 722, 722, 556, 500, // 0xd0
+    444, 444, 444, 444, 444, 444, 667, 444, 444, 444, 444, 444, 278, 278, 278, 278, // 0xe0
+    500, 500, 500, 500, 500, 500, 500, 564, 500, 500, 500, 500, 500, 500, 500, 500, // 0xf0
+];
+
+/// Courier advance widths for WinAnsi codes 32-255
+#[rustfmt::skip]
+pub const COURIER_WIDTHS: [u16; 224] = [
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x20
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x30
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x40
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x50
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x60
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x70
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x80
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x90
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0xa0
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0xb0
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0xc0
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0xd0
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0xe0
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0xf0
+];
+
+/// Courier-Bold advance widths for WinAnsi codes 32-255
+#[rustfmt::skip]
+pub const COURIER_BOLD_WIDTHS: [u16; 224] = [
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x20
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x30
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x40
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x50
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x60
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x70
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x80
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0x90
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0xa0
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0xb0
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0xc0
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0xd0
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0xe0
+    600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, 600, // 0xf0
+];
+
+/// Helvetica kerning pairs in 1/1000 em, sorted by pair
+#[rustfmt::skip]
+pub const HELVETICA_KERNING: &[(char, char, i16)] = &[
+    ('A', 'C', -30), ('A', 'G', -30), ('A', 'O', -30), ('A', 'Q', -30), ('A', 'T', -120),
+    ('A', 'U', -50), ('A', 'V', -70), ('A', 'W', -50), ('A', 'Y', -100), ('A', 'u', -30),
+    ('A', 'v', -40), ('A', 'w', -40), ('A', 'y', -40),
+    ('F', ',', -150), ('F', '.', -150), ('F', 'A', -80), ('F', 'a', -50), ('F', 'e', -30),
+    ('F', 'o', -30), ('F', 'r', -45),
+    ('L', 'T', -110), ('L', 'V', -110), ('L', 'W', -70), ('L', 'Y', -140), ('L', 'y', -30),
+    ('P', ',', -180), ('P', '.', -180), ('P', 'A', -120), ('P', 'a', -40), ('P', 'e', -50),
+    ('P', 'o', -50),
+    ('T', ',', -120), ('T', '-', -140), ('T', '.', -120), ('T', 'A', -120), ('T', 'O', -40),
+    ('T', 'a', -120), ('T', 'e', -120), ('T', 'o', -120), ('T', 'r', -120), ('T', 'u', -120),
+    ('T', 'w', -120), ('T', 'y', -120),
+    ('V', ',', -125), ('V', '-', -80), ('V', '.', -125), ('V', 'A', -80), ('V', 'O', -40),
+    ('V', 'a', -70), ('V', 'e', -80), ('V', 'o', -80), ('V', 'u', -70),
+    ('W', ',', -80), ('W', '.', -80), ('W', 'A', -50), ('W', 'O', -20), ('W', 'a', -40),
+    ('W', 'e', -30), ('W', 'o', -30), ('W', 'u', -30),
+    ('Y', ',', -140), ('Y', '-', -140), ('Y', '.', -140), ('Y', 'A', -110), ('Y', 'O', -85),
+    ('Y', 'a', -140), ('Y', 'e', -140), ('Y', 'o', -140), ('Y', 'u', -110),
+    ('r', ',', -50), ('r', '.', -50),
+    ('v', ',', -80), ('v', '.', -80),
+    ('w', ',', -60), ('w', '.', -60),
+    ('y', ',', -100), ('y', '.', -100),
+];
+
+/// Helvetica-Bold kerning pairs in 1/1000 em, sorted by pair
+#[rustfmt::skip]
+pub const HELVETICA_BOLD_KERNING: &[(char, char, i16)] = &[
+    ('A', 'C', -40), ('A', 'G', -50), ('A', 'O', -40), ('A', 'Q', -40), ('A', 'T', -90),
+    ('A', 'U', -50), ('A', 'V', -80), ('A', 'W', -60), ('A', 'Y', -110), ('A', 'u', -30),
+    ('A', 'v', -40), ('A', 'w', -30), ('A', 'y', -30),
+    ('F', ',', -100), ('F', '.', -100), ('F', 'A', -80), ('F', 'a', -20),
+    ('L', 'T', -90), ('L', 'V', -110), ('L', 'W', -80), ('L', 'Y', -120), ('L', 'y', -30),
+    ('P', ',', -120), ('P', '.', -120), ('P', 'A', -100), ('P', 'a', -30), ('P', 'e', -30),
+    ('P', 'o', -40),
+    ('T', ',', -80), ('T', '-', -120), ('T', '.', -80), ('T', 'A', -90), ('T', 'O', -40),
+    ('T', 'a', -80), ('T', 'e', -60), ('T', 'o', -80), ('T', 'r', -80), ('T', 'u', -90),
+    ('T', 'w', -60), ('T', 'y', -60),
+    ('V', ',', -120), ('V', '-', -80), ('V', '.', -120), ('V', 'A', -80), ('V', 'O', -50),
+    ('V', 'a', -60), ('V', 'e', -50), ('V', 'o', -90), ('V', 'u', -60),
+    ('W', ',', -80), ('W', '.', -80), ('W', 'A', -60), ('W', 'O', -20), ('W', 'a', -40),
+    ('W', 'e', -35), ('W', 'o', -60), ('W', 'u', -45),
+    ('Y', ',', -100), ('Y', '-', -120), ('Y', '.', -100), ('Y', 'A', -110), ('Y', 'O', -70),
+    ('Y', 'a', -90), ('Y', 'e', -80), ('Y', 'o', -100), ('Y', 'u', -100),
+    ('r', ',', -60), ('r', '.', -60),
+    ('v', ',', -80), ('v', '.', -80),
+    ('w', ',', -40), ('w', '.', -40),
+    ('y', ',', -80), ('y', '.', -80),
+];
+
+/// Times-Roman kerning pairs in 1/1000 em, sorted by pair
+#[rustfmt::skip]
+pub const TIMES_ROMAN_KERNING: &[(char, char, i16)] = &[
+    ('A', 'C', -40), ('A', 'G', -40), ('A', 'O', -55), ('A', 'Q', -55), ('A', 'T', -111),
+    ('A', 'U', -55), ('A', 'V', -135), ('A', 'W', -90), ('A', 'Y', -105), ('A', 'v', -74),
+    ('A', 'w', -92), ('A', 'y', -92),
+    ('F', ',', -80), ('F', '.', -80), ('F', 'A', -74), ('F', 'a', -15), ('F', 'o', -15),
+    ('L', 'T', -92), ('L', 'V', -100), ('L', 'W', -74), ('L', 'Y', -100), ('L', 'y', -55),
+    ('P', ',', -111), ('P', '.', -111), ('P', 'A', -92), ('P', 'a', -15),
+    ('T', ',', -74), ('T', '-', -92), ('T', '.', -74), ('T', 'A', -93), ('T', 'O', -18),
+    ('T', 'a', -80), ('T', 'e', -70), ('T', 'o', -80), ('T', 'r', -35), ('T', 'u', -45),
+    ('T', 'w', -80), ('T', 'y', -80),
+    ('V', ',', -129), ('V', '-', -100), ('V', '.', -129), ('V', 'A', -135), ('V', 'O', -40),
+    ('V', 'a', -111), ('V', 'e', -111), ('V', 'o', -129), ('V', 'u', -75),
+    ('W', ',', -92), ('W', '-', -65), ('W', '.', -92), ('W', 'A', -120), ('W', 'O', -10),
+    ('W', 'a', -80), ('W', 'e', -80), ('W', 'o', -80), ('W', 'u', -50),
+    ('Y', ',', -129), ('Y', '-', -111), ('Y', '.', -129), ('Y', 'A', -120), ('Y', 'O', -30),
+    ('Y', 'a', -100), ('Y', 'e', -100), ('Y', 'o', -110), ('Y', 'u', -111),
+    ('r', ',', -40), ('r', '.', -55),
+    ('v', ',', -65), ('v', '.', -65),
+    ('w', ',', -65), ('w', '.', -65),
+    ('y', ',', -65), ('y', '.', -65),
+];
+
+/// Kerning adjustment for a pair from a sorted table, in 1/1000 em
+pub fn kerning(table: &[(char, char, i16)], left: char, right: char) -> i16 {
+    table
+        .binary_search_by(|&(l, r, _)| (l, r).cmp(&(left, right)))
+        .map(|idx| table[idx].2)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kerning_tables_are_sorted() {
+        for table in [HELVETICA_KERNING, HELVETICA_BOLD_KERNING, TIMES_ROMAN_KERNING] {
+            assert!(table.windows(2).all(|pair| (pair[0].0, pair[0].1) < (pair[1].0, pair[1].1)));
+        }
+        assert_eq!(kerning(HELVETICA_KERNING, 'A', 'V'), -70);
+        assert_eq!(kerning(HELVETICA_KERNING, 'V', 'A'), -80);
+        assert_eq!(kerning(TIMES_ROMAN_KERNING, 'x', 'x'), 0);
+        assert_eq!(win_ansi_byte('—'), Some(0x97));
+        assert_eq!(win_ansi_byte('\u{2603}'), None);
+    }
+}
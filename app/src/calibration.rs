@@ -8,18 +8,53 @@
 //                                               app/src/calibration.rs
 
 use crate::coordinates::{DeviceCalibration, CoordinateSystem};
-use gloo_storage::{LocalStorage, Storage};
+#[cfg(feature = "web")]
+use crate::calibration_exchange::{
+    parse_provisioned, CalibrationExport, CALIBRATION_QUERY_PARAM, PROVISIONED_PROFILES_URL,
+};
+#[cfg(feature = "web")]
+use crate::display_profiles::{current_display, DisplayFingerprint, DisplayProfile, ProfileStore};
+#[cfg(feature = "web")]
+use crate::form_template::fetch_text;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "web")]
 use web_sys::window;
 
-const CALIBRATION_STORAGE_KEY: &str = "fl100_device_calibration";
 const TARGET_RULER_LENGTH_MM: f64 = 100.0; // 10cm ruler for calibration
+/// ISO/IEC 7810 ID-1 card (bank, ID and loyalty cards) in millimetres
+pub const ID1_CARD_WIDTH_MM: f64 = 85.60;
+pub const ID1_CARD_HEIGHT_MM: f64 = 53.98;
+const MAX_ASPECT_DEVIATION: f64 = 0.05; // X/Y scales further apart than 5% suggest a mis-measurement
+pub const ACCURACY_TARGET_MM: f64 = 0.25; // Required accuracy of rendered forms
+const OUTLIER_MAD_LIMIT: f64 = 3.0; // Robust z-score beyond which a sample is rejected
+const OUTLIER_FLOOR_PX: f64 = 2.0; // Never reject samples within this of the median
+const RECALIBRATION_CONFIDENCE: f64 = 0.6;
+/// Spread assumed for a lone reading, which can't show its own repeatability: reading a
+/// millimetre ruler by eye to about a third of a division
+const SINGLE_READING_SD_MM: f64 = 0.35;
+/// Pixels per mm outside which a reading can't be a real display (about 38-230 CSS DPI)
+const MIN_PLAUSIBLE_SCALE: f64 = 1.5;
+const MAX_PLAUSIBLE_SCALE: f64 = 9.0;
+const VERIFIED_CONFIDENCE: f64 = 0.95; // Every reference shape checked out on paper
+const VIEWPORT_CHANGE_THRESHOLD: f64 = 0.1; // 10% change in either dimension
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CalibrationState {
     pub step: CalibrationStep,
     pub measured_pixels: Option<f64>,
     pub estimated_scale: Option<f64>,
+    #[serde(default)]
+    pub measured_pixels_y: Option<f64>,
+    #[serde(default)]
+    pub estimated_scale_y: Option<f64>,
+    #[serde(default)]
+    pub method: CalibrationMethod,
+    /// Raw horizontal readings in pixels per 100mm
+    #[serde(default)]
+    pub samples_x: Vec<f64>,
+    /// Raw vertical readings in pixels per 100mm
+    #[serde(default)]
+    pub samples_y: Vec<f64>,
     pub confidence_score: f64,
     pub error_message: Option<String>,
 }
@@ -29,23 +64,150 @@ pub enum CalibrationStep {
     Welcome,
     Instructions,
     Measuring,
+    MeasuringVertical,
+    CardMatching,
     Validation,
+    Verification,
     Complete,
 }
 
+/// Direction a ruler reading was taken in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+/// Summary of repeated readings of the 100mm reference after outlier rejection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeasurementStats {
+    /// Samples kept for the estimate
+    pub samples: usize,
+    /// Samples discarded as outliers
+    pub rejected: usize,
+    pub mean_px: f64,
+    /// Pixels per mm from the mean reading
+    pub scale: f64,
+    /// Sample standard deviation of a reading, in millimetres
+    pub std_dev_mm: f64,
+    /// Half-width of the 95% confidence interval of the mean, in millimetres;
+    /// `None` from a single reading
+    pub ci95_mm: Option<f64>,
+}
+
+impl MeasurementStats {
+    /// Probability that the mean reading is right to within the accuracy target, from
+    /// the confidence interval; a single reading is given `SINGLE_READING_SD_MM`
+    pub fn confidence(&self) -> f64 {
+        let sigma = match self.ci95_mm {
+            Some(ci) => ci / 1.96,
+            None => SINGLE_READING_SD_MM,
+        };
+        if sigma <= 0.0 {
+            return 1.0;
+        }
+        erf(ACCURACY_TARGET_MM / (sigma * std::f64::consts::SQRT_2))
+    }
+
+    /// Whether the confidence interval is within the accuracy target
+    pub fn meets_target(&self) -> bool {
+        self.ci95_mm.is_some_and(|ci| ci <= ACCURACY_TARGET_MM)
+    }
+}
+
+/// Physical reference the user calibrated against
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum CalibrationMethod {
+    /// 100mm ruler, measured horizontally then vertically
+    #[default]
+    Ruler,
+    /// On-screen rectangle resized to match an ID-1 card
+    Card,
+}
+
+/// Physical object drawn at its real size to check a saved calibration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceShape {
+    /// 50mm square, checked with a ruler
+    Square,
+    /// 1-inch circle, checked across its horizontal diameter with a ruler
+    Circle,
+    /// ID-1 card outline, checked by laying a card on it
+    Card,
+}
+
+impl ReferenceShape {
+    pub const ALL: [ReferenceShape; 3] =
+        [ReferenceShape::Square, ReferenceShape::Circle, ReferenceShape::Card];
+
+    /// Width and height in millimetres
+    pub fn size_mm(&self) -> (f64, f64) {
+        match self {
+            ReferenceShape::Square => (50.0, 50.0),
+            ReferenceShape::Circle => (25.4, 25.4),
+            ReferenceShape::Card => (ID1_CARD_WIDTH_MM, ID1_CARD_HEIGHT_MM),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReferenceShape::Square => "50 mm square",
+            ReferenceShape::Circle => "1 inch circle",
+            ReferenceShape::Card => "Credit card",
+        }
+    }
+
+    /// Whether the shape has a height to check separately from its width
+    pub fn measured_vertically(&self) -> bool {
+        *self != ReferenceShape::Circle
+    }
+}
+
+/// How far a reference shape measured from its true size on each side that was
+/// checked; zero confirms a side, `None` leaves it out
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VerificationCheck {
+    pub shape: ReferenceShape,
+    /// Measured minus true width, in millimetres
+    pub deviation_x_mm: Option<f64>,
+    /// Measured minus true height, in millimetres
+    pub deviation_y_mm: Option<f64>,
+}
+
+impl VerificationCheck {
+    /// A shape confirmed to be exactly its true size on every side it is checked on
+    pub fn confirmed(shape: ReferenceShape) -> Self {
+        Self {
+            shape,
+            deviation_x_mm: Some(0.0),
+            deviation_y_mm: shape.measured_vertically().then_some(0.0),
+        }
+    }
+
+    /// Whether every side checked measured exactly right
+    pub fn is_exact(&self) -> bool {
+        [self.deviation_x_mm, self.deviation_y_mm].iter().flatten().all(|d| *d == 0.0)
+    }
+}
+
 impl Default for CalibrationState {
     fn default() -> Self {
         Self {
             step: CalibrationStep::Welcome,
             measured_pixels: None,
             estimated_scale: None,
+            measured_pixels_y: None,
+            estimated_scale_y: None,
+            method: CalibrationMethod::Ruler,
+            samples_x: Vec::new(),
+            samples_y: Vec::new(),
             confidence_score: 0.0,
             error_message: None,
         }
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct CalibrationManager {
     pub state: CalibrationState,
     coordinate_system: Option<CoordinateSystem>,
@@ -55,33 +217,21 @@ impl PartialEq for CalibrationManager {
     fn eq(&self, other: &Self) -> bool {
         // For simplicity, we'll consider managers equal if they have the same calibration state
         self.state.step == other.state.step && 
-        self.state.measured_pixels == other.state.measured_pixels
+        self.state.samples_x == other.state.samples_x &&
+        self.state.samples_y == other.state.samples_y &&
+        self.coordinate_system == other.coordinate_system
     }
 }
 
 impl CalibrationManager {
-    pub fn new() -> Self {
-        let loaded_calibration = Self::load_calibration();
-        let coordinate_system = loaded_calibration
-            .map(|cal| CoordinateSystem::new(cal));
-
+    /// Manager for an existing calibration, without touching storage
+    pub fn with_calibration(calibration: DeviceCalibration) -> Self {
         Self {
             state: CalibrationState::default(),
-            coordinate_system,
+            coordinate_system: Some(CoordinateSystem::new(calibration)),
         }
     }
 
-    /// Load calibration from localStorage
-    pub fn load_calibration() -> Option<DeviceCalibration> {
-        LocalStorage::get(CALIBRATION_STORAGE_KEY).ok()
-    }
-
-    /// Save calibration to localStorage
-    pub fn save_calibration(calibration: &DeviceCalibration) -> Result<(), String> {
-        LocalStorage::set(CALIBRATION_STORAGE_KEY, calibration)
-            .map_err(|e| format!("Failed to save calibration: {:?}", e))
-    }
-
     /// Start calibration process
     pub fn start_calibration(&mut self) {
         self.state = CalibrationState {
@@ -90,73 +240,148 @@ impl CalibrationManager {
         };
     }
 
-    /// Process user measurement input
+    /// Process a single horizontal measurement, replacing any earlier samples
     pub fn process_measurement(&mut self, measured_pixels: f64) -> Result<(), String> {
-        if measured_pixels <= 0.0 {
-            return Err("Measurement must be positive".to_string());
+        measurement_scale(measured_pixels)?;
+        self.state.samples_x.clear();
+        self.state.samples_y.clear();
+        self.add_sample(Axis::Horizontal, measured_pixels)?;
+        self.state.step = CalibrationStep::MeasuringVertical;
+        Ok(())
+    }
+
+    /// Process a single vertical measurement, taken after the horizontal one
+    pub fn process_vertical_measurement(&mut self, measured_pixels: f64) -> Result<(), String> {
+        self.state.estimated_scale
+            .ok_or("Measure horizontally first")?;
+        measurement_scale(measured_pixels)?;
+        self.state.samples_y.clear();
+        self.add_sample(Axis::Vertical, measured_pixels)?;
+        self.state.step = CalibrationStep::Validation;
+        Ok(())
+    }
+
+    /// Add one ruler reading to an axis and re-estimate from all its samples
+    pub fn add_sample(&mut self, axis: Axis, measured_pixels: f64) -> Result<(), String> {
+        measurement_scale(measured_pixels)?;
+        match axis {
+            Axis::Horizontal => self.state.samples_x.push(measured_pixels),
+            Axis::Vertical => self.state.samples_y.push(measured_pixels),
         }
+        self.state.method = CalibrationMethod::Ruler;
+        self.update_estimates();
+        Ok(())
+    }
 
-        if measured_pixels > 2000.0 {
-            return Err("Measurement seems too large - please check your ruler".to_string());
+    /// Drop the readings taken along an axis
+    pub fn clear_samples(&mut self, axis: Axis) {
+        match axis {
+            Axis::Horizontal => self.state.samples_x.clear(),
+            Axis::Vertical => self.state.samples_y.clear(),
         }
+        self.update_estimates();
+    }
 
-        let scale_factor = measured_pixels / TARGET_RULER_LENGTH_MM;
-        let confidence = self.calculate_confidence(scale_factor);
+    /// Statistics for the readings along an axis
+    pub fn sample_stats(&self, axis: Axis) -> Option<MeasurementStats> {
+        match axis {
+            Axis::Horizontal => measurement_stats(&self.state.samples_x),
+            Axis::Vertical => measurement_stats(&self.state.samples_y),
+        }
+    }
 
-        self.state.measured_pixels = Some(measured_pixels);
-        self.state.estimated_scale = Some(scale_factor);
-        self.state.confidence_score = confidence;
-        self.state.step = CalibrationStep::Validation;
+    /// Recompute scales and confidence from the collected samples
+    fn update_estimates(&mut self) {
+        let stats_x = self.sample_stats(Axis::Horizontal);
+        let stats_y = self.sample_stats(Axis::Vertical);
 
-        Ok(())
-    }
+        self.state.measured_pixels = stats_x.map(|stats| stats.mean_px);
+        self.state.estimated_scale = stats_x.map(|stats| stats.scale);
+        self.state.measured_pixels_y = stats_y.map(|stats| stats.mean_px);
+        self.state.estimated_scale_y = stats_y.map(|stats| stats.scale);
 
-    /// Calculate confidence score based on scale factor reasonableness
-    fn calculate_confidence(&self, scale_factor: f64) -> f64 {
-        // Typical range: 2-6 pixels per mm (50-150 DPI)
-        let min_reasonable = 2.0;
-        let max_reasonable = 6.0;
-        let optimal = 3.78; // ~96 DPI
+        let Some(stats_x) = stats_x else {
+            self.state.confidence_score = 0.0;
+            return;
+        };
 
-        if scale_factor < min_reasonable || scale_factor > max_reasonable {
-            0.3 // Low confidence for out-of-range values
-        } else {
-            let distance_from_optimal = (scale_factor - optimal).abs();
-            let max_distance = (max_reasonable - optimal).max(optimal - min_reasonable);
-            (1.0 - (distance_from_optimal / max_distance)).max(0.4)
+        // Each axis must be repeatable to the accuracy target; the scale itself only has
+        // to be one a real display can have, which every accepted reading is
+        let mut confidence = stats_x.confidence();
+        if let Some(stats_y) = stats_y {
+            confidence = confidence.min(stats_y.confidence());
+        }
+
+        // Real displays are close to square
+        if let Some(stats_y) = stats_y {
+            if (stats_x.scale / stats_y.scale - 1.0).abs() > MAX_ASPECT_DEVIATION {
+                confidence *= 0.5;
+            }
         }
+
+        self.state.confidence_score = confidence;
     }
 
-    /// Complete calibration and save
-    pub fn complete_calibration(&mut self) -> Result<DeviceCalibration, String> {
-        let scale_factor = self.state.estimated_scale
+    /// Process an on-screen rectangle sized to match an ID-1 card, which gives both
+    /// axes at once
+    pub fn process_card_measurement(&mut self, width_px: f64, height_px: f64) -> Result<(), String> {
+        // Express each side as the 100mm ruler reading it corresponds to
+        self.process_measurement(width_px / ID1_CARD_WIDTH_MM * TARGET_RULER_LENGTH_MM)?;
+        self.process_vertical_measurement(height_px / ID1_CARD_HEIGHT_MM * TARGET_RULER_LENGTH_MM)?;
+        self.state.method = CalibrationMethod::Card;
+
+        // A rectangle stretched away from the card's shape means a side doesn't line up
+        if let (Some(scale_x), Some(scale_y)) = (self.state.estimated_scale, self.state.estimated_scale_y) {
+            self.state.confidence_score *= aspect_agreement(scale_x, scale_y);
+        }
+
+        Ok(())
+    }
+
+    /// Build the calibration for the current measurement and the viewport it was taken in
+    pub fn build_calibration(
+        &self,
+        device_pixel_ratio: f64,
+        viewport_width: f64,
+        viewport_height: f64,
+        timestamp: u64,
+    ) -> Result<DeviceCalibration, String> {
+        let scale_x = self.state.estimated_scale
             .ok_or("No measurement available")?;
+        // Without a vertical measurement the display is taken to be square
+        let scale_y = self.state.estimated_scale_y.unwrap_or(scale_x);
 
-        let window = window().ok_or("No window object")?;
-        let device_pixel_ratio = window.device_pixel_ratio();
-        let inner_width = window.inner_width()
-            .map_err(|_| "Failed to get window width")?
-            .as_f64()
-            .ok_or("Invalid window width")?;
-        let inner_height = window.inner_height()
-            .map_err(|_| "Failed to get window height")?
-            .as_f64()
-            .ok_or("Invalid window height")?;
-
-        let calibration = DeviceCalibration {
-            scale_factor,
+        Ok(DeviceCalibration {
+            scale_x,
+            scale_y,
             confidence: self.state.confidence_score,
-            timestamp: js_sys::Date::now() as u64,
+            timestamp,
             device_pixel_ratio,
-            viewport_width: inner_width,
-            viewport_height: inner_height,
-        };
+            viewport_width,
+            viewport_height,
+        })
+    }
 
-        Self::save_calibration(&calibration)?;
-        self.coordinate_system = Some(CoordinateSystem::new(calibration.clone()));
+    /// Make a calibration current and finish the wizard
+    pub fn apply_calibration(&mut self, calibration: DeviceCalibration) {
+        self.coordinate_system = Some(CoordinateSystem::new(calibration));
         self.state.step = CalibrationStep::Complete;
+    }
 
-        Ok(calibration)
+    /// Follow a browser zoom or pixel ratio change of `zoom` without recalibrating
+    pub fn rescale(&mut self, zoom: f64) {
+        if let Some(coordinate_system) = &self.coordinate_system {
+            let calibration = coordinate_system.get_calibration().zoomed(zoom);
+            self.coordinate_system = Some(CoordinateSystem::new(calibration));
+        }
+    }
+
+    /// Correct the current calibration from checks of the verification shapes
+    pub fn verify(&mut self, checks: &[VerificationCheck]) -> Result<DeviceCalibration, String> {
+        let calibration = self.get_calibration().ok_or("Nothing to verify: not calibrated yet")?;
+        let verified = apply_verification(calibration, checks)?;
+        self.coordinate_system = Some(CoordinateSystem::new(verified.clone()));
+        Ok(verified)
     }
 
     /// Get current calibration state
@@ -174,44 +399,370 @@ impl CalibrationManager {
         self.coordinate_system.as_ref().map(|cs| cs.get_calibration())
     }
 
+    /// Whether a stored calibration is too weak or too stale for the given viewport
+    pub fn calibration_outdated(calibration: &DeviceCalibration, viewport_width: f64, viewport_height: f64) -> bool {
+        calibration.confidence < RECALIBRATION_CONFIDENCE
+            || viewport_changed_significantly(calibration, viewport_width, viewport_height)
+    }
+
+    /// Get estimated horizontal DPI for display
+    pub fn get_estimated_dpi(&self) -> Option<f64> {
+        self.state.estimated_scale.map(|scale| scale * 25.4)
+    }
+
+    /// Get estimated vertical DPI for display
+    pub fn get_estimated_dpi_y(&self) -> Option<f64> {
+        self.state.estimated_scale_y.map(|scale| scale * 25.4)
+    }
+}
+
+#[cfg(feature = "web")]
+impl CalibrationManager {
+    pub fn new() -> Self {
+        match Self::load_calibration() {
+            Some(calibration) => Self::with_calibration(calibration),
+            None => Self::default(),
+        }
+    }
+
+    /// Stored profile for the display the window is on
+    pub fn current_profile() -> Option<DisplayProfile> {
+        let display = current_display()?;
+        ProfileStore::load().find(&display).cloned()
+    }
+
+    /// Load the calibration for the current display from localStorage, rescaled if it
+    /// was made on the same screen at another zoom level
+    pub fn load_calibration() -> Option<DeviceCalibration> {
+        ProfileStore::load().calibration_for(&current_display()?)
+    }
+
+    /// Save calibration to localStorage as the current display's profile
+    pub fn save_calibration(calibration: &DeviceCalibration) -> Result<(), String> {
+        let display = current_display().ok_or("Cannot identify the current display")?;
+        let mut profiles = ProfileStore::load();
+        profiles.upsert(display, calibration.clone());
+        ProfileStore::save(&profiles)
+    }
+
+    /// Complete calibration and save
+    pub fn complete_calibration(&mut self) -> Result<DeviceCalibration, String> {
+        let window = window().ok_or("No window object")?;
+        let (inner_width, inner_height) = viewport_size(&window).ok_or("Failed to get window size")?;
+
+        let calibration = self.build_calibration(
+            window.device_pixel_ratio(),
+            inner_width,
+            inner_height,
+            js_sys::Date::now() as u64,
+        )?;
+
+        Self::save_calibration(&calibration)?;
+        self.apply_calibration(calibration.clone());
+
+        Ok(calibration)
+    }
+
+    /// Correct the saved calibration from checks of the verification shapes and save it
+    pub fn complete_verification(&mut self, checks: &[VerificationCheck]) -> Result<DeviceCalibration, String> {
+        let verified = self.verify(checks)?;
+        Self::save_calibration(&verified)?;
+        Ok(verified)
+    }
+
     /// Check if recalibration is needed
     pub fn needs_recalibration(&self) -> bool {
         match Self::load_calibration() {
-            Some(cal) => {
-                // Recalibrate if confidence is low or viewport changed significantly
-                cal.confidence < 0.6 || self.viewport_changed_significantly(&cal)
-            }
+            Some(cal) => match window().as_ref().and_then(viewport_size) {
+                Some((width, height)) => Self::calibration_outdated(&cal, width, height),
+                None => cal.confidence < RECALIBRATION_CONFIDENCE,
+            },
             None => true,
         }
     }
 
-    /// Check if viewport changed significantly since last calibration
-    fn viewport_changed_significantly(&self, last_cal: &DeviceCalibration) -> bool {
-        if let Some(window) = window() {
-            if let (Ok(current_width), Ok(current_height)) = 
-                (window.inner_width(), window.inner_height()) {
-                if let (Some(width), Some(height)) = 
-                    (current_width.as_f64(), current_height.as_f64()) {
-                    let width_change = (width - last_cal.viewport_width).abs() / last_cal.viewport_width;
-                    let height_change = (height - last_cal.viewport_height).abs() / last_cal.viewport_height;
-                    return width_change > 0.1 || height_change > 0.1; // 10% change threshold
+    /// Checksummed JSON export of the stored profile for a display
+    pub fn export_profile(display: &DisplayFingerprint) -> Result<String, String> {
+        let profile = ProfileStore::load()
+            .find(display)
+            .cloned()
+            .ok_or("No calibration stored for this display")?;
+        CalibrationExport::new(profile)?.to_json()
+    }
+
+    /// Check an exported profile's format, checksum and values, and store it in place
+    /// of any for the same display; used for files the user picked themselves
+    pub fn import_profile(json: &str) -> Result<DisplayProfile, String> {
+        let profile = CalibrationExport::from_json(json)?.profile;
+        Self::replace_profile(profile.clone())?;
+        Ok(profile)
+    }
+
+    /// Store a profile in place of any for the same display
+    pub fn replace_profile(profile: DisplayProfile) -> Result<(), String> {
+        let mut profiles = ProfileStore::load();
+        profiles.install(profile, true);
+        ProfileStore::save(&profiles)
+    }
+
+    /// Import a profile passed in the page URL, then drop the parameter so a later
+    /// reload or bookmark doesn't import it again. Anyone can send a link, so it is only
+    /// stored straight away when the display has no calibration of its own; otherwise
+    /// the conflict is returned for the user to settle.
+    pub fn import_from_query() -> Option<Result<Option<LinkConflict>, String>> {
+        let window = window()?;
+        let search = window.location().search().ok()?;
+        let params = web_sys::UrlSearchParams::new_with_str(&search).ok()?;
+        let json = params.get(CALIBRATION_QUERY_PARAM)?;
+
+        params.delete(CALIBRATION_QUERY_PARAM);
+        let query = String::from(params.to_string());
+        let location = window.location();
+        let url = format!(
+            "{}{}{}",
+            location.pathname().unwrap_or_default(),
+            if query.is_empty() { String::new() } else { format!("?{}", query) },
+            location.hash().unwrap_or_default()
+        );
+        if let Ok(history) = window.history() {
+            let _ = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&url));
+        }
+
+        Some(CalibrationExport::from_json(&json).and_then(|export| {
+            let linked = export.profile;
+            let mut profiles = ProfileStore::load();
+            match profiles.find(&linked.display) {
+                Some(existing) if *existing == linked => Ok(None),
+                Some(existing) => Ok(Some(LinkConflict { existing: existing.clone(), linked })),
+                None => {
+                    profiles.install(linked, false);
+                    ProfileStore::save(&profiles)?;
+                    Ok(None)
                 }
             }
+        }))
+    }
+
+    /// Store admin-provisioned profiles for displays the user hasn't calibrated;
+    /// returns how many were added
+    pub fn install_provisioned(provisioned: Vec<DisplayProfile>) -> Result<usize, String> {
+        let mut profiles = ProfileStore::load();
+        let added = provisioned
+            .into_iter()
+            .filter(|profile| profiles.install(profile.clone(), false))
+            .count();
+        if added > 0 {
+            ProfileStore::save(&profiles)?;
         }
-        false
+        Ok(added)
     }
 
-    /// Reset calibration (force new calibration)
+    /// Reset calibration for the current display (force new calibration)
     pub fn reset_calibration(&mut self) {
-        let _ = LocalStorage::delete(CALIBRATION_STORAGE_KEY);
+        if let Some(display) = current_display() {
+            let mut profiles = ProfileStore::load();
+            if profiles.remove(&display) {
+                if let Err(error) = ProfileStore::save(&profiles) {
+                    web_sys::console::warn_1(&error.into());
+                }
+            }
+        }
         self.coordinate_system = None;
         self.state = CalibrationState::default();
     }
+}
 
-    /// Get estimated DPI for display
-    pub fn get_estimated_dpi(&self) -> Option<f64> {
-        self.state.estimated_scale.map(|scale| scale * 25.4)
+/// Calibration from a page URL that wasn't stored because the display already has a
+/// different one; waits for the user to choose between them
+#[cfg(feature = "web")]
+pub struct LinkConflict {
+    pub existing: DisplayProfile,
+    pub linked: DisplayProfile,
+}
+
+/// Fetch the calibration profiles bundled with the app by an administrator
+#[cfg(feature = "web")]
+pub async fn fetch_provisioned() -> Result<Vec<DisplayProfile>, String> {
+    parse_provisioned(&fetch_text(PROVISIONED_PROFILES_URL).await?)
+}
+
+/// Inner width and height of the browser window in CSS pixels
+#[cfg(feature = "web")]
+fn viewport_size(window: &web_sys::Window) -> Option<(f64, f64)> {
+    let width = window.inner_width().ok()?.as_f64()?;
+    let height = window.inner_height().ok()?.as_f64()?;
+    Some((width, height))
+}
+
+/// Whether a scale in pixels per mm is one a real display can have
+pub fn plausible_scale(scale: f64) -> bool {
+    (MIN_PLAUSIBLE_SCALE..=MAX_PLAUSIBLE_SCALE).contains(&scale)
+}
+
+/// Pixels per mm for a ruler measurement, rejecting implausible values
+fn measurement_scale(measured_pixels: f64) -> Result<f64, String> {
+    if measured_pixels <= 0.0 {
+        return Err("Measurement must be positive".to_string());
+    }
+
+    let scale = measured_pixels / TARGET_RULER_LENGTH_MM;
+    if scale > MAX_PLAUSIBLE_SCALE {
+        return Err("Measurement seems too large - please check your ruler".to_string());
+    }
+    if scale < MIN_PLAUSIBLE_SCALE {
+        return Err("Measurement seems too small - please check your ruler".to_string());
+    }
+
+    Ok(scale)
+}
+
+/// Mean, spread and 95% confidence interval of 100mm readings, after rejecting
+/// samples far from the median
+pub fn measurement_stats(samples: &[f64]) -> Option<MeasurementStats> {
+    if samples.is_empty() {
+        return None;
     }
+
+    let kept = reject_outliers(samples);
+    let n = kept.len() as f64;
+    let mean_px = kept.iter().sum::<f64>() / n;
+    let scale = mean_px / TARGET_RULER_LENGTH_MM;
+    let std_dev_px = if kept.len() > 1 {
+        (kept.iter().map(|s| (s - mean_px).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+    } else {
+        0.0
+    };
+    let ci95_mm = (kept.len() > 1)
+        .then(|| t_critical_95(kept.len() - 1) * std_dev_px / n.sqrt() / scale);
+
+    Some(MeasurementStats {
+        samples: kept.len(),
+        rejected: samples.len() - kept.len(),
+        mean_px,
+        scale,
+        std_dev_mm: std_dev_px / scale,
+        ci95_mm,
+    })
+}
+
+/// Samples within a robust distance of the median (median absolute deviation);
+/// fewer than three samples cannot outvote each other and are all kept
+fn reject_outliers(samples: &[f64]) -> Vec<f64> {
+    if samples.len() < 3 {
+        return samples.to_vec();
+    }
+
+    let median_px = median(samples);
+    let deviations: Vec<f64> = samples.iter().map(|s| (s - median_px).abs()).collect();
+    // 1.4826 scales the MAD to a standard deviation for normal data
+    let limit = (OUTLIER_MAD_LIMIT * 1.4826 * median(&deviations)).max(OUTLIER_FLOOR_PX);
+
+    samples.iter().copied().filter(|s| (s - median_px).abs() <= limit).collect()
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Two-sided 95% Student's t critical value
+fn t_critical_95(degrees_of_freedom: usize) -> f64 {
+    const TABLE: [f64; 10] = [12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228];
+    match degrees_of_freedom {
+        0 => f64::INFINITY,
+        df if df <= TABLE.len() => TABLE[df - 1],
+        df if df < 30 => 2.1,
+        _ => 1.96,
+    }
+}
+
+/// Error function (Abramowitz and Stegun 7.1.26, accurate to 1.5e-7)
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    (1.0 - poly * (-x * x).exp()).copysign(x)
+}
+
+/// How closely the X and Y scales agree: 1 when equal, 0.5 at the deviation limit,
+/// 0 at twice the limit
+fn aspect_agreement(scale_x: f64, scale_y: f64) -> f64 {
+    let deviation = (scale_x / scale_y - 1.0).abs();
+    (1.0 - deviation / (2.0 * MAX_ASPECT_DEVIATION)).clamp(0.0, 1.0)
+}
+
+/// Calibration corrected by measurements of shapes drawn with it.
+///
+/// Each axis is rescaled by total drawn over total measured length of the sides checked
+/// on it, so larger shapes count for more; an axis nothing was checked on is left alone.
+/// Confidence rises when the corrected scale explains every side to within the accuracy
+/// target, and falls when the shapes disagree with each other.
+pub fn apply_verification(
+    calibration: &DeviceCalibration,
+    checks: &[VerificationCheck],
+) -> Result<DeviceCalibration, String> {
+    // (true size, deviation) of every side measured, per axis
+    let mut sides_x = Vec::new();
+    let mut sides_y = Vec::new();
+    for check in checks {
+        let (width, height) = check.shape.size_mm();
+        for (deviation, size, sides) in
+            [(check.deviation_x_mm, width, &mut sides_x), (check.deviation_y_mm, height, &mut sides_y)]
+        {
+            let Some(deviation) = deviation else { continue };
+            if !deviation.is_finite() || deviation.abs() >= size / 2.0 {
+                return Err(format!(
+                    "The {} deviation is implausible - please measure it again",
+                    check.shape.label()
+                ));
+            }
+            sides.push((size, deviation));
+        }
+    }
+    if sides_x.is_empty() && sides_y.is_empty() {
+        return Err("Check at least one reference shape".to_string());
+    }
+
+    let correction = |sides: &[(f64, f64)]| {
+        let nominal: f64 = sides.iter().map(|(size, _)| size).sum();
+        let measured: f64 = sides.iter().map(|(size, deviation)| size + deviation).sum();
+        if sides.is_empty() { 1.0 } else { nominal / measured }
+    };
+    let correction_x = correction(&sides_x);
+    let correction_y = correction(&sides_y);
+
+    // How far each side would still be off once redrawn at the corrected scale
+    let residual = |sides: &[(f64, f64)], correction: f64| {
+        sides
+            .iter()
+            .map(|(size, deviation)| ((size + deviation) * correction - size).abs())
+            .fold(0.0, f64::max)
+    };
+    let residual_mm = residual(&sides_x, correction_x).max(residual(&sides_y, correction_y));
+    let confidence = if residual_mm <= ACCURACY_TARGET_MM {
+        calibration.confidence.max(VERIFIED_CONFIDENCE)
+    } else {
+        calibration.confidence.min(ACCURACY_TARGET_MM / residual_mm)
+    };
+
+    Ok(DeviceCalibration {
+        scale_x: calibration.scale_x * correction_x,
+        scale_y: calibration.scale_y * correction_y,
+        confidence,
+        ..calibration.clone()
+    })
+}
+
+/// Check if viewport changed significantly since last calibration
+fn viewport_changed_significantly(last_cal: &DeviceCalibration, width: f64, height: f64) -> bool {
+    let width_change = (width - last_cal.viewport_width).abs() / last_cal.viewport_width;
+    let height_change = (height - last_cal.viewport_height).abs() / last_cal.viewport_height;
+    width_change > VIEWPORT_CHANGE_THRESHOLD || height_change > VIEWPORT_CHANGE_THRESHOLD
 }
 
 #[cfg(test)]
@@ -220,21 +771,44 @@ mod tests {
 
     #[test]
     fn test_calibration_confidence() {
-        let mut manager = CalibrationManager::new();
-        
-        // Good measurement (96 DPI = ~3.78 px/mm)
+        let mut manager = CalibrationManager::default();
+
+        // One reading can be accepted, but can't show it is repeatable
         manager.process_measurement(378.0).unwrap();
+        let single = manager.state.confidence_score;
+        assert!((0.5..RECALIBRATION_CONFIDENCE).contains(&single), "{}", single);
+
+        // Matching readings raise it
+        for pixels in [378.5, 377.5] {
+            manager.add_sample(Axis::Horizontal, pixels).unwrap();
+        }
         assert!(manager.state.confidence_score > 0.8);
 
-        // Poor measurement (too high)
-        let mut manager2 = CalibrationManager::new();
-        manager2.process_measurement(1000.0).unwrap();
-        assert!(manager2.state.confidence_score < 0.5);
+        // Outside the range of real displays
+        assert!(CalibrationManager::default().process_measurement(1000.0).is_err());
+        assert!(CalibrationManager::default().process_measurement(100.0).is_err());
+    }
+
+    #[test]
+    fn test_repeatable_high_density_display() {
+        // A MacBook Air shows about 5 CSS px per mm, far from the 96 DPI guess
+        let mut manager = CalibrationManager::default();
+        for pixels in [500.0, 500.5, 499.5, 500.0, 500.0] {
+            manager.add_sample(Axis::Horizontal, pixels).unwrap();
+        }
+        for pixels in [500.0, 500.0, 499.5, 500.5] {
+            manager.add_sample(Axis::Vertical, pixels).unwrap();
+        }
+        assert!(manager.state.confidence_score > 0.95, "{}", manager.state.confidence_score);
+
+        let calibration = manager.build_calibration(2.0, 1440.0, 900.0, 0).unwrap();
+        assert!((calibration.scale_x - 5.0).abs() < 1e-9);
+        assert!(!CalibrationManager::calibration_outdated(&calibration, 1440.0, 900.0));
     }
 
     #[test]
     fn test_measurement_validation() {
-        let mut manager = CalibrationManager::new();
+        let mut manager = CalibrationManager::default();
         
         // Invalid measurements
         assert!(manager.process_measurement(0.0).is_err());
@@ -244,4 +818,172 @@ mod tests {
         // Valid measurement
         assert!(manager.process_measurement(350.0).is_ok());
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_complete_and_outdated() {
+        let mut manager = CalibrationManager::default();
+        assert!(manager.build_calibration(1.0, 1280.0, 800.0, 0).is_err());
+
+        manager.process_measurement(378.0).unwrap();
+        manager.add_sample(Axis::Horizontal, 378.0).unwrap();
+        let calibration = manager.build_calibration(2.0, 1280.0, 800.0, 42).unwrap();
+        assert_eq!(calibration.device_pixel_ratio, 2.0);
+        assert!((calibration.scale_x - 3.78).abs() < 1e-9);
+        assert_eq!(calibration.scale_x, calibration.scale_y);
+
+        manager.apply_calibration(calibration.clone());
+        assert_eq!(manager.state.step, CalibrationStep::Complete);
+        assert!(manager.get_coordinate_system().is_some());
+
+        assert!(!CalibrationManager::calibration_outdated(&calibration, 1300.0, 820.0));
+        assert!(CalibrationManager::calibration_outdated(&calibration, 1600.0, 800.0));
+    }
+
+    #[test]
+    fn test_vertical_measurement() {
+        let mut manager = CalibrationManager::default();
+        assert!(manager.process_vertical_measurement(378.0).is_err());
+
+        manager.process_measurement(378.0).unwrap();
+        assert_eq!(manager.state.step, CalibrationStep::MeasuringVertical);
+        manager.process_vertical_measurement(382.0).unwrap();
+        assert_eq!(manager.state.step, CalibrationStep::Validation);
+        assert!(manager.state.confidence_score >= 0.5);
+
+        let calibration = manager.build_calibration(1.0, 1280.0, 800.0, 0).unwrap();
+        assert!((calibration.scale_x - 3.78).abs() < 1e-9);
+        assert!((calibration.scale_y - 3.82).abs() < 1e-9);
+
+        // Axes far apart are more likely a slipped ruler than a real display
+        manager.process_measurement(378.0).unwrap();
+        manager.process_vertical_measurement(450.0).unwrap();
+        assert!(manager.state.confidence_score < 0.3);
+    }
+
+    #[test]
+    fn test_measurement_stats() {
+        // The slipped 420px reading is rejected
+        let stats = measurement_stats(&[378.0, 379.0, 377.0, 378.0, 420.0]).unwrap();
+        assert_eq!((stats.samples, stats.rejected), (4, 1));
+        assert_eq!(stats.mean_px, 378.0);
+        let ci = stats.ci95_mm.unwrap();
+        assert!((ci - 0.3437).abs() < 0.001, "ci {}", ci);
+        assert!(stats.confidence() > 0.8);
+        assert!(!stats.meets_target());
+
+        // Readings scattered over 20px are not repeatable to 0.25mm
+        let scattered = measurement_stats(&[370.0, 385.0, 375.0, 390.0]).unwrap();
+        assert_eq!(scattered.rejected, 0);
+        assert!(scattered.confidence() < 0.2);
+
+        let single = measurement_stats(&[378.0]).unwrap();
+        assert_eq!(single.ci95_mm, None);
+        assert!(single.confidence() < RECALIBRATION_CONFIDENCE);
+        assert!(measurement_stats(&[]).is_none());
+    }
+
+    #[test]
+    fn test_multi_sample_confidence() {
+        let mut manager = CalibrationManager::default();
+        for pixels in [378.0, 378.0, 379.0, 378.0, 377.0, 378.0] {
+            manager.add_sample(Axis::Horizontal, pixels).unwrap();
+        }
+        for pixels in [378.0, 379.0, 378.0, 378.0, 350.0, 378.0] {
+            manager.add_sample(Axis::Vertical, pixels).unwrap();
+        }
+        assert_eq!(manager.sample_stats(Axis::Vertical).unwrap().rejected, 1);
+        assert!(manager.state.confidence_score > 0.9);
+
+        let calibration = manager.build_calibration(1.0, 1280.0, 800.0, 0).unwrap();
+        assert!((calibration.scale_y - 3.782).abs() < 1e-9);
+        assert_eq!(calibration.confidence, manager.state.confidence_score);
+
+        // Scattered readings on one axis bring the whole calibration down
+        manager.clear_samples(Axis::Horizontal);
+        for pixels in [370.0, 385.0, 375.0, 390.0] {
+            manager.add_sample(Axis::Horizontal, pixels).unwrap();
+        }
+        assert!(manager.state.confidence_score < 0.2);
+    }
+
+    #[test]
+    fn test_card_measurement() {
+        let mut manager = CalibrationManager::default();
+
+        // A card at 96 DPI is 323.5 x 204.0 px
+        manager.process_card_measurement(323.5, 204.0).unwrap();
+        assert_eq!(manager.state.method, CalibrationMethod::Card);
+        assert_eq!(manager.state.step, CalibrationStep::Validation);
+        let matched = manager.state.confidence_score;
+        assert!(matched >= 0.5);
+
+        let calibration = manager.build_calibration(1.0, 1280.0, 800.0, 0).unwrap();
+        assert!((calibration.scale_x - 3.779).abs() < 0.001);
+        assert!((calibration.scale_y - 3.779).abs() < 0.001);
+
+        // Stretched 3% vertically: plausible scales, but the shape gives it away
+        manager.process_card_measurement(323.5, 210.1).unwrap();
+        assert!(manager.state.confidence_score < matched * 0.75);
+
+        assert!(manager.process_card_measurement(0.0, 204.0).is_err());
+    }
+
+    #[test]
+    fn test_verification_adjusts_scale() {
+        let calibration = DeviceCalibration { scale_x: 3.8, scale_y: 3.8, confidence: 0.7, ..DeviceCalibration::default() };
+        let confirmed = VerificationCheck::confirmed;
+
+        let verified = apply_verification(&calibration, &ReferenceShape::ALL.map(confirmed)).unwrap();
+        assert_eq!((verified.scale_x, verified.scale_y), (3.8, 3.8));
+        assert_eq!(verified.confidence, 0.95);
+
+        // Everything measured 2% wide: the square 1mm, the circle ~0.5mm, the card ~1.7mm
+        let wide: Vec<_> = ReferenceShape::ALL
+            .iter()
+            .map(|&shape| VerificationCheck { deviation_x_mm: Some(shape.size_mm().0 * 0.02), ..confirmed(shape) })
+            .collect();
+        let verified = apply_verification(&calibration, &wide).unwrap();
+        assert!((verified.scale_x - 3.8 / 1.02).abs() < 1e-9);
+        assert_eq!(verified.scale_y, 3.8);
+        assert_eq!(verified.confidence, 0.95);
+
+        // The square says 2mm too wide and the card says spot on: no single scale fits
+        let inconsistent = [
+            VerificationCheck { deviation_x_mm: Some(2.0), ..confirmed(ReferenceShape::Square) },
+            confirmed(ReferenceShape::Card),
+        ];
+        let verified = apply_verification(&calibration, &inconsistent).unwrap();
+        assert!(verified.confidence < 0.5);
+
+        assert!(apply_verification(&calibration, &[]).is_err());
+        let absurd = VerificationCheck { deviation_y_mm: Some(30.0), ..confirmed(ReferenceShape::Square) };
+        assert!(apply_verification(&calibration, &[absurd]).is_err());
+
+        let mut manager = CalibrationManager::with_calibration(calibration.clone());
+        manager.verify(&wide).unwrap();
+        assert!((manager.get_calibration().unwrap().scale_x - 3.8 / 1.02).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_verification_uses_only_measured_sides() {
+        let calibration = DeviceCalibration { scale_x: 3.8, scale_y: 3.8, confidence: 0.4, ..DeviceCalibration::default() };
+
+        // Nothing entered confirms nothing
+        let blank = VerificationCheck { shape: ReferenceShape::Square, deviation_x_mm: None, deviation_y_mm: None };
+        assert!(apply_verification(&calibration, &[blank]).is_err());
+
+        // The circle's diameter is a width reading; it says nothing about the height
+        let circle = VerificationCheck::confirmed(ReferenceShape::Circle);
+        assert_eq!(circle.deviation_y_mm, None);
+        let circle_wide = VerificationCheck { deviation_x_mm: Some(0.508), ..circle };
+        let verified = apply_verification(&calibration, &[circle_wide]).unwrap();
+        assert!((verified.scale_x - 3.8 / 1.02).abs() < 1e-9);
+        assert_eq!(verified.scale_y, 3.8);
+
+        // Only the square's height measured, 1mm short; its width is left alone
+        let short = VerificationCheck { deviation_y_mm: Some(-1.0), ..blank };
+        assert!(!short.is_exact());
+        let verified = apply_verification(&calibration, &[short]).unwrap();
+        assert_eq!(verified.scale_x, 3.8);
+        assert!((verified.scale_y - 3.8 / 0.98).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,213 @@
+//
+// ██████╗ ██████╗  ██████╗ ██╗   ██╗██╗███████╗██╗ ██████╗ ███╗   ██╗██╗███╗   ██╗ ██████╗
+// ██╔══██╗██╔══██╗██╔═══██╗██║   ██║██║██╔════╝██║██╔═══██╗████╗  ██║██║████╗  ██║██╔════╝
+// ██████╔╝██████╔╝██║   ██║██║   ██║██║███████╗██║██║   ██║██╔██╗ ██║██║██╔██╗ ██║██║  ███╗
+// ██╔═══╝ ██╔══██╗██║   ██║╚██╗ ██╔╝██║╚════██║██║██║   ██║██║╚██╗██║██║██║╚██╗██║██║   ██║
+// ██║     ██║  ██║╚██████╔╝ ╚████╔╝ ██║███████║██║╚██████╔╝██║ ╚████║██║██║ ╚████║╚██████╔╝
+// ╚═╝     ╚═╝  ╚═╝ ╚═════╝   ╚═══╝  ╚═╝╚══════╝╚═╝ ╚═════╝ ╚═╝  ╚═══╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+//                                                           app/src/calibration_exchange.rs
+
+use crate::calibration::plausible_scale;
+use crate::display_profiles::DisplayProfile;
+use serde::{Deserialize, Serialize};
+
+/// Identifies a JSON file as one of our calibration exports
+pub const CALIBRATION_EXPORT_FORMAT: &str = "pixel-perfect-forms/calibration";
+
+/// Current calibration export schema version
+pub const CALIBRATION_EXPORT_VERSION: u32 = 1;
+
+/// Query parameter carrying a calibration export, e.g. `?calibration=<url-encoded JSON>`
+pub const CALIBRATION_QUERY_PARAM: &str = "calibration";
+
+/// Bundled file of admin-provisioned calibration exports, served with the app
+pub const PROVISIONED_PROFILES_URL: &str = "/static/calibration/profiles.json";
+
+/// A display profile packaged for another workstation with the same monitor model.
+///
+/// The checksum catches truncated or hand-edited files; it is not a signature and
+/// doesn't prove who made the export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationExport {
+    pub format: String,
+    pub schema_version: u32,
+    pub profile: DisplayProfile,
+    /// FNV-1a 64 of the profile's JSON, in hex
+    pub checksum: String,
+}
+
+impl CalibrationExport {
+    pub fn new(profile: DisplayProfile) -> Result<Self, String> {
+        Ok(Self {
+            format: CALIBRATION_EXPORT_FORMAT.to_string(),
+            schema_version: CALIBRATION_EXPORT_VERSION,
+            checksum: profile_checksum(&profile)?,
+            profile,
+        })
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize calibration: {}", e))
+    }
+
+    /// Parse an export, checking the format marker, version, checksum and that the
+    /// calibration is one a real display could have
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let value: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| format!("Calibration is not valid JSON: {}", e))?;
+        Self::from_value(value)
+    }
+
+    fn from_value(value: serde_json::Value) -> Result<Self, String> {
+        let format = value.get("format").and_then(|f| f.as_str());
+        if format != Some(CALIBRATION_EXPORT_FORMAT) {
+            return Err(format!(
+                "Not a calibration export (format {:?})",
+                format.unwrap_or("missing")
+            ));
+        }
+        let schema_version = value.get("schema_version").and_then(|v| v.as_u64());
+        if schema_version != Some(CALIBRATION_EXPORT_VERSION as u64) {
+            return Err(format!("Unsupported calibration schema version {:?}", schema_version));
+        }
+
+        let export: CalibrationExport = serde_json::from_value(value)
+            .map_err(|e| format!("Invalid calibration export: {}", e))?;
+        if profile_checksum(&export.profile)? != export.checksum.to_ascii_lowercase() {
+            return Err(format!(
+                "Calibration '{}' failed its checksum; the file was modified or truncated",
+                export.profile.name
+            ));
+        }
+        check_calibration(&export.profile)?;
+        Ok(export)
+    }
+}
+
+/// Profiles from a bundled provisioning file: a JSON array of exports. Every entry
+/// must be valid, so a bad file is noticed rather than half applied.
+pub fn parse_provisioned(json: &str) -> Result<Vec<DisplayProfile>, String> {
+    let entries: Vec<serde_json::Value> = serde_json::from_str(json)
+        .map_err(|e| format!("Provisioned calibrations are not a JSON array: {}", e))?;
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(idx, entry)| {
+            CalibrationExport::from_value(entry)
+                .map(|export| export.profile)
+                .map_err(|e| format!("Provisioned calibration {}: {}", idx + 1, e))
+        })
+        .collect()
+}
+
+/// The checksum only shows a file wasn't damaged; anyone can write one, so the values
+/// themselves must be sane before they size anything on screen
+fn check_calibration(profile: &DisplayProfile) -> Result<(), String> {
+    let calibration = &profile.calibration;
+    for scale in [calibration.scale_x, calibration.scale_y] {
+        if !plausible_scale(scale) {
+            return Err(format!(
+                "Calibration '{}' has an implausible scale of {} px/mm",
+                profile.name, scale
+            ));
+        }
+    }
+    if !(0.0..=1.0).contains(&calibration.confidence) {
+        return Err(format!(
+            "Calibration '{}' has an invalid confidence of {}",
+            profile.name, calibration.confidence
+        ));
+    }
+    if !(calibration.device_pixel_ratio > 0.0 && calibration.device_pixel_ratio.is_finite()) {
+        return Err(format!(
+            "Calibration '{}' has an invalid device pixel ratio of {}",
+            profile.name, calibration.device_pixel_ratio
+        ));
+    }
+    Ok(())
+}
+
+fn profile_checksum(profile: &DisplayProfile) -> Result<String, String> {
+    let json = serde_json::to_string(profile)
+        .map_err(|e| format!("Failed to serialize calibration: {}", e))?;
+    Ok(format!("{:016x}", fnv1a_64(json.as_bytes())))
+}
+
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinates::DeviceCalibration;
+    use crate::display_profiles::DisplayFingerprint;
+
+    fn profile() -> DisplayProfile {
+        DisplayProfile {
+            name: "Dell P2422H".to_string(),
+            display: DisplayFingerprint {
+                screen_width: 1920,
+                screen_height: 1080,
+                device_pixel_ratio: 1.0,
+                color_depth: 24,
+            },
+            calibration: DeviceCalibration {
+                scale_x: 3.63,
+                scale_y: 3.64,
+                confidence: 0.95,
+                ..DeviceCalibration::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        assert_eq!(fnv1a_64(b"a"), 0xaf63dc4c8601ec8c);
+
+        let export = CalibrationExport::new(profile()).unwrap();
+        let json = export.to_json().unwrap();
+        assert!(json.contains(CALIBRATION_EXPORT_FORMAT));
+        assert_eq!(CalibrationExport::from_json(&json).unwrap(), export);
+    }
+
+    #[test]
+    fn test_rejects_tampered_exports() {
+        let json = CalibrationExport::new(profile()).unwrap().to_json().unwrap();
+
+        let tampered = json.replace("3.63", "3.73");
+        assert!(CalibrationExport::from_json(&tampered).unwrap_err().contains("checksum"));
+        let foreign = json.replace(CALIBRATION_EXPORT_FORMAT, "something-else");
+        assert!(CalibrationExport::from_json(&foreign).unwrap_err().contains("Not a calibration"));
+        let newer = json.replace("\"schema_version\": 1", "\"schema_version\": 2");
+        assert!(CalibrationExport::from_json(&newer).is_err());
+    }
+
+    #[test]
+    fn test_rejects_implausible_calibrations() {
+        // A correct checksum says nothing about the values, since anyone can compute one
+        let mut absurd = profile();
+        absurd.calibration.scale_y = 40.0;
+        let json = CalibrationExport::new(absurd).unwrap().to_json().unwrap();
+        assert!(CalibrationExport::from_json(&json).unwrap_err().contains("implausible scale"));
+
+        let mut overconfident = profile();
+        overconfident.calibration.confidence = 1.5;
+        let json = CalibrationExport::new(overconfident).unwrap().to_json().unwrap();
+        assert!(CalibrationExport::from_json(&json).unwrap_err().contains("confidence"));
+    }
+
+    #[test]
+    fn test_parse_provisioned() {
+        let json = CalibrationExport::new(profile()).unwrap().to_json().unwrap();
+        let bundle = format!("[{}]", json);
+        assert_eq!(parse_provisioned(&bundle).unwrap(), vec![profile()]);
+        assert!(parse_provisioned("[]").unwrap().is_empty());
+
+        let broken = format!("[{}, {{\"format\": \"x\"}}]", json);
+        assert!(parse_provisioned(&broken).unwrap_err().starts_with("Provisioned calibration 2"));
+    }
+}
@@ -7,7 +7,13 @@
 //  ╚══╝╚══╝ ╚═╝╚══════╝╚═╝  ╚═╝╚═╝  ╚═╝╚═════╝      ╚═════╝ ╚═╝
 //                                          app/src/calibration_wizard.rs
 
-use crate::calibration::{CalibrationManager, CalibrationStep};
+use crate::calibration::{
+    Axis, CalibrationManager, CalibrationMethod, CalibrationStep, ReferenceShape, VerificationCheck,
+    ACCURACY_TARGET_MM, ID1_CARD_HEIGHT_MM, ID1_CARD_WIDTH_MM,
+};
+use crate::coordinates::CSS_PX_PER_MM;
+use crate::display_profiles::{current_display, CalibrationProfiles, DisplayFingerprint, ProfileStore};
+use crate::file_io::{download_text, read_file_text, take_selected_file};
 use yew::prelude::*;
 use web_sys::{HtmlInputElement, MouseEvent};
 
@@ -20,8 +26,20 @@ pub struct CalibrationWizardProps {
 pub struct CalibrationWizard {
     manager: CalibrationManager,
     measurement_input: NodeRef,
-    measuring_start_x: Option<f64>,
-    measuring_end_x: Option<f64>,
+    measuring_start: Option<(f64, f64)>,
+    measuring_end: Option<(f64, f64)>,
+    /// On-screen card rectangle in CSS pixels
+    card_size: (f64, f64),
+    /// Pointer position and card size when a resize drag started
+    card_resize_start: Option<((f64, f64), (f64, f64))>,
+    profiles: CalibrationProfiles,
+    profile_name_input: NodeRef,
+    /// Entered width and height deviations per `ReferenceShape::ALL`, kept as typed
+    deviations: Vec<(String, String)>,
+    /// Shapes ticked as exactly right, per `ReferenceShape::ALL`
+    confirmed_shapes: Vec<bool>,
+    /// Outcome of the last verification adjustment
+    verification_note: Option<String>,
 }
 
 pub enum CalibrationMsg {
@@ -31,9 +49,24 @@ pub enum CalibrationMsg {
     ProcessInput,
     StartMeasuring(MouseEvent),
     EndMeasuring(MouseEvent),
+    ClearSamples(Axis),
+    UseCard,
+    StartCardResize(MouseEvent),
+    CardResize(MouseEvent),
+    EndCardResize,
+    AcceptCard,
     CompleteCalibration,
     Cancel,
     ResetCalibration,
+    RenameProfile(DisplayFingerprint),
+    ForgetProfile(DisplayFingerprint),
+    ExportProfile(DisplayFingerprint),
+    ImportProfile(web_sys::File),
+    ProfileFileRead(Result<String, String>),
+    SetDeviation(usize, Axis, String),
+    ConfirmShape(usize, bool),
+    ApplyVerification,
+    FinishVerification,
 }
 
 impl Component for CalibrationWizard {
@@ -44,8 +77,15 @@ impl Component for CalibrationWizard {
         Self {
             manager: CalibrationManager::new(),
             measurement_input: NodeRef::default(),
-            measuring_start_x: None,
-            measuring_end_x: None,
+            measuring_start: None,
+            measuring_end: None,
+            card_size: nominal_card_size(),
+            card_resize_start: None,
+            profiles: ProfileStore::load(),
+            profile_name_input: NodeRef::default(),
+            deviations: vec![(String::new(), String::new()); ReferenceShape::ALL.len()],
+            confirmed_shapes: vec![false; ReferenceShape::ALL.len()],
+            verification_note: None,
         }
     }
 
@@ -56,10 +96,15 @@ impl Component for CalibrationWizard {
                 true
             }
             CalibrationMsg::NextStep => {
-                match self.manager.get_state().step {
-                    CalibrationStep::Instructions => {
-                        // Move to measuring step
-                        self.manager.state.step = CalibrationStep::Measuring;
+                let state = self.manager.get_state();
+                match state.step {
+                    // Move to measuring step
+                    CalibrationStep::Instructions => self.manager.state.step = CalibrationStep::Measuring,
+                    CalibrationStep::Measuring if state.estimated_scale.is_some() => {
+                        self.manager.state.step = CalibrationStep::MeasuringVertical;
+                    }
+                    CalibrationStep::MeasuringVertical if state.estimated_scale_y.is_some() => {
+                        self.manager.state.step = CalibrationStep::Validation;
                     }
                     _ => {}
                 }
@@ -73,9 +118,18 @@ impl Component for CalibrationWizard {
                     CalibrationStep::Measuring => {
                         self.manager.state.step = CalibrationStep::Instructions;
                     }
-                    CalibrationStep::Validation => {
+                    CalibrationStep::MeasuringVertical => {
                         self.manager.state.step = CalibrationStep::Measuring;
                     }
+                    CalibrationStep::CardMatching => {
+                        self.manager.state.step = CalibrationStep::Instructions;
+                    }
+                    CalibrationStep::Validation => {
+                        self.manager.state.step = match self.manager.get_state().method {
+                            CalibrationMethod::Ruler => CalibrationStep::MeasuringVertical,
+                            CalibrationMethod::Card => CalibrationStep::CardMatching,
+                        };
+                    }
                     _ => {}
                 }
                 true
@@ -83,9 +137,10 @@ impl Component for CalibrationWizard {
             CalibrationMsg::ProcessInput => {
                 if let Some(input) = self.measurement_input.cast::<HtmlInputElement>() {
                     if let Ok(pixels) = input.value().parse::<f64>() {
-                        match self.manager.process_measurement(pixels) {
+                        match self.process_step_measurement(pixels) {
                             Ok(_) => {
                                 self.manager.state.error_message = None;
+                                input.set_value("");
                             }
                             Err(error) => {
                                 self.manager.state.error_message = Some(error);
@@ -98,16 +153,21 @@ impl Component for CalibrationWizard {
                 true
             }
             CalibrationMsg::StartMeasuring(event) => {
-                self.measuring_start_x = Some(event.client_x() as f64);
-                self.measuring_end_x = None;
+                self.measuring_start = Some((event.client_x() as f64, event.client_y() as f64));
+                self.measuring_end = None;
                 true
             }
             CalibrationMsg::EndMeasuring(event) => {
-                if let Some(start_x) = self.measuring_start_x {
-                    let end_x = event.client_x() as f64;
-                    let measured_pixels = (end_x - start_x).abs();
+                if let Some((start_x, start_y)) = self.measuring_start {
+                    let end = (event.client_x() as f64, event.client_y() as f64);
+                    // The wizard is never zoomed, so client pixels are CSS pixels
+                    let measured_pixels = if self.manager.get_state().step == CalibrationStep::MeasuringVertical {
+                        (end.1 - start_y).abs()
+                    } else {
+                        (end.0 - start_x).abs()
+                    };
                     
-                    match self.manager.process_measurement(measured_pixels) {
+                    match self.process_step_measurement(measured_pixels) {
                         Ok(_) => {
                             self.manager.state.error_message = None;
                         }
@@ -116,15 +176,59 @@ impl Component for CalibrationWizard {
                         }
                     }
                     
-                    self.measuring_start_x = None;
-                    self.measuring_end_x = Some(end_x);
+                    self.measuring_start = None;
+                    self.measuring_end = Some(end);
+                }
+                true
+            }
+            CalibrationMsg::ClearSamples(axis) => {
+                self.manager.clear_samples(axis);
+                true
+            }
+            CalibrationMsg::UseCard => {
+                self.manager.state.step = CalibrationStep::CardMatching;
+                self.manager.state.error_message = None;
+                true
+            }
+            CalibrationMsg::StartCardResize(event) => {
+                let pointer = (event.client_x() as f64, event.client_y() as f64);
+                self.card_resize_start = Some((pointer, self.card_size));
+                event.prevent_default();
+                false
+            }
+            CalibrationMsg::CardResize(event) => {
+                let Some(((start_x, start_y), (width, height))) = self.card_resize_start else {
+                    return false;
+                };
+                // The handle sits on the bottom-right corner, so pointer deltas grow the card
+                self.card_size = (
+                    (width + event.client_x() as f64 - start_x).max(50.0),
+                    (height + event.client_y() as f64 - start_y).max(30.0),
+                );
+                true
+            }
+            CalibrationMsg::EndCardResize => {
+                self.card_resize_start = None;
+                false
+            }
+            CalibrationMsg::AcceptCard => {
+                let (width, height) = self.card_size;
+                match self.manager.process_card_measurement(width, height) {
+                    Ok(_) => {
+                        self.manager.state.error_message = None;
+                    }
+                    Err(error) => {
+                        self.manager.state.error_message = Some(error);
+                    }
                 }
                 true
             }
             CalibrationMsg::CompleteCalibration => {
                 match self.manager.complete_calibration() {
                     Ok(_) => {
-                        ctx.props().on_complete.emit(());
+                        // Saved; now check it against real objects before handing over
+                        self.profiles = ProfileStore::load();
+                        self.manager.state.step = CalibrationStep::Verification;
                     }
                     Err(error) => {
                         self.manager.state.error_message = Some(error);
@@ -140,6 +244,105 @@ impl Component for CalibrationWizard {
             }
             CalibrationMsg::ResetCalibration => {
                 self.manager.reset_calibration();
+                self.profiles = ProfileStore::load();
+                true
+            }
+            CalibrationMsg::RenameProfile(display) => {
+                let Some(input) = self.profile_name_input.cast::<HtmlInputElement>() else {
+                    return false;
+                };
+                let result = self.profiles.rename(&display, &input.value())
+                    .and_then(|_| ProfileStore::save(&self.profiles));
+                self.manager.state.error_message = result.err();
+                true
+            }
+            CalibrationMsg::ForgetProfile(display) => {
+                // Forgetting the current display's profile leaves it uncalibrated
+                if current_display().is_some_and(|current| current.matches(&display)) {
+                    self.manager.reset_calibration();
+                } else if self.profiles.remove(&display) {
+                    self.manager.state.error_message = ProfileStore::save(&self.profiles).err();
+                }
+                self.profiles = ProfileStore::load();
+                true
+            }
+            CalibrationMsg::ExportProfile(display) => {
+                let filename = format!("calibration-{}x{}.json", display.screen_width, display.screen_height);
+                let result = CalibrationManager::export_profile(&display)
+                    .and_then(|json| download_text(&filename, "application/json", &json));
+                self.manager.state.error_message = result.err();
+                true
+            }
+            CalibrationMsg::ImportProfile(file) => {
+                ctx.link().send_future(async move {
+                    CalibrationMsg::ProfileFileRead(read_file_text(file).await)
+                });
+                false
+            }
+            CalibrationMsg::SetDeviation(idx, axis, value) => {
+                if let Some((width, height)) = self.deviations.get_mut(idx) {
+                    *match axis {
+                        Axis::Horizontal => width,
+                        Axis::Vertical => height,
+                    } = value;
+                }
+                false
+            }
+            CalibrationMsg::ConfirmShape(idx, confirmed) => {
+                if let Some(shape) = self.confirmed_shapes.get_mut(idx) {
+                    *shape = confirmed;
+                }
+                false
+            }
+            CalibrationMsg::ApplyVerification => {
+                let checks = match self.verification_checks() {
+                    Ok(checks) => checks,
+                    Err(error) => {
+                        self.manager.state.error_message = Some(error);
+                        return true;
+                    }
+                };
+                let before = self.manager.get_calibration().cloned();
+                match (before, self.manager.complete_verification(&checks)) {
+                    (Some(before), Ok(after)) => {
+                        self.manager.state.error_message = None;
+                        self.profiles = ProfileStore::load();
+                        if checks.iter().all(VerificationCheck::is_exact) {
+                            ctx.props().on_complete.emit(());
+                        } else {
+                            // Redrawn at the new scale; they can be checked again
+                            self.deviations = vec![(String::new(), String::new()); ReferenceShape::ALL.len()];
+                            self.confirmed_shapes = vec![false; ReferenceShape::ALL.len()];
+                            self.verification_note = Some(format!(
+                                "Scale adjusted by {:+.2}% horizontally and {:+.2}% vertically ({:.0}% confidence). \
+                                 The shapes have been redrawn: check them again or finish.",
+                                (after.scale_x / before.scale_x - 1.0) * 100.0,
+                                (after.scale_y / before.scale_y - 1.0) * 100.0,
+                                after.confidence * 100.0
+                            ));
+                        }
+                    }
+                    (_, Err(error)) => self.manager.state.error_message = Some(error),
+                    (None, Ok(_)) => {}
+                }
+                true
+            }
+            CalibrationMsg::FinishVerification => {
+                ctx.props().on_complete.emit(());
+                false
+            }
+            CalibrationMsg::ProfileFileRead(text) => {
+                match text.and_then(|json| CalibrationManager::import_profile(&json)) {
+                    Ok(profile) => {
+                        self.manager.state.error_message = None;
+                        self.profiles = ProfileStore::load();
+                        // A profile for this display is ready to use straight away
+                        if current_display().is_some_and(|current| current.matches(&profile.display)) {
+                            ctx.props().on_complete.emit(());
+                        }
+                    }
+                    Err(error) => self.manager.state.error_message = Some(error),
+                }
                 true
             }
         }
@@ -157,7 +360,10 @@ impl Component for CalibrationWizard {
                         CalibrationStep::Welcome => self.render_welcome(ctx),
                         CalibrationStep::Instructions => self.render_instructions(ctx),
                         CalibrationStep::Measuring => self.render_measuring(ctx),
+                        CalibrationStep::MeasuringVertical => self.render_measuring_vertical(ctx),
+                        CalibrationStep::CardMatching => self.render_card_matching(ctx),
                         CalibrationStep::Validation => self.render_validation(ctx),
+                        CalibrationStep::Verification => self.render_verification(ctx),
                         CalibrationStep::Complete => self.render_complete(ctx),
                     }}
                 </div>
@@ -170,6 +376,58 @@ impl Component for CalibrationWizard {
 }
 
 impl CalibrationWizard {
+    /// Feed a measurement to the axis the current step is measuring
+    fn process_step_measurement(&mut self, pixels: f64) -> Result<(), String> {
+        self.manager.add_sample(self.step_axis(), pixels)
+    }
+
+    fn step_axis(&self) -> Axis {
+        if self.manager.get_state().step == CalibrationStep::MeasuringVertical {
+            Axis::Vertical
+        } else {
+            Axis::Horizontal
+        }
+    }
+
+    /// Running statistics for the readings taken so far on an axis
+    fn render_sample_stats(&self, ctx: &Context<Self>, axis: Axis) -> Html {
+        let Some(stats) = self.manager.sample_stats(axis) else {
+            return html! {
+                <p class="measurement-hint">{"Take at least three readings for a reliable result."}</p>
+            };
+        };
+
+        html! {
+            <div class="measurement-result">
+                <p>{format!(
+                    "{} reading{}{}: mean {:.1} pixels for 100mm ({:.1} DPI)",
+                    stats.samples,
+                    if stats.samples == 1 { "" } else { "s" },
+                    if stats.rejected > 0 { format!(", {} rejected as outliers", stats.rejected) } else { String::new() },
+                    stats.mean_px,
+                    stats.scale * 25.4
+                )}</p>
+                { match stats.ci95_mm {
+                    Some(ci) => html! {
+                        <p class={if stats.meets_target() { "status-good" } else { "status-warning" }}>
+                            {format!(
+                                "Spread ±{:.2}mm · 95% confidence ±{:.2}mm (target ±{}mm)",
+                                stats.std_dev_mm, ci, ACCURACY_TARGET_MM
+                            )}
+                        </p>
+                    },
+                    None => html! { <p>{"Repeat the measurement to check its accuracy."}</p> },
+                }}
+                <button
+                    class="secondary-button"
+                    onclick={ctx.link().callback(move |_| CalibrationMsg::ClearSamples(axis))}
+                >
+                    {"Clear readings"}
+                </button>
+            </div>
+        }
+    }
+
     fn render_header(&self) -> Html {
         html! {
             <header class="calibration-header">
@@ -203,6 +461,80 @@ impl CalibrationWizard {
                 >
                     {"Start Calibration"}
                 </button>
+
+                { self.render_profiles(ctx) }
+            </div>
+        }
+    }
+
+    /// Stored per-display calibrations; the one for this display can be renamed
+    fn render_profiles(&self, ctx: &Context<Self>) -> Html {
+        let current = current_display();
+        let import = html! {
+            <label class="secondary-button profile-import" title="Load a calibration exported on a workstation with the same monitor">
+                {"Import calibration…"}
+                <input
+                    type="file"
+                    accept=".json"
+                    onchange={ctx.link().batch_callback(|e: Event| {
+                        take_selected_file(&e).map(CalibrationMsg::ImportProfile)
+                    })}
+                />
+            </label>
+        };
+        if self.profiles.profiles.is_empty() {
+            return html! { <div class="display-profiles">{ import }</div> };
+        }
+
+        html! {
+            <div class="display-profiles">
+                <h3>{"Calibrated displays"}</h3>
+                <ul>
+                    { for self.profiles.profiles.iter().map(|profile| {
+                        let display = profile.display;
+                        let is_current = current.is_some_and(|current| current.matches(&display));
+                        let calibration = &profile.calibration;
+                        html! {
+                            <li class={if is_current { "display-profile current" } else { "display-profile" }}>
+                                { if is_current {
+                                    html! {
+                                        <>
+                                            <input
+                                                ref={self.profile_name_input.clone()}
+                                                type="text"
+                                                value={profile.name.clone()}
+                                            />
+                                            <button onclick={ctx.link().callback(move |_| CalibrationMsg::RenameProfile(display))}>
+                                                {"Rename"}
+                                            </button>
+                                            <span class="profile-current">{"This display"}</span>
+                                        </>
+                                    }
+                                } else {
+                                    html! { <span class="profile-name">{&profile.name}</span> }
+                                }}
+                                <span class="profile-meta">
+                                    {format!(
+                                        "{:.1} × {:.1} DPI · {:.0}% confidence",
+                                        calibration.scale_x * 25.4,
+                                        calibration.scale_y * 25.4,
+                                        calibration.confidence * 100.0
+                                    )}
+                                </span>
+                                <button
+                                    title="Download this calibration for workstations with the same monitor"
+                                    onclick={ctx.link().callback(move |_| CalibrationMsg::ExportProfile(display))}
+                                >
+                                    {"Export"}
+                                </button>
+                                <button onclick={ctx.link().callback(move |_| CalibrationMsg::ForgetProfile(display))}>
+                                    {"Forget"}
+                                </button>
+                            </li>
+                        }
+                    })}
+                </ul>
+                { import }
             </div>
         }
     }
@@ -225,9 +557,13 @@ impl CalibrationWizard {
                         <li>{"Place your ruler against the screen"}</li>
                         <li>{"Align the 0mm mark with the left edge of the ruler graphic"}</li>
                         <li>{"Click and drag to measure exactly 100mm"}</li>
+                        <li>{"Repeat a few times: each reading is averaged and stray ones are discarded"}</li>
+                        <li>{"Do the same with the ruler held vertically"}</li>
                         <li>{"We'll calculate your display's pixel density"}</li>
                     </ol>
                     
+                    <p>{"No ruler? Any bank, ID or loyalty card works too: they all share the same size."}</p>
+
                     <div class="tip">
                         <strong>{"💡 Tip:"}</strong> {" For best results, ensure your browser 
                         is at 100% zoom and your display brightness is comfortable."}
@@ -282,16 +618,107 @@ impl CalibrationWizard {
                     </div>
                 </div>
                 
-                { if let Some(pixels) = self.manager.get_state().measured_pixels {
-                    html! {
-                        <div class="measurement-result">
-                            <p>{format!("Measured: {:.1} pixels for 100mm", pixels)}</p>
-                            { if let Some(dpi) = self.manager.get_estimated_dpi() {
-                                html! { <p>{format!("Estimated DPI: {:.1}", dpi)}</p> }
-                            } else { html! {} }}
-                        </div>
-                    }
+                { self.render_sample_stats(ctx, Axis::Horizontal) }
+            </div>
+        }
+    }
+
+    fn render_card_matching(&self, ctx: &Context<Self>) -> Html {
+        let (width, height) = self.card_size;
+
+        html! {
+            <div
+                class="measuring-step"
+                onmousemove={ctx.link().callback(CalibrationMsg::CardResize)}
+                onmouseup={ctx.link().callback(|_| CalibrationMsg::EndCardResize)}
+                onmouseleave={ctx.link().callback(|_| CalibrationMsg::EndCardResize)}
+            >
+                <div class="step-icon">{"💳"}</div>
+                <h2>{"Match a card"}</h2>
+
+                <div class="ruler-container">
+                    <div class="ruler-instructions">
+                        {format!(
+                            "Hold a bank or ID card against the screen and drag the corner until the outline matches its edges ({} × {} mm)",
+                            ID1_CARD_WIDTH_MM, ID1_CARD_HEIGHT_MM
+                        )}
+                    </div>
+
+                    <div
+                        class="card-outline"
+                        style={format!("width: {}px; height: {}px", width, height)}
+                    >
+                        <div
+                            class="card-resize-handle"
+                            onmousedown={ctx.link().callback(CalibrationMsg::StartCardResize)}
+                        ></div>
+                    </div>
+                </div>
+
+                <p class="measurement-result">{format!("Card: {:.1} × {:.1} pixels", width, height)}</p>
+
+                <div class="action-buttons">
+                    <button
+                        class="primary-button"
+                        onclick={ctx.link().callback(|_| CalibrationMsg::AcceptCard)}
+                    >
+                        {"Use this size"}
+                    </button>
+                </div>
+            </div>
+        }
+    }
+
+    fn render_measuring_vertical(&self, ctx: &Context<Self>) -> Html {
+        let start_measuring = ctx.link().callback(CalibrationMsg::StartMeasuring);
+        let end_measuring = ctx.link().callback(CalibrationMsg::EndMeasuring);
+        let process_input = ctx.link().callback(|_| CalibrationMsg::ProcessInput);
+
+        html! {
+            <div class="measuring-step">
+                <div class="step-icon">{"↕️"}</div>
+                <h2>{"Measure 100mm vertically"}</h2>
+                
+                { if let Some(dpi) = self.manager.get_estimated_dpi() {
+                    html! { <p class="measurement-result">{format!("Horizontal DPI: {:.1}", dpi)}</p> }
                 } else { html! {} }}
+
+                <div class="ruler-container">
+                    <div class="ruler-instructions">
+                        {"Hold your ruler upright against this ruler and drag from 0mm down to 100mm"}
+                    </div>
+                    
+                    <div 
+                        class="digital-ruler vertical"
+                        onmousedown={start_measuring}
+                        onmouseup={end_measuring}
+                    >
+                        <div class="ruler-markings">
+                            { for (0..=10).map(|i| html! {
+                                <div class="ruler-mark" style={format!("top: {}%", i * 10)}>
+                                    <span class="ruler-label">{format!("{}cm", i)}</span>
+                                </div>
+                            })}
+                        </div>
+                        <div class="ruler-line"></div>
+                    </div>
+                </div>
+                
+                <div class="manual-input">
+                    <p>{"Or enter the pixel measurement manually:"}</p>
+                    <div class="input-group">
+                        <input 
+                            ref={self.measurement_input.clone()}
+                            type="number" 
+                            placeholder="Pixels for 100mm, vertically"
+                            min="50"
+                            max="1000"
+                        />
+                        <button onclick={process_input}>{"Process"}</button>
+                    </div>
+                </div>
+
+                { self.render_sample_stats(ctx, Axis::Vertical) }
             </div>
         }
     }
@@ -313,9 +740,33 @@ impl CalibrationWizard {
                         html! {
                             <div class="measurement-summary">
                                 <h3>{"Your Measurement"}</h3>
-                                <p>{format!("{:.1} pixels = 100mm", pixels)}</p>
-                                { if let Some(dpi) = self.manager.get_estimated_dpi() {
-                                    html! { <p>{format!("Display DPI: {:.1}", dpi)}</p> }
+                                { if state.method == CalibrationMethod::Card {
+                                    let (width, height) = self.card_size;
+                                    html! { <p>{format!("Card matched at {:.1} × {:.1} pixels", width, height)}</p> }
+                                } else {
+                                    html! {
+                                        <>
+                                            <p>{format!("{:.1} pixels = 100mm horizontally", pixels)}</p>
+                                            { if let Some(pixels_y) = state.measured_pixels_y {
+                                                html! { <p>{format!("{:.1} pixels = 100mm vertically", pixels_y)}</p> }
+                                            } else { html! {} }}
+                                        </>
+                                    }
+                                }}
+                                { match (self.manager.get_estimated_dpi(), self.manager.get_estimated_dpi_y()) {
+                                    (Some(dpi_x), Some(dpi_y)) => html! { <p>{format!("Display DPI: {:.1} × {:.1}", dpi_x, dpi_y)}</p> },
+                                    (Some(dpi), None) => html! { <p>{format!("Display DPI: {:.1}", dpi)}</p> },
+                                    _ => html! {},
+                                }}
+                                { for [(Axis::Horizontal, "horizontally"), (Axis::Vertical, "vertically")].into_iter().filter_map(|(axis, label)| {
+                                    let stats = self.manager.sample_stats(axis)?;
+                                    let ci = stats.ci95_mm?;
+                                    Some(html! {
+                                        <p>{format!("±{:.2}mm {} at 95% confidence from {} readings", ci, label, stats.samples)}</p>
+                                    })
+                                })}
+                                { if self.manager.sample_stats(Axis::Horizontal).is_some_and(|stats| stats.ci95_mm.is_none()) {
+                                    html! { <p>{"One reading can't show how repeatable it is; take a few more to raise the score."}</p> }
                                 } else { html! {} }}
                             </div>
                         }
@@ -366,7 +817,139 @@ impl CalibrationWizard {
         }
     }
 
-    fn render_complete(&self, _ctx: &Context<Self>) -> Html {
+    /// Reference shapes drawn at their physical size with the saved calibration
+    fn render_verification(&self, ctx: &Context<Self>) -> Html {
+        let Some(coord_system) = self.manager.get_coordinate_system() else {
+            return html! {};
+        };
+
+        html! {
+            <div class="verification-step">
+                <div class="step-icon">{"🔍"}</div>
+                <h2>{"Check Your Calibration"}</h2>
+                <p class="description">
+                    {"Measure each shape with a ruler, or lay a card on the outline. Tick \"Exactly \
+                      right\" when a shape matches, or enter how many millimetres larger (+) or \
+                      smaller (−) it measures. Shapes left blank aren't used."}
+                </p>
+
+                <div class="reference-shapes">
+                    { for ReferenceShape::ALL.iter().zip(&self.deviations).enumerate().map(|(idx, (shape, (dx, dy)))| {
+                        let confirmed = self.confirmed_shapes.get(idx).copied().unwrap_or(false);
+                        let (width_mm, height_mm) = shape.size_mm();
+                        let mut style = format!(
+                            "width: {:.2}px; height: {:.2}px;",
+                            coord_system.mm_to_px_x(width_mm),
+                            coord_system.mm_to_px_y(height_mm)
+                        );
+                        match shape {
+                            ReferenceShape::Circle => style.push_str(" border-radius: 50%;"),
+                            ReferenceShape::Card => style.push_str(&format!(
+                                " border-radius: {:.2}px;",
+                                coord_system.mm_to_px(CARD_CORNER_RADIUS_MM)
+                            )),
+                            ReferenceShape::Square => {}
+                        }
+                        html! {
+                            <div class="reference-shape">
+                                <div class="reference-outline" style={style}></div>
+                                <h4>{shape.label()}</h4>
+                                <label>
+                                    <input
+                                        type="checkbox"
+                                        checked={confirmed}
+                                        onchange={ctx.link().callback(move |e: Event| {
+                                            let input: HtmlInputElement = e.target_unchecked_into();
+                                            CalibrationMsg::ConfirmShape(idx, input.checked())
+                                        })}
+                                    />
+                                    {" Exactly right"}
+                                </label>
+                                <label>
+                                    { if shape.measured_vertically() { "Width ±mm " } else { "Diameter across ±mm " } }
+                                    { self.render_deviation_input(ctx, idx, Axis::Horizontal, dx) }
+                                </label>
+                                { if shape.measured_vertically() { html! {
+                                    <label>
+                                        {"Height ±mm "}
+                                        { self.render_deviation_input(ctx, idx, Axis::Vertical, dy) }
+                                    </label>
+                                }} else { html! {} }}
+                            </div>
+                        }
+                    })}
+                </div>
+
+                { if let Some(note) = &self.verification_note {
+                    html! { <p class="verification-note">{note}</p> }
+                } else { html! {} }}
+
+                <div class="action-buttons">
+                    <button
+                        class="primary-button"
+                        onclick={ctx.link().callback(|_| CalibrationMsg::ApplyVerification)}
+                    >
+                        {"Apply Measurements"}
+                    </button>
+                    <button
+                        class="secondary-button"
+                        onclick={ctx.link().callback(|_| CalibrationMsg::FinishVerification)}
+                    >
+                        {"Finish"}
+                    </button>
+                </div>
+            </div>
+        }
+    }
+
+    fn render_deviation_input(&self, ctx: &Context<Self>, idx: usize, axis: Axis, value: &str) -> Html {
+        html! {
+            <input
+                type="number"
+                step="0.1"
+                placeholder="0"
+                value={value.to_string()}
+                oninput={ctx.link().callback(move |e: InputEvent| {
+                    let input: HtmlInputElement = e.target_unchecked_into();
+                    CalibrationMsg::SetDeviation(idx, axis, input.value())
+                })}
+            />
+        }
+    }
+
+    /// Checks from the entered deviations. A blank side counts as exactly right only on
+    /// a shape ticked as such; shapes with nothing entered or ticked are left out.
+    fn verification_checks(&self) -> Result<Vec<VerificationCheck>, String> {
+        let mut checks = Vec::new();
+        for ((&shape, (dx, dy)), &confirmed) in
+            ReferenceShape::ALL.iter().zip(&self.deviations).zip(&self.confirmed_shapes)
+        {
+            let parse = |value: &str| {
+                let value = value.trim();
+                if value.is_empty() {
+                    return Ok(confirmed.then_some(0.0));
+                }
+                value
+                    .parse::<f64>()
+                    .map(Some)
+                    .map_err(|_| format!("{}: '{}' is not a number", shape.label(), value))
+            };
+            let check = VerificationCheck {
+                shape,
+                deviation_x_mm: parse(dx)?,
+                deviation_y_mm: if shape.measured_vertically() { parse(dy)? } else { None },
+            };
+            if check.deviation_x_mm.is_some() || check.deviation_y_mm.is_some() {
+                checks.push(check);
+            }
+        }
+        if checks.is_empty() {
+            return Err("Tick or measure at least one shape".to_string());
+        }
+        Ok(checks)
+    }
+
+    fn render_complete(&self, ctx: &Context<Self>) -> Html {
         html! {
             <div class="complete-step">
                 <div class="step-icon">{"🎉"}</div>
@@ -384,6 +967,13 @@ impl CalibrationWizard {
                         <li>{"✅ Verify print accuracy"}</li>
                     </ul>
                 </div>
+
+                <button
+                    class="secondary-button"
+                    onclick={ctx.link().callback(|_| CalibrationMsg::ResetCalibration)}
+                >
+                    {"Recalibrate"}
+                </button>
             </div>
         }
     }
@@ -408,6 +998,8 @@ impl CalibrationWizard {
             <nav class="calibration-navigation">
                 { match state.step {
                     CalibrationStep::Welcome => html! {},
+                    // Already saved; verification only refines it
+                    CalibrationStep::Verification => html! {},
                     CalibrationStep::Complete => html! {},
                     _ => html! {
                         <div class="nav-buttons">
@@ -419,14 +1011,31 @@ impl CalibrationWizard {
                             </button>
                             
                             { match state.step {
-                                CalibrationStep::Instructions => html! {
-                                    <button 
+                                CalibrationStep::Measuring | CalibrationStep::MeasuringVertical => html! {
+                                    <button
                                         class="nav-button next"
+                                        disabled={self.manager.sample_stats(self.step_axis()).is_none()}
                                         onclick={ctx.link().callback(|_| CalibrationMsg::NextStep)}
                                     >
                                         {"Next →"}
                                     </button>
                                 },
+                                CalibrationStep::Instructions => html! {
+                                    <>
+                                        <button
+                                            class="nav-button"
+                                            onclick={ctx.link().callback(|_| CalibrationMsg::UseCard)}
+                                        >
+                                            {"No ruler? Use a card"}
+                                        </button>
+                                        <button 
+                                            class="nav-button next"
+                                            onclick={ctx.link().callback(|_| CalibrationMsg::NextStep)}
+                                        >
+                                            {"Next →"}
+                                        </button>
+                                    </>
+                                },
                                 _ => html! {}
                             }}
                         </div>
@@ -446,4 +1055,12 @@ impl CalibrationWizard {
             </nav>
         }
     }
-}
\ No newline at end of file
+}
+
+/// Corner radius of an ID-1 card
+const CARD_CORNER_RADIUS_MM: f64 = 3.18;
+
+/// Card rectangle at the 96 DPI CSS reference size, as a starting point for matching
+fn nominal_card_size() -> (f64, f64) {
+    (ID1_CARD_WIDTH_MM * CSS_PX_PER_MM, ID1_CARD_HEIGHT_MM * CSS_PX_PER_MM)
+}
@@ -0,0 +1,332 @@
+//
+//  ██████╗ ██████╗ ███╗   ███╗██████╗
+// ██╔════╝██╔═══██╗████╗ ████║██╔══██╗
+// ██║     ██║   ██║██╔████╔██║██████╔╝
+// ██║     ██║   ██║██║╚██╔╝██║██╔══██╗
+// ╚██████╗╚██████╔╝██║ ╚═╝ ██║██████╔╝
+//  ╚═════╝ ╚═════╝ ╚═╝     ╚═╝╚═════╝
+//                      app/src/comb.rs
+
+use crate::afm::win_ansi_byte;
+use crate::coordinates::CSS_PX_PER_MM;
+use crate::font_metrics::{FontMetricsCalculator, StandardFont};
+use crate::form_data::{FieldValue, FormData};
+use crate::form_template::{FieldType, FormFieldInfo, FormTemplate};
+
+/// Character boxes of a comb field, one glyph per box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CombSettings {
+    pub cells: usize,
+    /// Width of each box, from the start of one to the start of the next
+    pub pitch_mm: f64,
+}
+
+impl CombSettings {
+    /// Settings of a comb field, if the template gives both a cell count and pitch
+    pub fn for_field(field: &FormFieldInfo) -> Option<Self> {
+        Some(Self { cells: field.comb_cells?, pitch_mm: field.comb_pitch_mm? })
+    }
+
+    /// Left edge of each glyph from the field's left edge, centred in its cell by its
+    /// advance width. Characters past the last cell have no box; see [`Self::overflow`].
+    pub fn glyph_offsets(
+        &self,
+        metrics: &mut FontMetricsCalculator,
+        font: &StandardFont,
+        size_pt: f64,
+        text: &str,
+    ) -> Vec<(char, f64)> {
+        text.chars()
+            .take(self.cells)
+            .enumerate()
+            .map(|(cell, c)| {
+                let glyph = c.to_string();
+                let advance_mm = metrics.measure_text_px(font, size_pt, &glyph) / CSS_PX_PER_MM;
+                (c, cell as f64 * self.pitch_mm + (self.pitch_mm - advance_mm) / 2.0)
+            })
+            .collect()
+    }
+
+    /// Number of characters in `text` past the last cell
+    pub fn overflow(&self, text: &str) -> usize {
+        text.chars().count().saturating_sub(self.cells)
+    }
+
+    /// Warning printed with a value too long for the boxes, carrying the whole value so
+    /// nothing is lost from the page
+    pub fn overflow_note(&self, text: &str) -> Option<String> {
+        match self.overflow(text) {
+            0 => None,
+            _ => Some(format!("(Does not fit {} boxes: {})", self.cells, text)),
+        }
+    }
+
+    /// Baseline below the top of a field `height_mm` tall, the same one single-line
+    /// values use
+    pub fn baseline_mm(font: &StandardFont, size_pt: f64, height_mm: f64) -> f64 {
+        font.centred_baseline_mm(size_pt, height_mm)
+    }
+
+    /// Cell under a point `x_mm` from the field's left edge
+    pub fn cell_at(&self, x_mm: f64) -> usize {
+        ((x_mm / self.pitch_mm).max(0.0) as usize).min(self.cells.saturating_sub(1))
+    }
+}
+
+/// Warnings for every comb field whose value has more characters than boxes, e.g. after
+/// importing data or restoring a draft saved against a different template
+pub fn overflow_warnings(template: &FormTemplate, form_data: &FormData) -> Vec<String> {
+    let fields = template.pages.iter().flat_map(|page| page.fields.iter());
+    fields
+        .filter(|field| field.field_type == FieldType::Comb)
+        .filter_map(|field| {
+            let comb = CombSettings::for_field(field)?;
+            let Some(FieldValue::Text(text)) = form_data.get(&field.id) else {
+                return None;
+            };
+            match comb.overflow(text) {
+                0 => None,
+                extra => Some(format!(
+                    "{}: {} characters for {} boxes, {} not shown",
+                    field.label,
+                    text.chars().count(),
+                    comb.cells,
+                    extra
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Keys a comb field responds to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CombKey {
+    Char(char),
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Home,
+    End,
+}
+
+impl CombKey {
+    /// Key for a DOM `KeyboardEvent.key` value
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "Backspace" => Some(CombKey::Backspace),
+            "Delete" => Some(CombKey::Delete),
+            "ArrowLeft" => Some(CombKey::Left),
+            "ArrowRight" => Some(CombKey::Right),
+            "Home" => Some(CombKey::Home),
+            "End" => Some(CombKey::End),
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some(CombKey::Char(c)),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+/// A comb field's value and the cell the cursor is on.
+///
+/// Typing fills the cursor's cell and moves to the next one, Backspace removes the
+/// character before the cursor (or in the last cell once every cell is full), and
+/// Delete removes the character at the cursor; later characters shift left. A value
+/// longer than the comb is kept whole, so editing never drops its extra characters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CombEditor {
+    chars: Vec<char>,
+    cursor: usize,
+    cells: usize,
+}
+
+impl CombEditor {
+    /// Editor over `value` with the cursor after the text
+    pub fn new(value: &str, cells: usize) -> Self {
+        let chars: Vec<char> = value.chars().collect();
+        let mut editor = Self { chars, cursor: 0, cells };
+        editor.cursor = editor.last_cursor();
+        editor
+    }
+
+    pub fn value(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Move to a cell, stopping at the first empty one
+    pub fn set_cursor(&mut self, cell: usize) {
+        self.cursor = cell.min(self.last_cursor());
+    }
+
+    /// Apply a key, returning whether the value changed
+    pub fn apply(&mut self, key: CombKey) -> bool {
+        match key {
+            CombKey::Char(c) => self.insert(&c.to_string()),
+            CombKey::Backspace => {
+                if self.chars.len() >= self.cells && self.cursor + 1 == self.cells {
+                    self.chars.pop();
+                } else if self.cursor > 0 {
+                    self.cursor -= 1;
+                    self.chars.remove(self.cursor);
+                } else {
+                    return false;
+                }
+                true
+            }
+            CombKey::Delete => {
+                if self.cursor >= self.chars.len() {
+                    return false;
+                }
+                self.chars.remove(self.cursor);
+                true
+            }
+            CombKey::Left => {
+                self.cursor = self.cursor.saturating_sub(1);
+                false
+            }
+            CombKey::Right => {
+                self.cursor = (self.cursor + 1).min(self.last_cursor());
+                false
+            }
+            CombKey::Home => {
+                self.cursor = 0;
+                false
+            }
+            CombKey::End => {
+                self.cursor = self.last_cursor();
+                false
+            }
+        }
+    }
+
+    /// Write pasted or composed text from the cursor on, overwriting one cell per
+    /// character. Characters that run past the last cell are kept as overflow rather
+    /// than overwriting each other; returns whether the value changed.
+    pub fn insert(&mut self, text: &str) -> bool {
+        let mut position = self.cursor;
+        for c in text.chars() {
+            if c.is_control() || win_ansi_byte(c).is_none() || self.cells == 0 {
+                continue;
+            }
+            if position < self.chars.len() {
+                self.chars[position] = c;
+            } else {
+                self.chars.push(c);
+            }
+            position += 1;
+        }
+
+        let changed = position != self.cursor;
+        self.cursor = position.min(self.last_cursor());
+        changed
+    }
+
+    /// The first empty cell, or the last cell once all are filled
+    fn last_cursor(&self) -> usize {
+        self.chars.len().min(self.cells.saturating_sub(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MM_PER_PT: f64 = 25.4 / 72.0;
+
+    #[test]
+    fn test_glyphs_centred_in_cells() {
+        let mut metrics = FontMetricsCalculator::new();
+        let font = StandardFont::Helvetica;
+        let comb = CombSettings { cells: 4, pitch_mm: 5.0 };
+        let offsets = comb.glyph_offsets(&mut metrics, &font, 12.0, "W1i-X");
+
+        // One glyph per cell; the fifth character has no cell
+        assert_eq!(offsets.iter().map(|(c, _)| *c).collect::<String>(), "W1i-");
+        for (cell, (c, offset)) in offsets.iter().enumerate() {
+            let advance = font.char_width_units(*c) / 1000.0 * 12.0 * MM_PER_PT;
+            let centre = offset + advance / 2.0;
+            assert!((centre - (cell as f64 + 0.5) * 5.0).abs() < 1e-9, "{}", c);
+        }
+        // Narrow glyphs sit further into their cell than wide ones
+        assert!(offsets[2].1 - 10.0 > offsets[0].1);
+
+        assert_eq!(comb.cell_at(7.4), 1);
+        assert_eq!(comb.cell_at(99.0), 3);
+    }
+
+    #[test]
+    fn test_editing_across_cells() {
+        let mut editor = CombEditor::new("", 4);
+        for c in "FL24".chars() {
+            assert!(editor.apply(CombKey::Char(c)));
+        }
+        // Full: the cursor stays on the last cell and typing overwrites it
+        assert_eq!((editor.value().as_str(), editor.cursor()), ("FL24", 3));
+        editor.apply(CombKey::Char('5'));
+        assert_eq!(editor.value(), "FL25");
+        assert!(!editor.apply(CombKey::Char('\n')));
+
+        // Backspace clears the last cell, then removes before the cursor
+        editor.apply(CombKey::Backspace);
+        assert_eq!((editor.value().as_str(), editor.cursor()), ("FL2", 3));
+        editor.apply(CombKey::Home);
+        editor.apply(CombKey::Right);
+        editor.apply(CombKey::Delete);
+        assert_eq!((editor.value().as_str(), editor.cursor()), ("F2", 1));
+        editor.apply(CombKey::Backspace);
+        assert_eq!((editor.value().as_str(), editor.cursor()), ("2", 0));
+        assert!(!editor.apply(CombKey::Backspace));
+
+        // Typing mid-value overwrites the cell rather than inserting
+        editor.apply(CombKey::Char('0'));
+        assert_eq!((editor.value().as_str(), editor.cursor()), ("0", 1));
+        editor.set_cursor(3);
+        assert_eq!(editor.cursor(), 1);
+        editor.apply(CombKey::Left);
+        editor.apply(CombKey::Char('1'));
+        assert_eq!(editor.value(), "1");
+
+        assert_eq!(CombKey::from_key("ArrowLeft"), Some(CombKey::Left));
+        assert_eq!(CombKey::from_key("é"), Some(CombKey::Char('é')));
+        assert_eq!(CombKey::from_key("Shift"), None);
+    }
+
+    #[test]
+    fn test_overflow_is_kept() {
+        let comb = CombSettings { cells: 4, pitch_mm: 5.0 };
+        assert_eq!(comb.overflow("FL-2024"), 3);
+        assert_eq!(comb.overflow_note("FL-2"), None);
+        assert_eq!(comb.overflow_note("FL-24").unwrap(), "(Does not fit 4 boxes: FL-24)");
+
+        // Editing a value that came in too long doesn't cut it to the boxes
+        let mut editor = CombEditor::new("FL-2024", 4);
+        assert_eq!(editor.cursor(), 3);
+        editor.apply(CombKey::Char('X'));
+        assert_eq!(editor.value(), "FL-X024");
+        editor.apply(CombKey::Delete);
+        assert_eq!(editor.value(), "FL-024");
+
+        // A paste fills cells from the cursor and keeps what runs past the last one
+        let mut editor = CombEditor::new("", 4);
+        assert!(editor.insert("AB\n12"));
+        assert_eq!((editor.value().as_str(), editor.cursor()), ("AB12", 3));
+        editor.set_cursor(2);
+        assert!(editor.insert("345"));
+        assert_eq!((editor.value().as_str(), editor.cursor()), ("AB345", 3));
+        assert!(!editor.insert("\u{2603}"));
+
+        let template = FormTemplate::fl100();
+        let mut data = FormData::for_template(&template);
+        data.set("case_number", FieldValue::Text("FL-2024-00123".to_string())).unwrap();
+        let warnings = overflow_warnings(&template, &data);
+        assert_eq!(warnings, vec!["Case Number: 13 characters for 12 boxes, 1 not shown"]);
+    }
+}
@@ -0,0 +1,283 @@
+//
+//  ██████╗ ██████╗ ███╗   ███╗██████╗    ██╗███╗   ██╗██████╗ ██╗   ██╗████████╗
+// ██╔════╝██╔═══██╗████╗ ████║██╔══██╗   ██║████╗  ██║██╔══██╗██║   ██║╚══██╔══╝
+// ██║     ██║   ██║██╔████╔██║██████╔╝   ██║██╔██╗ ██║██████╔╝██║   ██║   ██║
+// ██║     ██║   ██║██║╚██╔╝██║██╔══██╗   ██║██║╚██╗██║██╔═══╝ ██║   ██║   ██║
+// ╚██████╗╚██████╔╝██║ ╚═╝ ██║██████╔╝   ██║██║ ╚████║██║     ╚██████╔╝   ██║
+//  ╚═════╝ ╚═════╝ ╚═╝     ╚═╝╚═════╝    ╚═╝╚═╝  ╚═══╝╚═╝      ╚═════╝    ╚═╝
+//                                                          app/src/comb_input.rs
+
+use crate::comb::{CombEditor, CombKey, CombSettings};
+use crate::coordinates::{CoordinateSystem, PhysicalCoord, CSS_PX_PER_MM};
+use crate::font_metrics::{FontMetricsCalculator, StandardFont};
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{Event, HtmlInputElement};
+use yew::prelude::*;
+
+type Listener = Closure<dyn Fn(Event)>;
+
+#[derive(Properties, PartialEq)]
+pub struct CombInputProps {
+    pub id: String,
+    pub label: String,
+    pub position: PhysicalCoord,
+    pub settings: CombSettings,
+    pub height_mm: f64,
+    pub on_input: Callback<String>,
+    pub value: String,
+    pub font: Option<StandardFont>,
+    pub font_size_pt: Option<f64>,
+    pub coord_system: Option<CoordinateSystem>,
+}
+
+/// Row of character boxes, one character per box, with each glyph centred the way PDF
+/// export places it.
+///
+/// A transparent input over the boxes takes focus, so typed, pasted, IME-composed and
+/// on-screen keyboard text all arrive as `input` events; it is emptied after each one.
+/// Navigation and deletion keys are handled from `keydown`, with `beforeinput` covering
+/// deletions from keyboards that don't report their keys. Yew has no `beforeinput` or
+/// `compositionend` attributes, so those listeners are attached directly and removed
+/// when the component is destroyed.
+pub struct CombInput {
+    font_calculator: Rc<RefCell<FontMetricsCalculator>>,
+    capture_ref: NodeRef,
+    listeners: Vec<(&'static str, Listener)>,
+    cursor: usize,
+    focused: bool,
+}
+
+pub enum CombInputMsg {
+    Key(KeyboardEvent),
+    /// Deletion reported by `beforeinput`, already kept from reaching the input
+    Delete(CombKey),
+    /// Text that arrived in the capture input, once any IME composition has finished
+    Text,
+    SelectAt(MouseEvent),
+    Focus(bool),
+}
+
+impl Component for CombInput {
+    type Message = CombInputMsg;
+    type Properties = CombInputProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+        Self {
+            font_calculator: Rc::new(RefCell::new(FontMetricsCalculator::new())),
+            capture_ref: NodeRef::default(),
+            listeners: Vec::new(),
+            cursor: CombEditor::new(&props.value, props.settings.cells).cursor(),
+            focused: false,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            CombInputMsg::Key(e) => {
+                // Leave shortcuts such as undo to the document
+                if e.ctrl_key() || e.meta_key() || e.alt_key() {
+                    return false;
+                }
+                if e.is_composing() {
+                    return false;
+                }
+                // Characters come through the input event so paste and IME share one path
+                let key = match CombKey::from_key(&e.key()) {
+                    Some(CombKey::Char(_)) | None => return false,
+                    Some(key) => key,
+                };
+                e.prevent_default();
+                self.apply(props, |editor| editor.apply(key))
+            }
+            CombInputMsg::Delete(key) => self.apply(props, |editor| editor.apply(key)),
+            CombInputMsg::Text => {
+                let Some(input) = self.capture_ref.cast::<HtmlInputElement>() else {
+                    return false;
+                };
+                let text = input.value();
+                input.set_value("");
+                self.apply(props, |editor| editor.insert(&text))
+            }
+            CombInputMsg::SelectAt(e) => {
+                let x_mm = match &props.coord_system {
+                    Some(coord_system) => coord_system.px_to_mm_x(e.offset_x() as f64),
+                    None => e.offset_x() as f64 / CSS_PX_PER_MM,
+                };
+                let mut editor = CombEditor::new(&props.value, props.settings.cells);
+                editor.set_cursor(props.settings.cell_at(x_mm));
+                self.cursor = editor.cursor();
+                true
+            }
+            CombInputMsg::Focus(focused) => {
+                self.focused = focused;
+                true
+            }
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if !first_render {
+            return;
+        }
+        let Some(input) = self.capture_ref.cast::<HtmlInputElement>() else {
+            return;
+        };
+
+        let link = ctx.link().clone();
+        let before_input = Listener::new(move |e: Event| {
+            let Some(e) = e.dyn_ref::<InputEvent>() else {
+                return;
+            };
+            let key = match e.input_type().as_str() {
+                "deleteContentBackward" => CombKey::Backspace,
+                "deleteContentForward" => CombKey::Delete,
+                _ => return,
+            };
+            e.prevent_default();
+            link.send_message(CombInputMsg::Delete(key));
+        });
+        let link = ctx.link().clone();
+        let composition_end = Listener::new(move |_: Event| link.send_message(CombInputMsg::Text));
+
+        for (event, listener) in
+            [("beforeinput", before_input), ("compositionend", composition_end)]
+        {
+            match input.add_event_listener_with_callback(event, listener.as_ref().unchecked_ref()) {
+                Ok(()) => self.listeners.push((event, listener)),
+                Err(error) => web_sys::console::warn_1(&error),
+            }
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        if let Some(input) = self.capture_ref.cast::<HtmlInputElement>() {
+            for (event, listener) in &self.listeners {
+                let _ = input
+                    .remove_event_listener_with_callback(event, listener.as_ref().unchecked_ref());
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let settings = props.settings;
+        let font = props.font.clone().unwrap_or(StandardFont::Helvetica);
+        let font_size = props.font_size_pt.unwrap_or(12.0);
+
+        let to_px = |mm: f64| match &props.coord_system {
+            Some(coord_system) => coord_system.mm_to_px_x(mm),
+            None => mm * CSS_PX_PER_MM,
+        };
+        let (left, top) = match &props.coord_system {
+            Some(coord_system) => {
+                let screen = coord_system.physical_to_screen(props.position);
+                (screen.x, screen.y)
+            }
+            None => (props.position.x, props.position.y),
+        };
+        let height_px = match &props.coord_system {
+            Some(coord_system) => coord_system.mm_to_px_y(props.height_mm),
+            None => props.height_mm * CSS_PX_PER_MM,
+        };
+
+        let glyphs = match self.font_calculator.try_borrow_mut() {
+            Ok(mut calculator) => {
+                settings.glyph_offsets(&mut calculator, &font, font_size, &props.value)
+            }
+            Err(_) => Vec::new(),
+        };
+        let mut editor = CombEditor::new(&props.value, settings.cells);
+        editor.set_cursor(self.cursor);
+        let cursor = editor.cursor();
+
+        let style = format!(
+            "position: absolute; left: {}px; top: {}px; width: {}px;",
+            left,
+            top,
+            to_px(settings.pitch_mm * settings.cells as f64)
+        );
+        let glyph_style = format!(
+            "font-family: {}; font-weight: {}; font-size: {}pt; line-height: {}px;",
+            font.css_family(),
+            font.css_weight(),
+            font_size,
+            height_px
+        );
+
+        html! {
+            <div class="form-field comb-input" style={style}>
+                <label for={props.id.clone()}>{props.label.clone()}</label>
+                <div class="comb-cells" style={format!("height: {}px;", height_px)}>
+                    { for (0..settings.cells).map(|cell| html! {
+                        <div
+                            class={classes!(
+                                "comb-cell",
+                                (self.focused && cell == cursor).then_some("cursor")
+                            )}
+                            style={format!(
+                                "left: {}px; width: {}px;",
+                                to_px(cell as f64 * settings.pitch_mm),
+                                to_px(settings.pitch_mm)
+                            )}
+                        />
+                    })}
+                    { for glyphs.into_iter().map(|(c, offset)| html! {
+                        <span
+                            class="comb-glyph"
+                            style={format!("left: {}px; {}", to_px(offset), glyph_style)}
+                        >
+                            {c}
+                        </span>
+                    })}
+                    <input
+                        ref={self.capture_ref.clone()}
+                        type="text"
+                        id={props.id.clone()}
+                        class="comb-capture"
+                        autocomplete="off"
+                        autocapitalize="characters"
+                        spellcheck="false"
+                        onkeydown={ctx.link().callback(CombInputMsg::Key)}
+                        oninput={ctx.link().batch_callback(|e: InputEvent| {
+                            (!e.is_composing()).then_some(CombInputMsg::Text)
+                        })}
+                        onclick={ctx.link().callback(CombInputMsg::SelectAt)}
+                        onfocus={ctx.link().callback(|_| CombInputMsg::Focus(true))}
+                        onblur={ctx.link().callback(|_| CombInputMsg::Focus(false))}
+                    />
+                </div>
+                { match settings.overflow_note(&props.value) {
+                    // Kept rather than cut off, and shown whole the way print and export do
+                    Some(note) => html! {
+                        <span class="comb-overflow" title="Extra characters have no box">
+                            {note}
+                        </span>
+                    },
+                    None => html! {},
+                }}
+            </div>
+        }
+    }
+}
+
+impl CombInput {
+    /// Run an edit on the current value, emitting it if it changed, and keep the cursor
+    fn apply(
+        &mut self,
+        props: &CombInputProps,
+        edit: impl FnOnce(&mut CombEditor) -> bool,
+    ) -> bool {
+        let mut editor = CombEditor::new(&props.value, props.settings.cells);
+        editor.set_cursor(self.cursor);
+        if edit(&mut editor) {
+            props.on_input.emit(editor.value());
+        }
+        self.cursor = editor.cursor();
+        true
+    }
+}
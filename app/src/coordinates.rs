@@ -13,6 +13,112 @@ use serde::{Deserialize, Serialize};
 pub const US_LETTER_WIDTH_MM: f64 = 215.9;
 pub const US_LETTER_HEIGHT_MM: f64 = 279.4;
 
+/// US Legal page dimensions in millimeters
+pub const US_LEGAL_WIDTH_MM: f64 = 215.9;
+pub const US_LEGAL_HEIGHT_MM: f64 = 355.6;
+
+/// ISO A4 page dimensions in millimeters
+pub const A4_WIDTH_MM: f64 = 210.0;
+pub const A4_HEIGHT_MM: f64 = 297.0;
+
+/// Paper a form page is printed on
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum PaperSize {
+    #[default]
+    Letter,
+    Legal,
+    A4,
+    /// Portrait width and height in millimeters
+    Custom { width_mm: f64, height_mm: f64 },
+}
+
+impl PaperSize {
+    /// Portrait (width, height) in millimeters
+    pub fn portrait_mm(&self) -> (f64, f64) {
+        match *self {
+            PaperSize::Letter => (US_LETTER_WIDTH_MM, US_LETTER_HEIGHT_MM),
+            PaperSize::Legal => (US_LEGAL_WIDTH_MM, US_LEGAL_HEIGHT_MM),
+            PaperSize::A4 => (A4_WIDTH_MM, A4_HEIGHT_MM),
+            PaperSize::Custom { width_mm, height_mm } => (width_mm, height_mm),
+        }
+    }
+
+    /// Short name for labels and messages
+    pub fn name(&self) -> String {
+        match *self {
+            PaperSize::Letter => "Letter".to_string(),
+            PaperSize::Legal => "Legal".to_string(),
+            PaperSize::A4 => "A4".to_string(),
+            PaperSize::Custom { width_mm, height_mm } => format!("{} x {} mm", width_mm, height_mm),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Orientation {
+    #[default]
+    Portrait,
+    Landscape,
+}
+
+/// Paper size and orientation of a page; defaults to US Letter portrait
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct PageFormat {
+    pub paper: PaperSize,
+    #[serde(default)]
+    pub orientation: Orientation,
+}
+
+impl PageFormat {
+    pub fn new(paper: PaperSize, orientation: Orientation) -> Self {
+        Self { paper, orientation }
+    }
+
+    /// Page width in millimeters as laid out on screen and in exports
+    pub fn width_mm(&self) -> f64 {
+        self.size_mm().x
+    }
+
+    /// Page height in millimeters as laid out on screen and in exports
+    pub fn height_mm(&self) -> f64 {
+        self.size_mm().y
+    }
+
+    /// Page extent in millimeters, with the paper turned for landscape
+    pub fn size_mm(&self) -> PhysicalCoord {
+        let (short, long) = self.paper.portrait_mm();
+        match self.orientation {
+            Orientation::Portrait => PhysicalCoord { x: short, y: long },
+            Orientation::Landscape => PhysicalCoord { x: long, y: short },
+        }
+    }
+
+    /// Whether a coordinate lies on the page
+    pub fn contains(&self, coord: PhysicalCoord) -> bool {
+        let size = self.size_mm();
+        coord.x >= 0.0 && coord.x <= size.x && coord.y >= 0.0 && coord.y <= size.y
+    }
+
+    /// Reject custom sizes that cannot be laid out
+    pub fn validate(&self) -> Result<(), String> {
+        let (width, height) = self.paper.portrait_mm();
+        if !width.is_finite() || !height.is_finite() || width <= 0.0 || height <= 0.0 {
+            return Err(format!("Paper size must be positive, got {} x {} mm", width, height));
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for PageFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let orientation = match self.orientation {
+            Orientation::Portrait => "portrait",
+            Orientation::Landscape => "landscape",
+        };
+        write!(f, "{} {}", self.paper.name(), orientation)
+    }
+}
+
 
 /// Physical coordinate in millimeters
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -28,10 +134,15 @@ pub struct ScreenCoord {
     pub y: f64, // px from top edge
 }
 
+/// Pixels per mm at the 96 DPI CSS reference resolution
+pub const CSS_PX_PER_MM: f64 = 3.779527559;
+
 /// Device calibration data
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "StoredCalibration")]
 pub struct DeviceCalibration {
-    pub scale_factor: f64,     // pixels per mm
+    pub scale_x: f64,          // horizontal pixels per mm
+    pub scale_y: f64,          // vertical pixels per mm
     pub confidence: f64,       // 0.0 to 1.0
     pub timestamp: u64,        // Unix timestamp
     pub device_pixel_ratio: f64,
@@ -39,10 +150,31 @@ pub struct DeviceCalibration {
     pub viewport_height: f64,
 }
 
+impl DeviceCalibration {
+    /// Mean pixels per mm, for lengths without a direction such as font sizes
+    pub fn scale_factor(&self) -> f64 {
+        (self.scale_x + self.scale_y) / 2.0
+    }
+
+    /// The same calibration after CSS pixels grew by `zoom` (browser zoom or OS scaling):
+    /// a millimetre spans fewer of them and the viewport holds fewer
+    pub fn zoomed(&self, zoom: f64) -> DeviceCalibration {
+        DeviceCalibration {
+            scale_x: self.scale_x / zoom,
+            scale_y: self.scale_y / zoom,
+            device_pixel_ratio: self.device_pixel_ratio * zoom,
+            viewport_width: self.viewport_width / zoom,
+            viewport_height: self.viewport_height / zoom,
+            ..self.clone()
+        }
+    }
+}
+
 impl Default for DeviceCalibration {
     fn default() -> Self {
         Self {
-            scale_factor: 3.779527559, // 96 DPI assumption
+            scale_x: CSS_PX_PER_MM, // 96 DPI assumption
+            scale_y: CSS_PX_PER_MM,
             confidence: 0.5,
             timestamp: 0,
             device_pixel_ratio: 1.0,
@@ -52,6 +184,48 @@ impl Default for DeviceCalibration {
     }
 }
 
+/// Calibration as stored, in the current format or the earlier single-scale one
+#[derive(Deserialize)]
+struct StoredCalibration {
+    #[serde(default)]
+    scale_x: Option<f64>,
+    #[serde(default)]
+    scale_y: Option<f64>,
+    /// Single pixels-per-mm value written before X and Y were measured separately
+    #[serde(default)]
+    scale_factor: Option<f64>,
+    confidence: f64,
+    timestamp: u64,
+    device_pixel_ratio: f64,
+    viewport_width: f64,
+    viewport_height: f64,
+}
+
+impl TryFrom<StoredCalibration> for DeviceCalibration {
+    type Error = String;
+
+    fn try_from(stored: StoredCalibration) -> Result<Self, Self::Error> {
+        let (scale_x, scale_y) = match (stored.scale_x, stored.scale_y, stored.scale_factor) {
+            (Some(x), Some(y), _) => (x, y),
+            (None, None, Some(scale)) => (scale, scale),
+            _ => return Err("Calibration needs scale_x and scale_y or a legacy scale_factor".to_string()),
+        };
+        if !(scale_x > 0.0 && scale_y > 0.0 && scale_x.is_finite() && scale_y.is_finite()) {
+            return Err(format!("Calibration scale must be positive, got {} x {}", scale_x, scale_y));
+        }
+
+        Ok(Self {
+            scale_x,
+            scale_y,
+            confidence: stored.confidence,
+            timestamp: stored.timestamp,
+            device_pixel_ratio: stored.device_pixel_ratio,
+            viewport_width: stored.viewport_width,
+            viewport_height: stored.viewport_height,
+        })
+    }
+}
+
 /// Coordinate transformation utilities
 #[derive(Clone, PartialEq)]
 pub struct CoordinateSystem {
@@ -68,31 +242,56 @@ impl CoordinateSystem {
         &self.calibration
     }
 
-    /// Convert millimeters to CSS pixels
+    /// Convert millimeters to CSS pixels at the mean scale, for lengths without a direction
     pub fn mm_to_px(&self, mm: f64) -> f64 {
-        mm * self.calibration.scale_factor
+        mm * self.calibration.scale_factor()
     }
 
-    /// Convert CSS pixels to millimeters
+    /// Convert CSS pixels to millimeters at the mean scale, for lengths without a direction
     pub fn px_to_mm(&self, px: f64) -> f64 {
-        px / self.calibration.scale_factor
+        px / self.calibration.scale_factor()
     }
 
-    /// Convert physical coordinate to screen coordinate
+    /// Convert a horizontal length in millimeters to CSS pixels
+    pub fn mm_to_px_x(&self, mm: f64) -> f64 {
+        mm * self.calibration.scale_x
+    }
+
+    /// Convert a vertical length in millimeters to CSS pixels
+    pub fn mm_to_px_y(&self, mm: f64) -> f64 {
+        mm * self.calibration.scale_y
+    }
+
+    /// Convert a horizontal length in CSS pixels to millimeters
+    pub fn px_to_mm_x(&self, px: f64) -> f64 {
+        px / self.calibration.scale_x
+    }
+
+    /// Convert a vertical length in CSS pixels to millimeters
+    pub fn px_to_mm_y(&self, px: f64) -> f64 {
+        px / self.calibration.scale_y
+    }
+
+    /// Convert physical coordinate (or extent) to screen coordinate
     pub fn physical_to_screen(&self, coord: PhysicalCoord) -> ScreenCoord {
         ScreenCoord {
-            x: self.mm_to_px(coord.x),
-            y: self.mm_to_px(coord.y),
+            x: self.mm_to_px_x(coord.x),
+            y: self.mm_to_px_y(coord.y),
+        }
+    }
+
+    /// Convert screen coordinate (or extent) to physical coordinate
+    pub fn screen_to_physical(&self, coord: ScreenCoord) -> PhysicalCoord {
+        PhysicalCoord {
+            x: self.px_to_mm_x(coord.x),
+            y: self.px_to_mm_y(coord.y),
         }
     }
 
 
-    /// Validate coordinate is within US Letter bounds
-    pub fn validate_physical_bounds(&self, coord: PhysicalCoord) -> bool {
-        coord.x >= 0.0
-            && coord.x <= US_LETTER_WIDTH_MM
-            && coord.y >= 0.0
-            && coord.y <= US_LETTER_HEIGHT_MM
+    /// Validate coordinate is within the bounds of a page
+    pub fn validate_physical_bounds(&self, coord: PhysicalCoord, format: &PageFormat) -> bool {
+        format.contains(coord)
     }
 
     /// Snap coordinate to grid with tolerance
@@ -133,9 +332,15 @@ mod tests {
         let calibration = DeviceCalibration::default();
         let coord_sys = CoordinateSystem::new(calibration);
 
-        assert!(coord_sys.validate_physical_bounds(PhysicalCoord { x: 100.0, y: 100.0 }));
-        assert!(!coord_sys.validate_physical_bounds(PhysicalCoord { x: 300.0, y: 100.0 }));
-        assert!(!coord_sys.validate_physical_bounds(PhysicalCoord { x: -10.0, y: 100.0 }));
+        let letter = PageFormat::default();
+
+        assert!(coord_sys.validate_physical_bounds(PhysicalCoord { x: 100.0, y: 100.0 }, &letter));
+        assert!(!coord_sys.validate_physical_bounds(PhysicalCoord { x: 300.0, y: 100.0 }, &letter));
+        assert!(!coord_sys.validate_physical_bounds(PhysicalCoord { x: -10.0, y: 100.0 }, &letter));
+
+        let legal_landscape = PageFormat::new(PaperSize::Legal, Orientation::Landscape);
+        assert!(coord_sys.validate_physical_bounds(PhysicalCoord { x: 300.0, y: 100.0 }, &legal_landscape));
+        assert!(!coord_sys.validate_physical_bounds(PhysicalCoord { x: 100.0, y: 250.0 }, &legal_landscape));
     }
 
     #[test]
@@ -148,4 +353,59 @@ mod tests {
         
         assert_eq!(snapped, PhysicalCoord { x: 10.0, y: 20.0 });
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_anisotropic_scale_and_migration() {
+        let coord_sys = CoordinateSystem::new(DeviceCalibration {
+            scale_x: 4.0,
+            scale_y: 5.0,
+            ..DeviceCalibration::default()
+        });
+        let screen = coord_sys.physical_to_screen(PhysicalCoord { x: 10.0, y: 10.0 });
+        assert_eq!(screen, ScreenCoord { x: 40.0, y: 50.0 });
+        assert_eq!(coord_sys.screen_to_physical(screen), PhysicalCoord { x: 10.0, y: 10.0 });
+        assert_eq!(coord_sys.mm_to_px(2.0), 9.0);
+
+        // Calibrations stored before separate axes carry a single scale_factor
+        let legacy = r#"{"scale_factor":3.5,"confidence":0.9,"timestamp":1,"device_pixel_ratio":2.0,"viewport_width":1280.0,"viewport_height":800.0}"#;
+        let migrated: DeviceCalibration = serde_json::from_str(legacy).unwrap();
+        assert_eq!((migrated.scale_x, migrated.scale_y), (3.5, 3.5));
+
+        let json = serde_json::to_string(&coord_sys.get_calibration()).unwrap();
+        assert!(!json.contains("scale_factor"));
+        assert_eq!(&serde_json::from_str::<DeviceCalibration>(&json).unwrap(), coord_sys.get_calibration());
+
+        assert!(serde_json::from_str::<DeviceCalibration>(&legacy.replace("3.5", "-1.0")).is_err());
+    }
+
+    #[test]
+    fn test_zoomed_calibration() {
+        let calibration = DeviceCalibration { scale_x: 4.0, scale_y: 3.8, ..DeviceCalibration::default() };
+        let zoomed = calibration.zoomed(1.25);
+        assert_eq!((zoomed.scale_x, zoomed.scale_y), (3.2, 3.04));
+        assert_eq!(zoomed.device_pixel_ratio, 1.25);
+        assert_eq!((zoomed.viewport_width, zoomed.viewport_height), (1536.0, 864.0));
+        assert_eq!(zoomed.confidence, calibration.confidence);
+
+        // The physical size of a box is unchanged
+        let before = CoordinateSystem::new(calibration);
+        let after = CoordinateSystem::new(zoomed);
+        assert!((before.mm_to_px_x(50.0) - after.mm_to_px_x(50.0) * 1.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_page_formats() {
+        assert_eq!(PageFormat::default().size_mm(), PhysicalCoord { x: 215.9, y: 279.4 });
+        assert_eq!(PageFormat::new(PaperSize::A4, Orientation::Landscape).width_mm(), 297.0);
+        assert_eq!(PageFormat::new(PaperSize::Legal, Orientation::Portrait).height_mm(), 355.6);
+        assert_eq!(PageFormat::new(PaperSize::Legal, Orientation::Landscape).to_string(), "Legal landscape");
+
+        let json = r#"{"paper":{"Custom":{"width_mm":100.0,"height_mm":150.0}}}"#;
+        let custom: PageFormat = serde_json::from_str(json).unwrap();
+        assert_eq!(custom.orientation, Orientation::Portrait);
+        assert_eq!(custom.size_mm(), PhysicalCoord { x: 100.0, y: 150.0 });
+
+        let invalid = PageFormat::new(PaperSize::Custom { width_mm: 0.0, height_mm: 150.0 }, Orientation::Portrait);
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -96,6 +96,12 @@ pub struct CVAlignmentSystem {
     coordinate_system: Option<CoordinateSystem>,
 }
 
+impl Default for CVAlignmentSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CVAlignmentSystem {
     /// Create new CV alignment system
     pub fn new() -> Self {
@@ -109,7 +115,6 @@ impl CVAlignmentSystem {
 
     /// Create and initialize the CV worker
     fn create_worker() -> Option<Worker> {
-        let window = web_sys::window()?;
         let worker = Worker::new("/static/workers/cv-worker.js").ok()?;
         
         // Send ping to verify worker is ready
@@ -240,8 +245,8 @@ impl CVAlignmentSystem {
         let coord_system = self.coordinate_system.as_ref()?;
 
         // Convert correction from pixels to millimeters
-        let correction_x_mm = correction.x / coord_system.get_calibration().scale_factor;
-        let correction_y_mm = correction.y / coord_system.get_calibration().scale_factor;
+        let correction_x_mm = coord_system.px_to_mm_x(correction.x);
+        let correction_y_mm = coord_system.px_to_mm_y(correction.y);
 
         Some(PhysicalCoord {
             x: original_position.x + correction_x_mm,
@@ -355,12 +360,12 @@ impl CVAlignmentSystem {
                 for i in 0..fields.length() {
                     if let Ok(field) = fields.get(i).dyn_into::<js_sys::Object>() {
                         if let (Ok(x), Ok(y), Ok(width), Ok(height), Ok(area), Ok(aspect_ratio)) = (
-                            js_sys::Reflect::get(&field, &"x".into()).and_then(|v| Ok(v.as_f64().unwrap_or(0.0))),
-                            js_sys::Reflect::get(&field, &"y".into()).and_then(|v| Ok(v.as_f64().unwrap_or(0.0))),
-                            js_sys::Reflect::get(&field, &"width".into()).and_then(|v| Ok(v.as_f64().unwrap_or(0.0))),
-                            js_sys::Reflect::get(&field, &"height".into()).and_then(|v| Ok(v.as_f64().unwrap_or(0.0))),
-                            js_sys::Reflect::get(&field, &"area".into()).and_then(|v| Ok(v.as_f64().unwrap_or(0.0))),
-                            js_sys::Reflect::get(&field, &"aspectRatio".into()).and_then(|v| Ok(v.as_f64().unwrap_or(0.0))),
+                            js_sys::Reflect::get(&field, &"x".into()).map(|v| v.as_f64().unwrap_or(0.0)),
+                            js_sys::Reflect::get(&field, &"y".into()).map(|v| v.as_f64().unwrap_or(0.0)),
+                            js_sys::Reflect::get(&field, &"width".into()).map(|v| v.as_f64().unwrap_or(0.0)),
+                            js_sys::Reflect::get(&field, &"height".into()).map(|v| v.as_f64().unwrap_or(0.0)),
+                            js_sys::Reflect::get(&field, &"area".into()).map(|v| v.as_f64().unwrap_or(0.0)),
+                            js_sys::Reflect::get(&field, &"aspectRatio".into()).map(|v| v.as_f64().unwrap_or(0.0)),
                         ) {
                             field_boundaries.push(FieldBoundary {
                                 x, y, width, height, area, aspect_ratio
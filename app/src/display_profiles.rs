@@ -0,0 +1,397 @@
+//
+// ██████╗ ██╗███████╗██████╗ ██╗      █████╗ ██╗   ██╗███████╗
+// ██╔══██╗██║██╔════╝██╔══██╗██║     ██╔══██╗╚██╗ ██╔╝██╔════╝
+// ██║  ██║██║███████╗██████╔╝██║     ███████║ ╚████╔╝ ███████╗
+// ██║  ██║██║╚════██║██╔═══╝ ██║     ██╔══██║  ╚██╔╝  ╚════██║
+// ██████╔╝██║███████║██║     ███████╗██║  ██║   ██║   ███████║
+// ╚═════╝ ╚═╝╚══════╝╚═╝     ╚══════╝╚═╝  ╚═╝   ╚═╝   ╚══════╝
+//                                  app/src/display_profiles.rs
+
+use crate::coordinates::DeviceCalibration;
+#[cfg(feature = "web")]
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "web")]
+use wasm_bindgen::{closure::Closure, JsCast};
+#[cfg(feature = "web")]
+use web_sys::{window, MediaQueryList, Window};
+
+#[cfg(feature = "web")]
+const PROFILES_STORAGE_KEY: &str = "fl100_calibration_profiles";
+/// Single calibration stored before per-display profiles
+#[cfg(feature = "web")]
+const LEGACY_CALIBRATION_STORAGE_KEY: &str = "fl100_device_calibration";
+const DPR_TOLERANCE: f64 = 0.01;
+/// Relative tolerance for screen sizes in device pixels, which come from rounded CSS sizes
+const DEVICE_SIZE_TOLERANCE: f64 = 0.01;
+
+/// What the browser reveals about the display a window is on
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DisplayFingerprint {
+    /// Screen size in CSS pixels
+    pub screen_width: u32,
+    pub screen_height: u32,
+    pub device_pixel_ratio: f64,
+    pub color_depth: u32,
+}
+
+impl DisplayFingerprint {
+    /// Whether two fingerprints describe the same display
+    pub fn matches(&self, other: &DisplayFingerprint) -> bool {
+        self.screen_width == other.screen_width
+            && self.screen_height == other.screen_height
+            && self.color_depth == other.color_depth
+            && (self.device_pixel_ratio - other.device_pixel_ratio).abs() < DPR_TOLERANCE
+    }
+
+    /// Screen size in device pixels, which zooming leaves unchanged
+    pub fn device_size(&self) -> (f64, f64) {
+        (
+            self.screen_width as f64 * self.device_pixel_ratio,
+            self.screen_height as f64 * self.device_pixel_ratio,
+        )
+    }
+
+    /// How the display changed since `previous`
+    pub fn change_from(&self, previous: &DisplayFingerprint) -> DisplayChange {
+        if self.matches(previous) {
+            return DisplayChange::Unchanged;
+        }
+
+        // Zoom rescales CSS pixels but not the screen; another display almost never has
+        // the same device resolution at a different ratio
+        let (width, height) = self.device_size();
+        let (previous_width, previous_height) = previous.device_size();
+        let close = |a: f64, b: f64| (a - b).abs() <= a.max(b) * DEVICE_SIZE_TOLERANCE;
+        let zoom = self.device_pixel_ratio / previous.device_pixel_ratio;
+        if self.color_depth == previous.color_depth
+            && close(width, previous_width)
+            && close(height, previous_height)
+            && zoom.is_finite()
+            && (zoom - 1.0).abs() >= DPR_TOLERANCE
+        {
+            DisplayChange::Zoom(zoom)
+        } else {
+            DisplayChange::Other
+        }
+    }
+
+    /// Name given to a new profile for this display
+    pub fn default_name(&self) -> String {
+        format!("{} × {} @{}x", self.screen_width, self.screen_height, self.device_pixel_ratio)
+    }
+}
+
+/// Difference between two display fingerprints
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisplayChange {
+    Unchanged,
+    /// Same screen with CSS pixels scaled by this factor, from browser zoom or OS scaling
+    Zoom(f64),
+    /// Another display, or a change that can't be told apart from one
+    Other,
+}
+
+/// A named calibration for one display
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DisplayProfile {
+    pub name: String,
+    pub display: DisplayFingerprint,
+    pub calibration: DeviceCalibration,
+}
+
+/// All stored display profiles
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct CalibrationProfiles {
+    pub profiles: Vec<DisplayProfile>,
+}
+
+impl CalibrationProfiles {
+    /// Profiles from stored JSON, read one at a time so an entry that no longer parses
+    /// (say, written by another version) doesn't take the others with it. Returns the
+    /// profiles and an error for each entry dropped.
+    pub fn from_stored(stored: serde_json::Value) -> (Self, Vec<String>) {
+        let entries = match stored.get("profiles") {
+            Some(serde_json::Value::Array(entries)) => entries.clone(),
+            _ => {
+                let error = "Stored calibration profiles are unreadable".to_string();
+                return (Self::default(), vec![error]);
+            }
+        };
+        let mut profiles = Self::default();
+        let mut errors = Vec::new();
+        for (idx, entry) in entries.into_iter().enumerate() {
+            match serde_json::from_value(entry) {
+                Ok(profile) => profiles.profiles.push(profile),
+                Err(e) => {
+                    errors.push(format!("Dropped stored calibration profile {}: {}", idx + 1, e))
+                }
+            }
+        }
+        (profiles, errors)
+    }
+
+    /// Profile calibrated on a display
+    pub fn find(&self, display: &DisplayFingerprint) -> Option<&DisplayProfile> {
+        self.profiles.iter().find(|profile| profile.display.matches(display))
+    }
+
+    /// Calibration for a display, derived from a profile of the same screen at another
+    /// zoom level when it has none of its own
+    pub fn calibration_for(&self, display: &DisplayFingerprint) -> Option<DeviceCalibration> {
+        if let Some(profile) = self.find(display) {
+            return Some(profile.calibration.clone());
+        }
+        self.profiles.iter().find_map(|profile| match display.change_from(&profile.display) {
+            DisplayChange::Zoom(zoom) => Some(profile.calibration.zoomed(zoom)),
+            _ => None,
+        })
+    }
+
+    /// Store a calibration for a display, keeping the name of an existing profile
+    pub fn upsert(&mut self, display: DisplayFingerprint, calibration: DeviceCalibration) {
+        match self.profiles.iter_mut().find(|profile| profile.display.matches(&display)) {
+            Some(profile) => {
+                profile.display = display;
+                profile.calibration = calibration;
+            }
+            None => self.profiles.push(DisplayProfile {
+                name: display.default_name(),
+                display,
+                calibration,
+            }),
+        }
+    }
+
+    /// Add an imported profile under its own name; a profile already stored for the
+    /// display is kept unless `replace`. Returns whether the profile was stored.
+    pub fn install(&mut self, profile: DisplayProfile, replace: bool) -> bool {
+        match self.profiles.iter_mut().find(|existing| existing.display.matches(&profile.display)) {
+            Some(existing) if replace => *existing = profile,
+            Some(_) => return false,
+            None => self.profiles.push(profile),
+        }
+        true
+    }
+
+    /// Rename the profile for a display
+    pub fn rename(&mut self, display: &DisplayFingerprint, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Profile name cannot be empty".to_string());
+        }
+        let profile = self
+            .profiles
+            .iter_mut()
+            .find(|profile| profile.display.matches(display))
+            .ok_or("No calibration stored for this display")?;
+        profile.name = name.to_string();
+        Ok(())
+    }
+
+    /// Forget the profile for a display; returns whether one was stored
+    pub fn remove(&mut self, display: &DisplayFingerprint) -> bool {
+        let before = self.profiles.len();
+        self.profiles.retain(|profile| !profile.display.matches(display));
+        self.profiles.len() != before
+    }
+}
+
+/// Persists display profiles in localStorage
+#[cfg(feature = "web")]
+pub struct ProfileStore;
+
+#[cfg(feature = "web")]
+impl ProfileStore {
+    /// Stored profiles, adopting a pre-profile calibration for the current display
+    pub fn load() -> CalibrationProfiles {
+        if let Ok(stored) = LocalStorage::get::<serde_json::Value>(PROFILES_STORAGE_KEY) {
+            let (profiles, errors) = CalibrationProfiles::from_stored(stored);
+            for error in errors {
+                web_sys::console::warn_1(&error.into());
+            }
+            return profiles;
+        }
+
+        let mut profiles = CalibrationProfiles::default();
+        let legacy: Option<DeviceCalibration> =
+            LocalStorage::get(LEGACY_CALIBRATION_STORAGE_KEY).ok();
+        if let (Some(calibration), Some(display)) = (legacy, current_display()) {
+            profiles.upsert(display, calibration);
+            match Self::save(&profiles) {
+                Ok(()) => LocalStorage::delete(LEGACY_CALIBRATION_STORAGE_KEY),
+                Err(error) => web_sys::console::warn_1(&error.into()),
+            }
+        }
+        profiles
+    }
+
+    /// Save all profiles
+    pub fn save(profiles: &CalibrationProfiles) -> Result<(), String> {
+        LocalStorage::set(PROFILES_STORAGE_KEY, profiles)
+            .map_err(|e| format!("Failed to save calibration profiles: {:?}", e))
+    }
+}
+
+/// Fingerprint of the display the window is currently on
+#[cfg(feature = "web")]
+pub fn current_display() -> Option<DisplayFingerprint> {
+    let window = window()?;
+    let screen = window.screen().ok()?;
+    Some(DisplayFingerprint {
+        screen_width: screen.width().ok()?.max(0) as u32,
+        screen_height: screen.height().ok()?.max(0) as u32,
+        device_pixel_ratio: window.device_pixel_ratio(),
+        color_depth: screen.color_depth().ok()?.max(0) as u32,
+    })
+}
+
+/// Calls back when the window may have been zoomed or moved to another display: on
+/// resize and when the device pixel ratio changes. The DPR query is fixed at creation,
+/// so recreate the watcher after each change. Listeners are removed on drop.
+#[cfg(feature = "web")]
+pub struct DisplayWatcher {
+    window: Window,
+    resolution_query: Option<MediaQueryList>,
+    listener: Closure<dyn Fn()>,
+}
+
+#[cfg(feature = "web")]
+impl DisplayWatcher {
+    pub fn new(on_change: impl Fn() + 'static) -> Option<Self> {
+        let window = window()?;
+        let listener = Closure::<dyn Fn()>::new(on_change);
+        window
+            .add_event_listener_with_callback("resize", listener.as_ref().unchecked_ref())
+            .ok()?;
+
+        let query = format!("(resolution: {}dppx)", window.device_pixel_ratio());
+        let resolution_query = window.match_media(&query).ok().flatten();
+        if let Some(resolution_query) = &resolution_query {
+            if let Err(error) = resolution_query
+                .add_event_listener_with_callback("change", listener.as_ref().unchecked_ref())
+            {
+                web_sys::console::warn_1(&error);
+            }
+        }
+
+        Some(Self { window, resolution_query, listener })
+    }
+}
+
+#[cfg(feature = "web")]
+impl Drop for DisplayWatcher {
+    fn drop(&mut self) {
+        let callback = self.listener.as_ref().unchecked_ref();
+        let _ = self.window.remove_event_listener_with_callback("resize", callback);
+        if let Some(resolution_query) = &self.resolution_query {
+            let _ = resolution_query.remove_event_listener_with_callback("change", callback);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(device_pixel_ratio: f64) -> DisplayFingerprint {
+        DisplayFingerprint {
+            screen_width: 1920,
+            screen_height: 1080,
+            device_pixel_ratio,
+            color_depth: 24,
+        }
+    }
+
+    fn calibration(scale: f64) -> DeviceCalibration {
+        DeviceCalibration { scale_x: scale, scale_y: scale, ..DeviceCalibration::default() }
+    }
+
+    #[test]
+    fn test_profile_selection() {
+        let mut profiles = CalibrationProfiles::default();
+        profiles.upsert(display(1.0), calibration(3.8));
+        profiles.upsert(display(2.0), calibration(7.5));
+
+        assert_eq!(profiles.find(&display(1.0)).unwrap().calibration.scale_x, 3.8);
+        assert_eq!(profiles.find(&display(2.0)).unwrap().calibration.scale_x, 7.5);
+        // Browsers report fractional ratios with rounding noise
+        assert!(profiles.find(&display(2.0000001)).is_some());
+        assert!(profiles.find(&display(1.5)).is_none());
+
+        let mut other = display(1.0);
+        other.color_depth = 30;
+        assert!(profiles.find(&other).is_none());
+    }
+
+    #[test]
+    fn test_bad_stored_profile_dropped_alone() {
+        let mut profiles = CalibrationProfiles::default();
+        profiles.upsert(display(1.0), calibration(3.8));
+        profiles.upsert(display(2.0), calibration(7.5));
+        let mut stored = serde_json::to_value(&profiles).unwrap();
+        stored["profiles"][0]["calibration"] = serde_json::json!("not a calibration");
+
+        let (loaded, errors) = CalibrationProfiles::from_stored(stored);
+        assert_eq!(loaded.profiles, profiles.profiles[1..]);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("Dropped stored calibration profile 1"), "{}", errors[0]);
+
+        let (loaded, errors) = CalibrationProfiles::from_stored(serde_json::json!([1, 2]));
+        assert!(loaded.profiles.is_empty());
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_zoom_detection() {
+        let base = display(1.0);
+        // Chrome at 125%: the screen shrinks in CSS pixels as the ratio grows
+        let zoomed = DisplayFingerprint { screen_width: 1536, screen_height: 864, ..display(1.25) };
+        assert_eq!(base.change_from(&base), DisplayChange::Unchanged);
+        assert_eq!(zoomed.change_from(&base), DisplayChange::Zoom(1.25));
+        // Same CSS size at twice the ratio is a different, denser screen
+        assert_eq!(display(2.0).change_from(&base), DisplayChange::Other);
+
+        let mut profiles = CalibrationProfiles::default();
+        profiles.upsert(base, calibration(4.0));
+        assert_eq!(profiles.calibration_for(&zoomed).unwrap().scale_x, 3.2);
+        assert!(profiles.calibration_for(&display(2.0)).is_none());
+    }
+
+    #[test]
+    fn test_recalibration_keeps_name() {
+        let mut profiles = CalibrationProfiles::default();
+        profiles.upsert(display(2.0), calibration(7.5));
+        assert_eq!(profiles.profiles[0].name, "1920 × 1080 @2x");
+
+        profiles.rename(&display(2.0), "  Office monitor ").unwrap();
+        profiles.upsert(display(2.0), calibration(7.6));
+        assert_eq!(profiles.profiles.len(), 1);
+        assert_eq!(profiles.profiles[0].name, "Office monitor");
+        assert_eq!(profiles.profiles[0].calibration.scale_x, 7.6);
+
+        let imported = DisplayProfile {
+            name: "Provisioned".to_string(),
+            display: display(2.0),
+            calibration: calibration(7.7),
+        };
+        assert!(!profiles.install(imported.clone(), false));
+        assert!(profiles.install(imported, true));
+        assert_eq!(profiles.profiles[0].name, "Provisioned");
+
+        assert!(profiles.rename(&display(2.0), " ").is_err());
+        assert!(profiles.rename(&display(1.0), "Laptop").is_err());
+        assert!(profiles.remove(&display(2.0)));
+        assert!(!profiles.remove(&display(2.0)));
+    }
+
+    #[test]
+    fn test_profiles_round_trip() {
+        let mut profiles = CalibrationProfiles::default();
+        profiles.upsert(display(1.25), calibration(4.7));
+
+        let json = serde_json::to_string(&profiles).unwrap();
+        let restored: CalibrationProfiles = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, profiles);
+    }
+}
@@ -8,42 +8,60 @@
 //                                                            app/src/document_manager.rs
 
 use crate::calibration::CalibrationManager;
-use crate::coordinates::{PhysicalCoord, US_LETTER_WIDTH_MM, US_LETTER_HEIGHT_MM};
-use crate::overlay_manager::{OverlayManager, Overlay, OverlayType, Transform};
+use crate::checkbox::Checkbox;
+use crate::comb::{overflow_warnings, CombSettings};
+use crate::coordinates::{CoordinateSystem, DeviceCalibration, PageFormat, PhysicalCoord, ScreenCoord};
+use crate::drafts::{Draft, DraftStore, DraftSummary, ViewState};
+use crate::field_exchange::{export_fields, import_fields, ExchangeFormat};
+use crate::file_io::{download_bytes, download_text, print_bytes, read_file_text, take_selected_file};
+use crate::history::{EditCommand, EditHistory};
+use crate::form_data::{FieldChange, FieldValue, FormData};
+use crate::form_template::{FieldType, FormFieldInfo, FormTemplate, PageInfo};
+use crate::interchange::DocumentExport;
+use crate::font_metrics::FontMetricsCalculator;
+use crate::pdf_export::{export_pdf, ExportOptions};
+use crate::printer_calibration::PrinterStore;
+use crate::printer_setup::PrinterSetup;
+use crate::svg_export::export_page_svg;
+use crate::text_layout::LineSettings;
+use crate::overlay::{Overlay, OverlayType, Transform};
+use crate::overlay_manager::OverlayManager;
+use crate::viewport::{field_at, overlay_at, PageLayout, PagePoint, Viewport};
+use crate::signature::Signature;
+use crate::text_input::TextInput;
+use crate::multiline_input::MultilineInput;
+use crate::comb_input::CombInput;
+use gloo_timers::callback::Timeout;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
 use yew::prelude::*;
-use web_sys::HtmlElement;
+use web_sys::{HtmlElement, HtmlInputElement};
 
-#[derive(Clone, PartialEq)]
-pub struct PageInfo {
-    pub page_number: usize,
-    pub title: String,
-    pub fields: Vec<FormFieldInfo>,
-}
-
-#[derive(Clone, PartialEq)]
-pub struct FormFieldInfo {
-    pub id: String,
-    pub field_type: FieldType,
-    pub position: PhysicalCoord,
-    pub size: PhysicalCoord,
-    pub label: String,
-}
-
-#[derive(Clone, PartialEq)]
-pub enum FieldType {
-    TextInput,
-    Checkbox,
-    Signature,
-    Date,
-}
+/// Quiet period after the last change before the draft is written to storage
+const AUTOSAVE_DELAY_MS: u32 = 1_000;
 
 #[derive(Properties, PartialEq)]
 pub struct DocumentManagerProps {
     pub calibration_manager: CalibrationManager,
+    #[prop_or_else(FormTemplate::fl100)]
+    pub template: FormTemplate,
+    /// Notified after every field value change
+    #[prop_or_default]
+    pub on_field_change: Callback<FieldChange>,
+    /// Storage id the document autosaves under. Changing it keeps the document as it
+    /// is and saves it under the new id from the next change on.
+    pub draft_id: String,
+    /// Notified with the draft's index entry after every autosave
+    #[prop_or_default]
+    pub on_draft_saved: Callback<DraftSummary>,
+    /// Previously saved draft to restore on mount
+    #[prop_or_default]
+    pub draft: Option<Draft>,
 }
 
 pub struct DocumentManager {
     pages: Vec<PageInfo>,
+    form_data: FormData,
     overlays: Vec<Overlay>,
     current_page: usize,
     scroll_container_ref: NodeRef,
@@ -54,6 +72,15 @@ pub struct DocumentManager {
     snap_enabled: bool,
     snap_tolerance: f64,
     overlay_counter: usize,
+    draft_saved: bool,
+    /// Pending autosave, restarted by each change and cancelled on drop
+    autosave_timeout: Option<Timeout>,
+    /// Flushes a pending autosave when the page is hidden or closed
+    page_hide_listener: Option<Closure<dyn Fn()>>,
+    history: EditHistory,
+    /// Outcome of the last import/export, shown in the toolbar
+    data_status: Option<Result<String, String>>,
+    show_printer_setup: bool,
 }
 
 pub enum DocumentMsg {
@@ -70,35 +97,96 @@ pub enum DocumentMsg {
     ToggleSnap,
     SetSnapTolerance(f64),
     UpdateOverlay(usize, Overlay),
-    CreateOverlay(OverlayType, PhysicalCoord),
+    CreateOverlay(OverlayType, PagePoint),
+    PlaceOverlay(ScreenCoord),
     DeleteOverlay(String),
     DuplicateOverlay(String),
+    SetFieldValue(String, FieldValue),
+    EndOverlayGesture,
+    Undo,
+    Redo,
+    ExportDocument,
+    ExportFields(ExchangeFormat),
+    ExportPdf,
+    /// Print through the PDF, so the printer correction applies
+    Print,
+    ExportSvg,
+    TogglePrinterSetup,
+    ImportFile(web_sys::File),
+    DocumentFileRead(Result<String, String>),
+    /// Write a pending autosave now
+    FlushDraft,
+}
+
+impl DocumentMsg {
+    /// Whether handling the message can change what a draft stores: the document's
+    /// content or its view state. Exports, scrolling and panels don't.
+    fn changes_draft(&self) -> bool {
+        !matches!(
+            self,
+            DocumentMsg::ScrollToPage(_)
+                | DocumentMsg::EndOverlayGesture
+                | DocumentMsg::ExportDocument
+                | DocumentMsg::ExportFields(_)
+                | DocumentMsg::ExportPdf
+                | DocumentMsg::Print
+                | DocumentMsg::ExportSvg
+                | DocumentMsg::TogglePrinterSetup
+                | DocumentMsg::ImportFile(_)
+                | DocumentMsg::FlushDraft
+        )
+    }
 }
 
 impl Component for DocumentManager {
     type Message = DocumentMsg;
     type Properties = DocumentManagerProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        let pages = Self::initialize_fl100_pages();
-        
-        Self {
-            pages,
+    fn create(ctx: &Context<Self>) -> Self {
+        let template = &ctx.props().template;
+        let view = ViewState::default();
+
+        let mut document = Self {
+            pages: template.pages.clone(),
+            form_data: FormData::for_template(template),
             overlays: Vec::new(),
             current_page: 0,
             scroll_container_ref: NodeRef::default(),
-            zoom_level: 1.0,
-            show_rulers: true,
-            show_grid: true,
-            grid_size_mm: 5.0,
-            snap_enabled: true,
-            snap_tolerance: 2.0,
+            zoom_level: view.zoom_level,
+            show_rulers: view.show_rulers,
+            show_grid: view.show_grid,
+            grid_size_mm: view.grid_size_mm,
+            snap_enabled: view.snap_enabled,
+            snap_tolerance: view.snap_tolerance,
             overlay_counter: 0,
+            draft_saved: false,
+            autosave_timeout: None,
+            page_hide_listener: None,
+            history: EditHistory::default(),
+            data_status: None,
+            show_printer_setup: false,
+        };
+
+        if let Some(draft) = ctx.props().draft.as_ref().filter(|d| d.form_id == template.form_id) {
+            document.restore_draft(template, draft);
+        }
+
+        let link = ctx.link().clone();
+        let listener = Closure::<dyn Fn()>::new(move || link.send_message(DocumentMsg::FlushDraft));
+        if let Some(window) = web_sys::window() {
+            let callback = listener.as_ref().unchecked_ref();
+            match window.add_event_listener_with_callback("pagehide", callback) {
+                Ok(()) => document.page_hide_listener = Some(listener),
+                Err(error) => web_sys::console::warn_1(&error),
+            }
         }
+
+        document
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-        match msg {
+        let changes_draft = msg.changes_draft();
+        let should_render = match msg {
             DocumentMsg::NavigateToPage(page) => {
                 if page < self.pages.len() && page != self.current_page {
                     self.current_page = page;
@@ -159,31 +247,56 @@ impl Component for DocumentManager {
             DocumentMsg::UpdateOverlay(page_idx, overlay) => {
                 if let Some(existing_overlay) = self.overlays.iter_mut()
                     .find(|o| o.id == overlay.id && o.page_index == page_idx) {
+                    // Drags emit a stream of small deltas; coalesce them into one undo step
+                    self.history.record_coalesced(EditCommand::UpdateOverlay {
+                        before: existing_overlay.clone(),
+                        after: overlay.clone(),
+                    });
                     *existing_overlay = overlay;
                     true
                 } else {
                     false
                 }
             }
-            DocumentMsg::CreateOverlay(overlay_type, position) => {
+            DocumentMsg::CreateOverlay(overlay_type, point) => {
                 self.overlay_counter += 1;
                 let new_overlay = Overlay {
                     id: format!("overlay_{}", self.overlay_counter),
-                    page_index: self.current_page,
+                    page_index: point.page_index,
                     overlay_type,
                     transform: Transform::default(),
-                    position,
+                    position: point.position,
                     size: PhysicalCoord { x: 30.0, y: 20.0 },
                     visible: true,
                     selected: false,
                     z_index: self.overlay_counter as i32,
                 };
+                self.history.record(EditCommand::CreateOverlay {
+                    overlay: new_overlay.clone(),
+                    index: self.overlays.len(),
+                });
                 self.overlays.push(new_overlay);
                 true
             }
+            DocumentMsg::PlaceOverlay(client) => {
+                // Double-clicking empty page space drops a text box under the pointer
+                let Some(coord_sys) = ctx.props().calibration_manager.get_coordinate_system() else {
+                    return false;
+                };
+                let Some(point) = self.viewport().and_then(|v| v.screen_to_physical(coord_sys, client)) else {
+                    return false;
+                };
+                let page = &self.pages[point.page_index];
+                if overlay_at(&self.overlays, point, coord_sys).is_some() || field_at(page, point.position).is_some() {
+                    return false;
+                }
+                ctx.link().send_message(DocumentMsg::CreateOverlay(OverlayType::TextBox, point));
+                false
+            }
             DocumentMsg::DeleteOverlay(overlay_id) => {
                 if let Some(pos) = self.overlays.iter().position(|o| o.id == overlay_id) {
-                    self.overlays.remove(pos);
+                    let overlay = self.overlays.remove(pos);
+                    self.history.record(EditCommand::DeleteOverlay { overlay, index: pos });
                     true
                 } else {
                     false
@@ -197,26 +310,200 @@ impl Component for DocumentManager {
                     duplicate.transform.translate_x += 10.0;
                     duplicate.transform.translate_y += 10.0;
                     duplicate.z_index = self.overlay_counter as i32;
+                    self.history.record(EditCommand::CreateOverlay {
+                        overlay: duplicate.clone(),
+                        index: self.overlays.len(),
+                    });
                     self.overlays.push(duplicate);
                     true
                 } else {
                     false
                 }
             }
+            DocumentMsg::SetFieldValue(field_id, value) => {
+                match self.form_data.set(&field_id, value) {
+                    Ok(Some(change)) => {
+                        // Keystrokes in one text field undo a word or a burst at a time
+                        if matches!(change.new_value, FieldValue::Text(_)) {
+                            let now = js_sys::Date::now() as u64;
+                            self.history.record_typing(change.clone().into(), now);
+                        } else {
+                            self.history.record(change.clone().into());
+                        }
+                        ctx.props().on_field_change.emit(change);
+                        true
+                    }
+                    Ok(None) => false,
+                    Err(error) => {
+                        web_sys::console::warn_1(&error.into());
+                        false
+                    }
+                }
+            }
+            DocumentMsg::EndOverlayGesture => {
+                self.history.end_gesture();
+                false
+            }
+            DocumentMsg::Undo => match self.history.undo() {
+                Some(command) => self.apply_command(ctx, command),
+                None => false,
+            },
+            DocumentMsg::Redo => match self.history.redo() {
+                Some(command) => self.apply_command(ctx, command),
+                None => false,
+            },
+            DocumentMsg::ExportDocument => {
+                let template = &ctx.props().template;
+                let export = DocumentExport::from_document(template, &self.form_data, &self.overlays);
+                let filename = format!("{}.json", ctx.props().draft_id);
+                self.data_status = Some(
+                    export
+                        .to_json()
+                        .and_then(|json| download_text(&filename, "application/json", &json))
+                        .map(|()| format!("Exported {}", filename)),
+                );
+                true
+            }
+            DocumentMsg::ExportFields(format) => {
+                let contents = export_fields(&ctx.props().template, &self.form_data, format);
+                let filename = format!("{}.{}", ctx.props().draft_id, format.extension());
+                self.data_status = Some(
+                    download_text(&filename, format.mime_type(), &contents)
+                        .map(|()| format!("Exported {}", filename)),
+                );
+                true
+            }
+            DocumentMsg::ExportPdf => {
+                let pdf = self.corrected_pdf(ctx);
+                let filename = format!("{}.pdf", ctx.props().draft_id);
+                self.data_status = Some(
+                    download_bytes(&filename, "application/pdf", &pdf)
+                        .map(|()| format!("Exported {}", filename)),
+                );
+                true
+            }
+            DocumentMsg::Print => {
+                let printer = PrinterStore::load().active;
+                self.data_status = Some(
+                    print_bytes("application/pdf", &self.corrected_pdf(ctx)).map(|()| match printer {
+                        Some(printer) => format!("Printing, corrected for {}", printer),
+                        None => "Printing".to_string(),
+                    }),
+                );
+                true
+            }
+            DocumentMsg::ExportSvg => {
+                let coord_system = self.export_coordinate_system(ctx);
+                // Built-in metrics keep exports identical across browsers and installed fonts
+                let mut metrics = FontMetricsCalculator::with_builtin_metrics();
+                let filename = format!("{}-page{}.svg", ctx.props().draft_id, self.current_page + 1);
+                self.data_status = Some(
+                    export_page_svg(
+                        &ctx.props().template,
+                        self.current_page,
+                        &self.form_data,
+                        &self.overlays,
+                        &coord_system,
+                        &mut metrics,
+                        &ExportOptions::default(),
+                    )
+                    .and_then(|svg| download_text(&filename, "image/svg+xml", &svg))
+                    .map(|()| format!("Exported {}", filename)),
+                );
+                true
+            }
+            DocumentMsg::TogglePrinterSetup => {
+                self.show_printer_setup = !self.show_printer_setup;
+                true
+            }
+            DocumentMsg::ImportFile(file) => {
+                ctx.link().send_future(async move {
+                    DocumentMsg::DocumentFileRead(read_file_text(file).await)
+                });
+                false
+            }
+            DocumentMsg::DocumentFileRead(text) => {
+                let result = text.and_then(|text| match ExchangeFormat::detect(&text) {
+                    Some(_) => self.import_field_values(ctx, &text),
+                    None => self.import_document(ctx, &text),
+                });
+                self.data_status = Some(result);
+                true
+            }
+            DocumentMsg::FlushDraft => {
+                if self.autosave_timeout.take().is_some() {
+                    self.autosave(ctx);
+                }
+                false
+            }
+        };
+
+        if should_render && changes_draft {
+            self.schedule_autosave(ctx);
         }
+
+        should_render
+    }
+
+    fn destroy(&mut self, ctx: &Context<Self>) {
+        if self.autosave_timeout.take().is_some() {
+            self.autosave(ctx);
+        }
+        if let (Some(window), Some(listener)) = (web_sys::window(), &self.page_hide_listener) {
+            let callback = listener.as_ref().unchecked_ref();
+            let _ = window.remove_event_listener_with_callback("pagehide", callback);
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, first_render: bool) {
+        // Bring a restored draft back to the page the user was working on
+        if first_render && self.current_page > 0 {
+            self.smooth_scroll_to_page(self.current_page);
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().template != old_props.template {
+            self.pages = ctx.props().template.pages.clone();
+            self.form_data.sync_with_template(&ctx.props().template);
+            self.current_page = self.current_page.min(self.pages.len().saturating_sub(1));
+        }
+        if ctx.props().draft_id != old_props.draft_id {
+            self.draft_saved = false;
+        }
+        true
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let coord_system = ctx.props().calibration_manager.get_coordinate_system();
-        
+
         html! {
-            <div class="document-manager">
+            <div
+                class="document-manager"
+                tabindex="0"
+                onkeydown={ctx.link().batch_callback(|e: KeyboardEvent| {
+                    let msg = history_shortcut(&e).or_else(|| print_shortcut(&e));
+                    if msg.is_some() {
+                        e.prevent_default();
+                    }
+                    msg
+                })}
+            >
                 { self.render_toolbar(ctx) }
+                { if self.show_printer_setup {
+                    html! {
+                        <PrinterSetup
+                            format={self.pages.get(self.current_page).map(|page| page.format).unwrap_or_default()}
+                            on_close={ctx.link().callback(|_| DocumentMsg::TogglePrinterSetup)}
+                        />
+                    }
+                } else { html! {} }}
                 
                 <div class="document-workspace">
-                    { if self.show_rulers && coord_system.is_some() {
-                        self.render_rulers(coord_system.unwrap())
-                    } else { html! {} }}
+                    { match coord_system {
+                        Some(coord_system) if self.show_rulers => self.render_rulers(coord_system),
+                        _ => html! {},
+                    }}
                     
                     <div 
                         class="document-scroll-container"
@@ -231,9 +518,10 @@ impl Component for DocumentManager {
                             })}
                         </div>
                         
-                        { if self.show_grid && coord_system.is_some() {
-                            self.render_global_grid(coord_system.unwrap())
-                        } else { html! {} }}
+                        { match coord_system {
+                            Some(coord_system) if self.show_grid => self.render_global_grid(coord_system),
+                            _ => html! {},
+                        }}
                     </div>
                 </div>
                 
@@ -245,95 +533,220 @@ impl Component for DocumentManager {
 }
 
 impl DocumentManager {
-    fn initialize_fl100_pages() -> Vec<PageInfo> {
-        vec![
-            PageInfo {
-                page_number: 1,
-                title: "FL-100 Page 1 - Petition for Dissolution".to_string(),
-                fields: vec![
-                    FormFieldInfo {
-                        id: "petitioner_name".to_string(),
-                        field_type: FieldType::TextInput,
-                        position: PhysicalCoord { x: 25.0, y: 45.0 },
-                        size: PhysicalCoord { x: 120.0, y: 20.0 },
-                        label: "Petitioner Name".to_string(),
-                    },
-                    FormFieldInfo {
-                        id: "respondent_name".to_string(),
-                        field_type: FieldType::TextInput,
-                        position: PhysicalCoord { x: 25.0, y: 75.0 },
-                        size: PhysicalCoord { x: 120.0, y: 20.0 },
-                        label: "Respondent Name".to_string(),
-                    },
-                    FormFieldInfo {
-                        id: "case_number".to_string(),
-                        field_type: FieldType::TextInput,
-                        position: PhysicalCoord { x: 150.0, y: 25.0 },
-                        size: PhysicalCoord { x: 80.0, y: 15.0 },
-                        label: "Case Number".to_string(),
-                    },
-                ],
-            },
-            PageInfo {
-                page_number: 2,
-                title: "FL-100 Page 2 - Children Information".to_string(),
-                fields: vec![
-                    FormFieldInfo {
-                        id: "child_1_name".to_string(),
-                        field_type: FieldType::TextInput,
-                        position: PhysicalCoord { x: 25.0, y: 50.0 },
-                        size: PhysicalCoord { x: 100.0, y: 18.0 },
-                        label: "Child 1 Name".to_string(),
-                    },
-                    FormFieldInfo {
-                        id: "child_1_birthdate".to_string(),
-                        field_type: FieldType::Date,
-                        position: PhysicalCoord { x: 135.0, y: 50.0 },
-                        size: PhysicalCoord { x: 70.0, y: 18.0 },
-                        label: "Birth Date".to_string(),
-                    },
-                    FormFieldInfo {
-                        id: "custody_arrangement".to_string(),
-                        field_type: FieldType::Checkbox,
-                        position: PhysicalCoord { x: 25.0, y: 85.0 },
-                        size: PhysicalCoord { x: 15.0, y: 15.0 },
-                        label: "Joint Custody".to_string(),
-                    },
-                ],
+    /// Coordinate system for exports; overlays only exist with a calibration, so forms
+    /// without one fall back to 96 DPI
+    fn export_coordinate_system(&self, ctx: &Context<Self>) -> CoordinateSystem {
+        ctx.props().calibration_manager
+            .get_coordinate_system()
+            .cloned()
+            .unwrap_or_else(|| CoordinateSystem::new(DeviceCalibration::default()))
+    }
+
+    /// The document as a PDF, corrected for the selected printer
+    fn corrected_pdf(&self, ctx: &Context<Self>) -> Vec<u8> {
+        export_pdf(
+            &ctx.props().template,
+            &self.form_data,
+            &self.overlays,
+            &self.export_coordinate_system(ctx),
+            // Printer settings are shared by every open document, so read them fresh
+            &ExportOptions {
+                print_correction: PrinterStore::load().active_correction(),
+                ..ExportOptions::default()
             },
-            PageInfo {
-                page_number: 3,
-                title: "FL-100 Page 3 - Property & Financial".to_string(),
-                fields: vec![
-                    FormFieldInfo {
-                        id: "separate_property".to_string(),
-                        field_type: FieldType::Checkbox,
-                        position: PhysicalCoord { x: 25.0, y: 40.0 },
-                        size: PhysicalCoord { x: 15.0, y: 15.0 },
-                        label: "Separate Property".to_string(),
-                    },
-                    FormFieldInfo {
-                        id: "community_property".to_string(),
-                        field_type: FieldType::Checkbox,
-                        position: PhysicalCoord { x: 25.0, y: 65.0 },
-                        size: PhysicalCoord { x: 15.0, y: 15.0 },
-                        label: "Community Property".to_string(),
-                    },
-                    FormFieldInfo {
-                        id: "petitioner_signature".to_string(),
-                        field_type: FieldType::Signature,
-                        position: PhysicalCoord { x: 25.0, y: 220.0 },
-                        size: PhysicalCoord { x: 150.0, y: 30.0 },
-                        label: "Petitioner Signature".to_string(),
-                    },
-                ],
+        )
+    }
+
+    /// Paper and orientation of the page being viewed
+    fn current_page_format(&self) -> PageFormat {
+        self.pages.get(self.current_page).map(|page| page.format).unwrap_or_default()
+    }
+
+    /// Replace the document contents with an imported export
+    fn import_document(&mut self, ctx: &Context<Self>, json: &str) -> Result<String, String> {
+        let export = DocumentExport::from_json(json)?;
+        let revision_warning = export.revision_warning(&ctx.props().template);
+        let (form_data, overlays) = export.into_document(&ctx.props().template)?;
+
+        let filled = form_data.values().values().filter(|v| !v.is_empty()).count();
+        self.form_data = form_data;
+        self.overlays = overlays;
+        // Keep generated ids clear of imported ones
+        self.overlay_counter = self.overlays
+            .iter()
+            .filter_map(|o| o.id.strip_prefix("overlay_")?.parse::<usize>().ok())
+            .fold(self.overlay_counter, usize::max);
+        // Imported contents replace the document, so earlier edits can no longer be undone
+        self.history = EditHistory::default();
+
+        let mut message = format!("Imported {} filled fields and {} overlays", filled, self.overlays.len());
+        if let Some(warning) = revision_warning {
+            web_sys::console::warn_1(&warning.clone().into());
+            message = format!("{}. {}", message, warning);
+        }
+        Ok(self.with_overflow_warnings(ctx, message))
+    }
+
+    /// Merge field values from an XFDF/FDF file into the document
+    fn import_field_values(&mut self, ctx: &Context<Self>, text: &str) -> Result<String, String> {
+        let import = import_fields(&ctx.props().template, &self.form_data, text)?;
+        for skipped in &import.skipped {
+            web_sys::console::warn_1(&format!("Skipped {}", skipped).into());
+        }
+
+        self.form_data = import.form_data;
+        self.history = EditHistory::default();
+
+        let message = match import.skipped.len() {
+            0 => format!("Imported {} fields", import.imported),
+            skipped => format!("Imported {} fields, skipped {} (see console)", import.imported, skipped),
+        };
+        Ok(self.with_overflow_warnings(ctx, message))
+    }
+
+    /// Log comb values too long for their boxes and mention them in an import message;
+    /// the values are kept whole and flagged on the field
+    fn with_overflow_warnings(&self, ctx: &Context<Self>, message: String) -> String {
+        let warnings = overflow_warnings(&ctx.props().template, &self.form_data);
+        for warning in &warnings {
+            web_sys::console::warn_1(&warning.into());
+        }
+        match warnings.len() {
+            0 => message,
+            count => format!("{}; {} too long for their boxes (see console)", message, count),
+        }
+    }
+
+    /// Apply an undo/redo command without recording it again
+    fn apply_command(&mut self, ctx: &Context<Self>, command: EditCommand) -> bool {
+        match command {
+            EditCommand::SetField { field_id, new_value, .. } => {
+                match self.form_data.set(&field_id, new_value) {
+                    Ok(Some(change)) => {
+                        ctx.props().on_field_change.emit(change);
+                        true
+                    }
+                    Ok(None) => false,
+                    Err(error) => {
+                        web_sys::console::warn_1(&error.into());
+                        false
+                    }
+                }
+            }
+            EditCommand::UpdateOverlay { after, .. } => {
+                match self.overlays.iter_mut().find(|o| o.id == after.id) {
+                    Some(existing_overlay) => {
+                        *existing_overlay = after;
+                        true
+                    }
+                    None => false,
+                }
+            }
+            EditCommand::CreateOverlay { overlay, index } => {
+                let index = index.min(self.overlays.len());
+                self.overlays.insert(index, overlay);
+                true
+            }
+            EditCommand::DeleteOverlay { overlay, .. } => {
+                let count = self.overlays.len();
+                self.overlays.retain(|o| o.id != overlay.id);
+                self.overlays.len() != count
+            }
+        }
+    }
+
+    /// Apply a saved draft to this document
+    fn restore_draft(&mut self, template: &FormTemplate, draft: &Draft) {
+        self.form_data = draft.form_data.clone();
+        self.form_data.sync_with_template(template);
+        self.overlays = draft.overlays.clone();
+        self.overlay_counter = draft.overlay_counter;
+        self.current_page = draft.view.current_page.min(self.pages.len().saturating_sub(1));
+        self.zoom_level = draft.view.zoom_level;
+        self.show_rulers = draft.view.show_rulers;
+        self.show_grid = draft.view.show_grid;
+        self.grid_size_mm = draft.view.grid_size_mm;
+        self.snap_enabled = draft.view.snap_enabled;
+        self.snap_tolerance = draft.view.snap_tolerance;
+        self.draft_saved = true;
+
+        for warning in overflow_warnings(template, &self.form_data) {
+            web_sys::console::warn_1(&warning.into());
+        }
+    }
+
+    /// Snapshot the document as a draft
+    fn to_draft(&self, ctx: &Context<Self>) -> Draft {
+        Draft {
+            draft_id: ctx.props().draft_id.clone(),
+            form_id: ctx.props().template.form_id.clone(),
+            form_revision: ctx.props().template.revision.clone(),
+            updated_at: js_sys::Date::now() as u64,
+            form_data: self.form_data.clone(),
+            overlays: self.overlays.clone(),
+            overlay_counter: self.overlay_counter,
+            view: ViewState {
+                current_page: self.current_page,
+                zoom_level: self.zoom_level,
+                show_rulers: self.show_rulers,
+                show_grid: self.show_grid,
+                grid_size_mm: self.grid_size_mm,
+                snap_enabled: self.snap_enabled,
+                snap_tolerance: self.snap_tolerance,
             },
-        ]
+        }
+    }
+
+    /// Save the draft once changes have settled, so a burst of keystrokes or drag moves
+    /// is written once. Replacing the timeout cancels the one already pending.
+    fn schedule_autosave(&mut self, ctx: &Context<Self>) {
+        let link = ctx.link().clone();
+        self.autosave_timeout = Some(Timeout::new(AUTOSAVE_DELAY_MS, move || {
+            link.send_message(DocumentMsg::FlushDraft)
+        }));
+    }
+
+    /// Persist the document once it holds any content, so untouched forms don't become drafts
+    fn autosave(&mut self, ctx: &Context<Self>) {
+        let draft = self.to_draft(ctx);
+        if !self.draft_saved && !draft.has_content() {
+            return;
+        }
+
+        match DraftStore::save(&draft) {
+            Ok(()) => {
+                self.draft_saved = true;
+                ctx.props().on_draft_saved.emit(draft.summary());
+            }
+            Err(error) => web_sys::console::warn_1(&error.into()),
+        }
     }
 
     fn render_toolbar(&self, ctx: &Context<Self>) -> Html {
         html! {
             <div class="document-toolbar">
+                <div class="toolbar-section">
+                    <h3>{"Edit"}</h3>
+                    <div class="nav-controls history-controls">
+                        <button
+                            onclick={ctx.link().callback(|_| DocumentMsg::Undo)}
+                            disabled={!self.history.can_undo()}
+                            title="Undo (Ctrl+Z)"
+                            class="nav-button"
+                        >
+                            {"↶ Undo"}
+                        </button>
+                        <button
+                            onclick={ctx.link().callback(|_| DocumentMsg::Redo)}
+                            disabled={!self.history.can_redo()}
+                            title="Redo (Ctrl+Shift+Z)"
+                            class="nav-button"
+                        >
+                            {"↷ Redo"}
+                        </button>
+                    </div>
+                </div>
+
                 <div class="toolbar-section">
                     <h3>{"Navigation"}</h3>
                     <div class="nav-controls">
@@ -403,6 +816,24 @@ impl DocumentManager {
                             />
                             {"Snap"}
                         </label>
+                        <label class="checkbox-label" title="Snap tolerance in millimetres">
+                            <input
+                                type="number"
+                                class="snap-tolerance-input"
+                                min="0.5"
+                                max="10"
+                                step="0.5"
+                                value={self.snap_tolerance.to_string()}
+                                disabled={!self.snap_enabled}
+                                onchange={ctx.link().batch_callback(|e: Event| {
+                                    let input: HtmlInputElement = e.target_unchecked_into();
+                                    input.value().parse::<f64>().ok()
+                                        .filter(|tolerance| *tolerance > 0.0)
+                                        .map(DocumentMsg::SetSnapTolerance)
+                                })}
+                            />
+                            {"mm"}
+                        </label>
                     </div>
                 </div>
                 
@@ -429,6 +860,76 @@ impl DocumentManager {
                         </button>
                     </div>
                 </div>
+
+                <div class="toolbar-section">
+                    <h3>{"Data"}</h3>
+                    <div class="data-controls">
+                        <button
+                            class="nav-button"
+                            onclick={ctx.link().callback(|_| DocumentMsg::ExportPdf)}
+                            title="Download the filled form as a vector PDF"
+                        >
+                            {"PDF"}
+                        </button>
+                        <button
+                            class="nav-button"
+                            onclick={ctx.link().callback(|_| DocumentMsg::Print)}
+                            title="Print the filled form from the PDF, corrected for the selected printer"
+                        >
+                            {"Print"}
+                        </button>
+                        <button
+                            class="nav-button"
+                            onclick={ctx.link().callback(|_| DocumentMsg::ExportSvg)}
+                            title="Download the current page as SVG in millimetres"
+                        >
+                            {"SVG"}
+                        </button>
+                        <button
+                            class={if self.show_printer_setup { "nav-button active" } else { "nav-button" }}
+                            onclick={ctx.link().callback(|_| DocumentMsg::TogglePrinterSetup)}
+                            title="Calibrate printers and pick the one PDF and printed output is corrected for"
+                        >
+                            {"Printer…"}
+                        </button>
+                        <button
+                            class="nav-button"
+                            onclick={ctx.link().callback(|_| DocumentMsg::ExportDocument)}
+                            title="Download field values and overlays as JSON"
+                        >
+                            {"Export JSON"}
+                        </button>
+                        <button
+                            class="nav-button"
+                            onclick={ctx.link().callback(|_| DocumentMsg::ExportFields(ExchangeFormat::Xfdf))}
+                            title="Download field values as XFDF for PDF tools"
+                        >
+                            {"XFDF"}
+                        </button>
+                        <button
+                            class="nav-button"
+                            onclick={ctx.link().callback(|_| DocumentMsg::ExportFields(ExchangeFormat::Fdf))}
+                            title="Download field values as FDF for PDF tools"
+                        >
+                            {"FDF"}
+                        </button>
+                        <label class="nav-button data-import" title="Load field values from JSON, XFDF or FDF">
+                            {"Import…"}
+                            <input
+                                type="file"
+                                accept=".json,.xfdf,.fdf,.xml"
+                                onchange={ctx.link().batch_callback(|e: Event| {
+                                    take_selected_file(&e).map(DocumentMsg::ImportFile)
+                                })}
+                            />
+                        </label>
+                    </div>
+                    { match &self.data_status {
+                        Some(Ok(message)) => html! { <span class="data-status status-good">{message}</span> },
+                        Some(Err(error)) => html! { <span class="data-status status-warning">{error}</span> },
+                        None => html! {},
+                    }}
+                </div>
             </div>
         }
     }
@@ -455,21 +956,29 @@ impl DocumentManager {
 
     fn render_page(&self, ctx: &Context<Self>, page_idx: usize, page: &PageInfo, coord_system: Option<&crate::coordinates::CoordinateSystem>) -> Html {
         if let Some(coord_sys) = coord_system {
-            let width_px = coord_sys.mm_to_px(US_LETTER_WIDTH_MM);
-            let height_px = coord_sys.mm_to_px(US_LETTER_HEIGHT_MM);
+            let width_px = coord_sys.mm_to_px_x(page.format.width_mm());
+            let height_px = coord_sys.mm_to_px_y(page.format.height_mm());
             
             html! {
                 <div 
                     class="document-page"
                     style={format!("width: {}px; height: {}px", width_px, height_px)}
                     data-page={page_idx.to_string()}
+                    ondblclick={ctx.link().batch_callback(|e: MouseEvent| {
+                        // Leave double-clicks inside inputs to the inputs
+                        let on_input = e.target_dyn_into::<HtmlInputElement>().is_some();
+                        (!on_input).then(|| DocumentMsg::PlaceOverlay(ScreenCoord {
+                            x: e.client_x() as f64,
+                            y: e.client_y() as f64,
+                        }))
+                    })}
                 >
                     <div class="page-header">
                         <h2>{&page.title}</h2>
                     </div>
                     
                     <div class="page-content">
-                        { self.render_form_fields(coord_sys, &page.fields) }
+                        { self.render_form_fields(ctx, coord_sys, &page.fields) }
                         
                         <OverlayManager 
                             calibration_manager={ctx.props().calibration_manager.clone()}
@@ -478,6 +987,13 @@ impl DocumentManager {
                             on_overlay_change={ctx.link().callback(|(page_idx, overlay)| {
                                 DocumentMsg::UpdateOverlay(page_idx, overlay)
                             })}
+                            on_gesture_end={ctx.link().callback(|_| DocumentMsg::EndOverlayGesture)}
+                            on_create={ctx.link().callback(|(overlay_type, point)| {
+                                DocumentMsg::CreateOverlay(overlay_type, point)
+                            })}
+                            zoom={self.zoom_level}
+                            on_delete={ctx.link().callback(DocumentMsg::DeleteOverlay)}
+                            on_duplicate={ctx.link().callback(DocumentMsg::DuplicateOverlay)}
                             snap_enabled={self.snap_enabled}
                             snap_tolerance={self.snap_tolerance}
                             grid_size_mm={self.grid_size_mm}
@@ -485,7 +1001,10 @@ impl DocumentManager {
                     </div>
                     
                     <div class="page-footer">
-                        {"California Judicial Council Form FL-100"}
+                        {format!(
+                            "California Judicial Council Form {} (Rev. {})",
+                            ctx.props().template.form_id, ctx.props().template.revision
+                        )}
                     </div>
                 </div>
             }
@@ -501,11 +1020,11 @@ impl DocumentManager {
         }
     }
 
-    fn render_form_fields(&self, coord_sys: &crate::coordinates::CoordinateSystem, fields: &[FormFieldInfo]) -> Html {
+    fn render_form_fields(&self, ctx: &Context<Self>, coord_sys: &crate::coordinates::CoordinateSystem, fields: &[FormFieldInfo]) -> Html {
         let field_elements = fields.iter().map(|field| {
             let screen_coord = coord_sys.physical_to_screen(field.position);
-            let width_px = coord_sys.mm_to_px(field.size.x);
-            let height_px = coord_sys.mm_to_px(field.size.y);
+            let width_px = coord_sys.mm_to_px_x(field.size.x);
+            let height_px = coord_sys.mm_to_px_y(field.size.y);
             
             html! {
                 <div 
@@ -516,32 +1035,7 @@ impl DocumentManager {
                         screen_coord.x, screen_coord.y, width_px, height_px
                     )}
                 >
-                    { match field.field_type {
-                        FieldType::TextInput => html! {
-                            <input 
-                                type="text" 
-                                placeholder={field.label.clone()}
-                                class="field-input text-input"
-                            />
-                        },
-                        FieldType::Checkbox => html! {
-                            <input 
-                                type="checkbox" 
-                                class="field-input checkbox-input"
-                            />
-                        },
-                        FieldType::Date => html! {
-                            <input 
-                                type="date" 
-                                class="field-input date-input"
-                            />
-                        },
-                        FieldType::Signature => html! {
-                            <div class="field-input signature-input">
-                                <span class="signature-placeholder">{"Click to sign"}</span>
-                            </div>
-                        },
-                    }}
+                    { self.render_field_input(ctx, coord_sys, field, width_px, height_px) }
                     <div class="field-overlay"></div>
                 </div>
             }
@@ -554,22 +1048,127 @@ impl DocumentManager {
         }
     }
 
+    /// Render a controlled input for a field, reading from and writing to the form data store
+    fn render_field_input(
+        &self,
+        ctx: &Context<Self>,
+        coord_sys: &crate::coordinates::CoordinateSystem,
+        field: &FormFieldInfo,
+        width_px: f64,
+        height_px: f64,
+    ) -> Html {
+        // Components are positioned relative to the field wrapper
+        let origin = PhysicalCoord { x: 0.0, y: 0.0 };
+        let field_id = field.id.clone();
+
+        match field.field_type {
+            FieldType::TextInput => html! {
+                <TextInput
+                    id={format!("{}_input", field.id)}
+                    label={field.label.clone()}
+                    position={origin}
+                    width_mm={field.size.x}
+                    height_mm={field.size.y}
+                    value={self.form_data.text(&field.id)}
+                    on_input={ctx.link().callback(move |value: String| {
+                        DocumentMsg::SetFieldValue(field_id.clone(), FieldValue::Text(value))
+                    })}
+                    font={None}
+                    font_size_pt={None}
+                    auto_fit={field.auto_fit}
+                    coord_system={Some(coord_sys.clone())}
+                />
+            },
+            FieldType::MultilineText => html! {
+                <MultilineInput
+                    id={format!("{}_input", field.id)}
+                    label={field.label.clone()}
+                    position={origin}
+                    settings={LineSettings::for_field(field, ExportOptions::default().font_size_pt)}
+                    height_mm={field.size.y}
+                    value={self.form_data.text(&field.id)}
+                    on_input={ctx.link().callback(move |value: String| {
+                        DocumentMsg::SetFieldValue(field_id.clone(), FieldValue::Text(value))
+                    })}
+                    font={None}
+                    font_size_pt={None}
+                    coord_system={Some(coord_sys.clone())}
+                />
+            },
+            FieldType::Comb => match CombSettings::for_field(field) {
+                Some(settings) => html! {
+                    <CombInput
+                        id={format!("{}_input", field.id)}
+                        label={field.label.clone()}
+                        position={origin}
+                        settings={settings}
+                        height_mm={field.size.y}
+                        value={self.form_data.text(&field.id)}
+                        on_input={ctx.link().callback(move |value: String| {
+                            DocumentMsg::SetFieldValue(field_id.clone(), FieldValue::Text(value))
+                        })}
+                        font={None}
+                        font_size_pt={None}
+                        coord_system={Some(coord_sys.clone())}
+                    />
+                },
+                // Validated templates always carry comb settings
+                None => html! {},
+            },
+            FieldType::Checkbox => html! {
+                <Checkbox
+                    id={format!("{}_input", field.id)}
+                    label={field.label.clone()}
+                    position={origin}
+                    checked={self.form_data.is_checked(&field.id)}
+                    on_toggle={ctx.link().callback(move |checked: bool| {
+                        DocumentMsg::SetFieldValue(field_id.clone(), FieldValue::Checked(checked))
+                    })}
+                />
+            },
+            FieldType::Date => html! {
+                <input 
+                    type="date" 
+                    class="field-input date-input"
+                    value={self.form_data.text(&field.id)}
+                    onchange={ctx.link().callback(move |e: Event| {
+                        let input: HtmlInputElement = e.target_unchecked_into();
+                        DocumentMsg::SetFieldValue(field_id.clone(), FieldValue::Date(input.value()))
+                    })}
+                />
+            },
+            FieldType::Signature => html! {
+                <Signature
+                    id={format!("{}_input", field.id)}
+                    label={field.label.clone()}
+                    position={origin}
+                    width={width_px}
+                    height={height_px}
+                    signature_data={self.form_data.signature(&field.id)}
+                    on_sign={ctx.link().callback(move |data: String| {
+                        DocumentMsg::SetFieldValue(field_id.clone(), FieldValue::Signature(data))
+                    })}
+                />
+            },
+        }
+    }
 
     fn render_rulers(&self, coord_sys: &crate::coordinates::CoordinateSystem) -> Html {
-        let width_px = coord_sys.mm_to_px(US_LETTER_WIDTH_MM);
-        let height_px = coord_sys.mm_to_px(US_LETTER_HEIGHT_MM);
+        // Rulers follow the paper of the page being viewed
+        let format = self.current_page_format();
         
         html! {
             <div class="rulers">
-                { self.render_horizontal_ruler(coord_sys, width_px) }
-                { self.render_vertical_ruler(coord_sys, height_px) }
+                { self.render_horizontal_ruler(coord_sys, format.width_mm()) }
+                { self.render_vertical_ruler(coord_sys, format.height_mm()) }
             </div>
         }
     }
 
-    fn render_horizontal_ruler(&self, coord_sys: &crate::coordinates::CoordinateSystem, width_px: f64) -> Html {
-        let marks = (0..=(US_LETTER_WIDTH_MM as u32)).filter_map(|mm| {
-            let px = coord_sys.mm_to_px(mm as f64);
+    fn render_horizontal_ruler(&self, coord_sys: &crate::coordinates::CoordinateSystem, width_mm: f64) -> Html {
+        let width_px = coord_sys.mm_to_px_x(width_mm);
+        let marks = (0..=(width_mm as u32)).filter_map(|mm| {
+            let px = coord_sys.mm_to_px_x(mm as f64);
             let is_major = mm % 10 == 0;
             let is_minor = mm % 5 == 0;
             
@@ -596,9 +1195,10 @@ impl DocumentManager {
         }
     }
 
-    fn render_vertical_ruler(&self, coord_sys: &crate::coordinates::CoordinateSystem, height_px: f64) -> Html {
-        let marks = (0..=(US_LETTER_HEIGHT_MM as u32)).filter_map(|mm| {
-            let px = coord_sys.mm_to_px(mm as f64);
+    fn render_vertical_ruler(&self, coord_sys: &crate::coordinates::CoordinateSystem, height_mm: f64) -> Html {
+        let height_px = coord_sys.mm_to_px_y(height_mm);
+        let marks = (0..=(height_mm as u32)).filter_map(|mm| {
+            let px = coord_sys.mm_to_px_y(mm as f64);
             let is_major = mm % 10 == 0;
             let is_minor = mm % 5 == 0;
             
@@ -626,16 +1226,20 @@ impl DocumentManager {
     }
 
     fn render_global_grid(&self, coord_sys: &crate::coordinates::CoordinateSystem) -> Html {
-        let width_px = coord_sys.mm_to_px(US_LETTER_WIDTH_MM);
-        let height_px = coord_sys.mm_to_px(US_LETTER_HEIGHT_MM) * self.pages.len() as f64;
-        let grid_size_px = coord_sys.mm_to_px(self.grid_size_mm);
+        // Cover the widest page and the full stack of pages
+        let width_mm = self.pages.iter().map(|page| page.format.width_mm()).fold(0.0, f64::max);
+        let height_mm: f64 = self.pages.iter().map(|page| page.format.height_mm()).sum();
+        let width_px = coord_sys.mm_to_px_x(width_mm);
+        let height_px = coord_sys.mm_to_px_y(height_mm);
+        let grid_x_px = coord_sys.mm_to_px_x(self.grid_size_mm);
+        let grid_y_px = coord_sys.mm_to_px_y(self.grid_size_mm);
 
         html! {
             <div 
                 class="global-grid-overlay"
                 style={format!(
                     "background-size: {}px {}px; width: {}px; height: {}px; opacity: {}",
-                    grid_size_px, grid_size_px, width_px, height_px,
+                    grid_x_px, grid_y_px, width_px, height_px,
                     if self.snap_enabled { "0.8" } else { "0.3" }
                 )}
             ></div>
@@ -659,7 +1263,7 @@ impl DocumentManager {
                     html! {
                         <div class="status-section">
                             <span class="status-label">{"DPI:"}</span>
-                            <span class="status-value">{format!("{:.1}", calibration.scale_factor * 25.4)}</span>
+                            <span class="status-value">{format!("{:.1} × {:.1}", calibration.scale_x * 25.4, calibration.scale_y * 25.4)}</span>
                         </div>
                     }
                 } else { html! {} }}
@@ -668,6 +1272,11 @@ impl DocumentManager {
                     <span class="status-label">{"Page:"}</span>
                     <span class="status-value">{format!("{} of {}", self.current_page + 1, self.pages.len())}</span>
                 </div>
+
+                <div class="status-section">
+                    <span class="status-label">{"Paper:"}</span>
+                    <span class="status-value">{self.current_page_format().to_string()}</span>
+                </div>
                 
                 <div class="status-section">
                     <span class="status-label">{"Zoom:"}</span>
@@ -691,11 +1300,74 @@ impl DocumentManager {
 
     fn smooth_scroll_to_page(&self, page: usize) {
         if let Some(container) = self.scroll_container_ref.cast::<HtmlElement>() {
-            let page_height = 800.0; // Approximate page height in pixels
-            let target_scroll = page as f64 * page_height;
+            // Measured page offsets stay right at any zoom; fall back to an estimate before layout
+            let target_scroll = match self.viewport().and_then(|v| v.pages.get(page).copied()) {
+                Some(layout) => layout.origin.y * self.zoom_level,
+                None => page as f64 * 800.0,
+            };
             
             // Use simple scroll for now (smooth scrolling requires more web-sys features)
             container.set_scroll_top(target_scroll as i32);
         }
     }
-}
\ No newline at end of file
+
+    /// Current on-screen layout of the document's pages
+    fn viewport(&self) -> Option<Viewport> {
+        measure_viewport(&self.scroll_container_ref, self.zoom_level, &self.pages)
+    }
+}
+
+/// Measure the scroll container and the field area of each page, which is where page
+/// coordinates start
+fn measure_viewport(container_ref: &NodeRef, zoom: f64, pages: &[PageInfo]) -> Option<Viewport> {
+    let container = container_ref.cast::<HtmlElement>()?;
+    let bounds = container.get_bounding_client_rect();
+    let origin = ScreenCoord {
+        x: bounds.left() + container.client_left() as f64,
+        y: bounds.top() + container.client_top() as f64,
+    };
+    let scroll = ScreenCoord {
+        x: container.scroll_left() as f64,
+        y: container.scroll_top() as f64,
+    };
+
+    let pages = pages
+        .iter()
+        .enumerate()
+        .map(|(idx, page)| {
+            let area = container
+                .query_selector(&format!("[data-page=\"{}\"] .form-fields", idx))
+                .ok()??;
+            let rect = area.get_bounding_client_rect();
+            Some(PageLayout {
+                origin: ScreenCoord {
+                    x: (rect.left() - origin.x + scroll.x) / zoom,
+                    y: (rect.top() - origin.y + scroll.y) / zoom,
+                },
+                size: page.format.size_mm(),
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(Viewport { origin, scroll, zoom, pages })
+}
+
+/// Ctrl/Cmd+P while a printer correction is selected; the browser's own print of the
+/// page can't apply it, so the corrected PDF is printed instead
+fn print_shortcut(e: &KeyboardEvent) -> Option<DocumentMsg> {
+    let print = (e.ctrl_key() || e.meta_key()) && !e.alt_key() && e.key().eq_ignore_ascii_case("p");
+    (print && PrinterStore::load().active_correction().is_some()).then_some(DocumentMsg::Print)
+}
+
+/// Map Ctrl/Cmd+Z, Ctrl/Cmd+Shift+Z and Ctrl+Y to history actions
+fn history_shortcut(e: &KeyboardEvent) -> Option<DocumentMsg> {
+    if !(e.ctrl_key() || e.meta_key()) || e.alt_key() {
+        return None;
+    }
+    match e.key().to_ascii_lowercase().as_str() {
+        "z" if e.shift_key() => Some(DocumentMsg::Redo),
+        "z" => Some(DocumentMsg::Undo),
+        "y" => Some(DocumentMsg::Redo),
+        _ => None,
+    }
+}
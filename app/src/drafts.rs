@@ -0,0 +1,196 @@
+//
+// ██████╗ ██████╗  █████╗ ███████╗████████╗███████╗
+// ██╔══██╗██╔══██╗██╔══██╗██╔════╝╚══██╔══╝██╔════╝
+// ██║  ██║██████╔╝███████║█████╗     ██║   ███████╗
+// ██║  ██║██╔══██╗██╔══██║██╔══╝     ██║   ╚════██║
+// ██████╔╝██║  ██║██║  ██║██║        ██║   ███████║
+// ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚═╝        ╚═╝   ╚══════╝
+//                                 app/src/drafts.rs
+
+use crate::form_data::FormData;
+use crate::overlay::Overlay;
+#[cfg(feature = "web")]
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "web")]
+const DRAFT_INDEX_STORAGE_KEY: &str = "fl100_draft_index";
+const DRAFT_STORAGE_KEY_PREFIX: &str = "fl100_draft_";
+
+/// Document view settings restored together with a draft
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViewState {
+    pub current_page: usize,
+    pub zoom_level: f64,
+    pub show_rulers: bool,
+    pub show_grid: bool,
+    pub grid_size_mm: f64,
+    pub snap_enabled: bool,
+    pub snap_tolerance: f64,
+}
+
+impl Default for ViewState {
+    fn default() -> Self {
+        Self {
+            current_page: 0,
+            zoom_level: 1.0,
+            show_rulers: true,
+            show_grid: true,
+            grid_size_mm: 5.0,
+            snap_enabled: true,
+            snap_tolerance: 2.0,
+        }
+    }
+}
+
+/// In-progress document: field values, overlays and view state for one form
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Draft {
+    pub draft_id: String,
+    pub form_id: String,
+    pub form_revision: String,
+    pub updated_at: u64, // Unix timestamp (ms)
+    pub form_data: FormData,
+    pub overlays: Vec<Overlay>,
+    pub overlay_counter: usize,
+    pub view: ViewState,
+}
+
+/// Index entry describing a stored draft
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DraftSummary {
+    pub draft_id: String,
+    pub form_id: String,
+    pub form_revision: String,
+    pub updated_at: u64,
+    pub filled_fields: usize,
+}
+
+impl Draft {
+    /// Index entry for this draft
+    pub fn summary(&self) -> DraftSummary {
+        DraftSummary {
+            draft_id: self.draft_id.clone(),
+            form_id: self.form_id.clone(),
+            form_revision: self.form_revision.clone(),
+            updated_at: self.updated_at,
+            filled_fields: self.form_data.values().values().filter(|v| !v.is_empty()).count(),
+        }
+    }
+
+    /// Whether the draft holds anything worth keeping
+    pub fn has_content(&self) -> bool {
+        self.form_data.values().values().any(|v| !v.is_empty()) || !self.overlays.is_empty()
+    }
+}
+
+/// Persists drafts in localStorage, one key per draft plus an index
+#[cfg(feature = "web")]
+pub struct DraftStore;
+
+#[cfg(feature = "web")]
+impl DraftStore {
+    /// Generate a new draft id for a form
+    pub fn new_draft_id(form_id: &str) -> String {
+        format!("{}-{}", form_id, js_sys::Date::now() as u64)
+    }
+
+    /// Save a draft and update the index
+    pub fn save(draft: &Draft) -> Result<(), String> {
+        LocalStorage::set(storage_key(&draft.draft_id), draft)
+            .map_err(|e| format!("Failed to save draft: {:?}", e))?;
+
+        let mut index = Self::list();
+        upsert_summary(&mut index, draft.summary());
+        LocalStorage::set(DRAFT_INDEX_STORAGE_KEY, &index)
+            .map_err(|e| format!("Failed to save draft index: {:?}", e))
+    }
+
+    /// Load a draft by id
+    pub fn load(draft_id: &str) -> Option<Draft> {
+        LocalStorage::get(storage_key(draft_id)).ok()
+    }
+
+    /// All stored drafts, most recently updated first
+    pub fn list() -> Vec<DraftSummary> {
+        LocalStorage::get(DRAFT_INDEX_STORAGE_KEY).unwrap_or_default()
+    }
+
+    /// Delete a draft and remove it from the index
+    pub fn delete(draft_id: &str) -> Result<(), String> {
+        LocalStorage::delete(storage_key(draft_id));
+
+        let mut index = Self::list();
+        index.retain(|summary| summary.draft_id != draft_id);
+        LocalStorage::set(DRAFT_INDEX_STORAGE_KEY, &index)
+            .map_err(|e| format!("Failed to save draft index: {:?}", e))
+    }
+}
+
+/// localStorage key holding a single draft
+pub fn storage_key(draft_id: &str) -> String {
+    format!("{}{}", DRAFT_STORAGE_KEY_PREFIX, draft_id)
+}
+
+/// Insert or replace a summary, keeping the index sorted newest first
+pub fn upsert_summary(index: &mut Vec<DraftSummary>, summary: DraftSummary) {
+    index.retain(|existing| existing.draft_id != summary.draft_id);
+    index.push(summary);
+    index.sort_by_key(|summary| std::cmp::Reverse(summary.updated_at));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::form_data::FieldValue;
+    use crate::form_template::FormTemplate;
+
+    fn draft(draft_id: &str, updated_at: u64) -> Draft {
+        let template = FormTemplate::fl100();
+        Draft {
+            draft_id: draft_id.to_string(),
+            form_id: template.form_id.clone(),
+            form_revision: template.revision.clone(),
+            updated_at,
+            form_data: FormData::for_template(&template),
+            overlays: Vec::new(),
+            overlay_counter: 0,
+            view: ViewState::default(),
+        }
+    }
+
+    #[test]
+    fn test_draft_summary() {
+        let mut draft = draft("FL-100-1", 10);
+        assert!(!draft.has_content());
+
+        draft.form_data.set("petitioner_name", FieldValue::Text("Jane Doe".to_string())).unwrap();
+        draft.form_data.set("separate_property", FieldValue::Checked(true)).unwrap();
+
+        assert!(draft.has_content());
+        assert_eq!(draft.summary().filled_fields, 2);
+        assert_eq!(storage_key(&draft.draft_id), "fl100_draft_FL-100-1");
+    }
+
+    #[test]
+    fn test_index_ordering() {
+        let mut index = Vec::new();
+        upsert_summary(&mut index, draft("a", 10).summary());
+        upsert_summary(&mut index, draft("b", 20).summary());
+        upsert_summary(&mut index, draft("a", 30).summary());
+
+        let ids: Vec<_> = index.iter().map(|s| s.draft_id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert_eq!(index[0].updated_at, 30);
+    }
+
+    #[test]
+    fn test_draft_round_trip() {
+        let mut original = draft("FL-100-2", 42);
+        original.form_data.set("case_number", FieldValue::Text("FL-2026-001".to_string())).unwrap();
+
+        let json = serde_json::to_string(&original).unwrap();
+        let restored: Draft = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, original);
+    }
+}
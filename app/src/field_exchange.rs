@@ -0,0 +1,790 @@
+//
+// ███████╗██╗███████╗██╗     ██████╗    ███████╗██╗  ██╗ ██████╗██╗  ██╗ █████╗ ███╗   ██╗ ██████╗ ███████╗
+// ██╔════╝██║██╔════╝██║     ██╔══██╗   ██╔════╝╚██╗██╔╝██╔════╝██║  ██║██╔══██╗████╗  ██║██╔════╝ ██╔════╝
+// █████╗  ██║█████╗  ██║     ██║  ██║   █████╗   ╚███╔╝ ██║     ███████║███████║██╔██╗ ██║██║  ███╗█████╗
+// ██╔══╝  ██║██╔══╝  ██║     ██║  ██║   ██╔══╝   ██╔██╗ ██║     ██╔══██║██╔══██║██║╚██╗██║██║   ██║██╔══╝
+// ██║     ██║███████╗███████╗██████╔╝   ███████╗██╔╝ ██╗╚██████╗██║  ██║██║  ██║██║ ╚████║╚██████╔╝███████╗
+// ╚═╝     ╚═╝╚══════╝╚══════╝╚═════╝    ╚══════╝╚═╝  ╚═╝ ╚═════╝╚═╝  ╚═╝╚═╝  ╚═╝╚═╝  ╚═══╝ ╚═════╝ ╚══════╝
+//                                                                                 app/src/field_exchange.rs
+
+use crate::form_data::{format_us_date, FieldValue, FormData};
+use crate::form_template::{FieldType, FormTemplate};
+use crate::xml::escape_xml;
+
+/// Export state of a checked checkbox in the Judicial Council PDFs
+pub const CHECKBOX_ON_STATE: &str = "1";
+pub const CHECKBOX_OFF_STATE: &str = "Off";
+
+const XFDF_NAMESPACE: &str = "http://ns.adobe.com/xfdf/";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExchangeFormat {
+    Xfdf,
+    Fdf,
+}
+
+impl ExchangeFormat {
+    /// Recognise a file's format from its contents
+    pub fn detect(text: &str) -> Option<Self> {
+        let text = text.trim_start_matches('\u{feff}').trim_start();
+        if text.starts_with("%FDF") {
+            Some(ExchangeFormat::Fdf)
+        } else if text.starts_with('<') && text.contains("<xfdf") {
+            Some(ExchangeFormat::Xfdf)
+        } else {
+            None
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExchangeFormat::Xfdf => "xfdf",
+            ExchangeFormat::Fdf => "fdf",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ExchangeFormat::Xfdf => "application/vnd.adobe.xfdf",
+            ExchangeFormat::Fdf => "application/vnd.fdf",
+        }
+    }
+}
+
+/// Result of merging an exchange file into a form
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldImport {
+    pub form_data: FormData,
+    pub imported: usize,
+    /// PDF field names that were ignored, with the reason
+    pub skipped: Vec<String>,
+}
+
+/// Serialize the exchangeable field values of a form.
+///
+/// Fields are named through the template's `pdf_field_aliases`, checkboxes use the
+/// `1`/`Off` export states, dates are written as `MM/DD/YYYY`, and signatures are
+/// left out since XFDF/FDF values can't carry images.
+pub fn export_fields(
+    template: &FormTemplate,
+    form_data: &FormData,
+    format: ExchangeFormat,
+) -> String {
+    let fields = exchange_values(template, form_data);
+    let pdf_file = pdf_file_name(template);
+    match format {
+        ExchangeFormat::Xfdf => write_xfdf(&pdf_file, &fields),
+        ExchangeFormat::Fdf => write_fdf(&pdf_file, &fields),
+    }
+}
+
+/// Merge field values from an XFDF or FDF file into existing form data.
+///
+/// Fields the file doesn't mention keep their current values; names that don't
+/// map onto the template are reported in `skipped` rather than failing the import.
+pub fn import_fields(
+    template: &FormTemplate,
+    base: &FormData,
+    text: &str,
+) -> Result<FieldImport, String> {
+    let format = ExchangeFormat::detect(text).ok_or("File is neither XFDF nor FDF")?;
+    let entries = match format {
+        ExchangeFormat::Xfdf => parse_xfdf(text)?,
+        ExchangeFormat::Fdf => parse_fdf(text)?,
+    };
+
+    let mut form_data = base.clone();
+    let mut imported = 0;
+    let mut skipped = Vec::new();
+
+    for (pdf_name, raw) in entries {
+        let Some(field) = template.field_for_pdf_name(&pdf_name) else {
+            skipped.push(format!("{}: not a field of {}", pdf_name, template.form_id));
+            continue;
+        };
+
+        let value = match FieldValue::empty_for(&field.field_type) {
+            FieldValue::Text(_) => FieldValue::Text(raw),
+            FieldValue::Checked(_) => FieldValue::Checked(is_checked_state(&raw)),
+            FieldValue::Date(_) => match parse_date(&raw) {
+                Some(date) => FieldValue::Date(date),
+                None => {
+                    skipped.push(format!("{}: '{}' is not a date", pdf_name, raw));
+                    continue;
+                }
+            },
+            FieldValue::Signature(_) => {
+                skipped.push(format!("{}: signatures can't be imported", pdf_name));
+                continue;
+            }
+        };
+
+        form_data.set(&field.id, value)?;
+        imported += 1;
+    }
+
+    if imported == 0 {
+        return Err(format!("No fields in the file match form {}", template.form_id));
+    }
+
+    Ok(FieldImport { form_data, imported, skipped })
+}
+
+/// A field value as written to an exchange file
+struct ExchangeValue {
+    pdf_name: String,
+    value: String,
+    /// Checkbox states are PDF names in FDF; every other value is a string
+    field_type: FieldType,
+}
+
+/// Exchangeable values in template order
+fn exchange_values(template: &FormTemplate, form_data: &FormData) -> Vec<ExchangeValue> {
+    template
+        .pages
+        .iter()
+        .flat_map(|page| page.fields.iter())
+        .filter_map(|field| {
+            let value = match form_data.get(&field.id)? {
+                FieldValue::Text(text) => text.clone(),
+                FieldValue::Checked(true) => CHECKBOX_ON_STATE.to_string(),
+                FieldValue::Checked(false) => CHECKBOX_OFF_STATE.to_string(),
+                FieldValue::Date(date) => format_us_date(date),
+                FieldValue::Signature(_) => return None,
+            };
+            Some(ExchangeValue {
+                pdf_name: template.pdf_field_name(&field.id).to_string(),
+                value,
+                field_type: field.field_type.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Judicial Council PDF file name for a form, e.g. `fl100.pdf`
+fn pdf_file_name(template: &FormTemplate) -> String {
+    format!("{}.pdf", template.form_id.to_lowercase().replace('-', ""))
+}
+
+fn is_checked_state(state: &str) -> bool {
+    !matches!(state.trim().to_ascii_lowercase().as_str(), "" | "off" | "0" | "no" | "false")
+}
+
+/// Accept `MM/DD/YYYY`, `M/D/YYYY` or `YYYY-MM-DD`, returning `YYYY-MM-DD`
+fn parse_date(text: &str) -> Option<String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Some(String::new());
+    }
+
+    let (year, month, day) = if let [month, day, year] = text.split('/').collect::<Vec<_>>()[..] {
+        (year, month, day)
+    } else if let [year, month, day] = text.split('-').collect::<Vec<_>>()[..] {
+        (year, month, day)
+    } else {
+        return None;
+    };
+
+    let year: u32 = year.parse().ok().filter(|y| (1000..=9999).contains(y))?;
+    let month: u32 = month.parse().ok().filter(|m| (1..=12).contains(m))?;
+    let day: u32 = day.parse().ok().filter(|d| (1..=days_in_month(year, month)).contains(d))?;
+    Some(format!("{:04}-{:02}-{:02}", year, month, day))
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+fn write_xfdf(pdf_file: &str, fields: &[ExchangeValue]) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!("<xfdf xmlns=\"{}\" xml:space=\"preserve\">\n", XFDF_NAMESPACE));
+    xml.push_str(&format!("  <f href=\"{}\"/>\n", escape_xml(pdf_file)));
+    xml.push_str("  <fields>\n");
+    for field in fields {
+        xml.push_str(&format!(
+            "    <field name=\"{}\"><value>{}</value></field>\n",
+            escape_xml(&field.pdf_name),
+            escape_xml(&field.value)
+        ));
+    }
+    xml.push_str("  </fields>\n");
+    xml.push_str("</xfdf>\n");
+    xml
+}
+
+/// Extract `(full field name, value)` pairs, joining nested `<field>` names with `.`
+fn parse_xfdf(xml: &str) -> Result<Vec<(String, String)>, String> {
+    let mut entries = Vec::new();
+    let mut field_names: Vec<String> = Vec::new();
+    let mut value: Option<String> = None;
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        if let Some(text) = value.as_mut() {
+            text.push_str(&decode_entities(&rest[..start])?);
+        }
+        rest = &rest[start..];
+
+        if let Some(body) = rest.strip_prefix("<![CDATA[") {
+            let end = body.find("]]>").ok_or("Unterminated CDATA section in XFDF")?;
+            if let Some(text) = value.as_mut() {
+                text.push_str(&body[..end]);
+            }
+            rest = &body[end + 3..];
+            continue;
+        }
+        if let Some(body) = rest.strip_prefix("<!--") {
+            let end = body.find("-->").ok_or("Unterminated comment in XFDF")?;
+            rest = &body[end + 3..];
+            continue;
+        }
+        if rest.starts_with("<?") || rest.starts_with("<!") {
+            let end = rest.find('>').ok_or("Unterminated declaration in XFDF")?;
+            rest = &rest[end + 1..];
+            continue;
+        }
+
+        let end = tag_end(rest).ok_or("Unterminated tag in XFDF")?;
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            match local_name(name.trim()) {
+                "field" => {
+                    field_names.pop();
+                }
+                "value" => {
+                    if let Some(text) = value.take() {
+                        entries.push((field_names.join("."), text));
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let element = tag.split_whitespace().next().unwrap_or_default();
+        match local_name(element) {
+            "field" => {
+                let name = xml_attribute(tag, "name")
+                    .ok_or("XFDF field is missing its name attribute")??;
+                if !self_closing {
+                    field_names.push(name);
+                }
+            }
+            "value" if !field_names.is_empty() => {
+                if self_closing {
+                    entries.push((field_names.join("."), String::new()));
+                } else {
+                    value = Some(String::new());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !field_names.is_empty() {
+        return Err(format!("XFDF field '{}' is never closed", field_names.join(".")));
+    }
+
+    Ok(entries)
+}
+
+/// Index of the `>` closing the tag at the start of `text`, skipping quoted attribute values
+fn tag_end(text: &str) -> Option<usize> {
+    let mut quote = None;
+    for (idx, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '>') => return Some(idx),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Element name without its namespace prefix
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn xml_attribute(tag: &str, attribute: &str) -> Option<Result<String, String>> {
+    let mut rest = tag;
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].split_whitespace().last().unwrap_or_default();
+        let after = rest[eq + 1..].trim_start();
+        let quote = after.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let close = after[1..].find(quote)?;
+        if local_name(key) == attribute {
+            return Some(decode_entities(&after[1..close + 1]));
+        }
+        rest = &after[close + 2..];
+    }
+    None
+}
+
+fn decode_entities(text: &str) -> Result<String, String> {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        let semi = rest[amp..].find(';').ok_or("Unterminated entity in XFDF")? + amp;
+        let entity = &rest[amp + 1..semi];
+        let c = match entity {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => {
+                let code =
+                    if let Some(hex) = entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                        u32::from_str_radix(hex, 16).ok()
+                    } else {
+                        entity.strip_prefix('#').and_then(|dec| dec.parse().ok())
+                    };
+                code.and_then(char::from_u32)
+                    .ok_or_else(|| format!("Unknown entity '&{};' in XFDF", entity))?
+            }
+        };
+        decoded.push(c);
+        rest = &rest[semi + 1..];
+    }
+    decoded.push_str(rest);
+    Ok(decoded)
+}
+
+fn write_fdf(pdf_file: &str, fields: &[ExchangeValue]) -> String {
+    let mut fdf = String::new();
+    fdf.push_str("%FDF-1.2\n");
+    fdf.push_str("1 0 obj\n");
+    fdf.push_str(&format!("<< /FDF << /F {}\n/Fields [\n", pdf_string(pdf_file)));
+    for field in fields {
+        let value = match field.field_type {
+            FieldType::Checkbox => format!("/{}", field.value),
+            _ => pdf_string(&field.value),
+        };
+        fdf.push_str(&format!("<< /T {} /V {} >>\n", pdf_string(&field.pdf_name), value));
+    }
+    fdf.push_str("] >> >>\nendobj\n");
+    fdf.push_str("trailer\n<< /Root 1 0 R >>\n%%EOF\n");
+    fdf
+}
+
+/// PDF string literal; text outside ASCII is written as UTF-16BE hex with a BOM
+fn pdf_string(text: &str) -> String {
+    if text.is_ascii() {
+        let mut literal = String::from("(");
+        for c in text.chars() {
+            match c {
+                '(' | ')' | '\\' => {
+                    literal.push('\\');
+                    literal.push(c);
+                }
+                '\n' => literal.push_str("\\n"),
+                '\r' => literal.push_str("\\r"),
+                _ => literal.push(c),
+            }
+        }
+        literal.push(')');
+        literal
+    } else {
+        let hex: String = text.encode_utf16().map(|unit| format!("{:04X}", unit)).collect();
+        format!("<FEFF{}>", hex)
+    }
+}
+
+/// Minimal PDF object model, enough to walk an FDF field tree
+#[derive(Debug, Clone, PartialEq)]
+enum PdfObject {
+    Dict(Vec<(String, PdfObject)>),
+    Array(Vec<PdfObject>),
+    Name(String),
+    Str(Vec<u8>),
+    Other,
+}
+
+impl PdfObject {
+    fn get(&self, key: &str) -> Option<&PdfObject> {
+        match self {
+            PdfObject::Dict(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_text(&self) -> Option<String> {
+        match self {
+            PdfObject::Str(bytes) => Some(decode_pdf_text(bytes)),
+            PdfObject::Name(name) => Some(name.clone()),
+            _ => None,
+        }
+    }
+}
+
+fn parse_fdf(fdf: &str) -> Result<Vec<(String, String)>, String> {
+    let mut lexer = PdfLexer { bytes: fdf.as_bytes(), pos: 0 };
+    let mut objects = Vec::new();
+    while let Some(object) = lexer.next_object()? {
+        objects.push(object);
+    }
+
+    let fields = objects
+        .iter()
+        .find_map(|object| object.get("FDF")?.get("Fields"))
+        .ok_or("FDF has no /Fields array")?;
+
+    let mut entries = Vec::new();
+    collect_fdf_fields(fields, None, &mut entries);
+    Ok(entries)
+}
+
+/// Walk a /Fields or /Kids array, qualifying child names with their parent's
+fn collect_fdf_fields(
+    fields: &PdfObject,
+    parent: Option<&str>,
+    entries: &mut Vec<(String, String)>,
+) {
+    let PdfObject::Array(items) = fields else { return };
+    for field in items {
+        let Some(partial) = field.get("T").and_then(PdfObject::as_text) else { continue };
+        let name = match parent {
+            Some(parent) => format!("{}.{}", parent, partial),
+            None => partial,
+        };
+
+        if let Some(value) = field.get("V").and_then(PdfObject::as_text) {
+            entries.push((name.clone(), value));
+        }
+        if let Some(kids) = field.get("Kids") {
+            collect_fdf_fields(kids, Some(&name), entries);
+        }
+    }
+}
+
+/// UTF-16BE with a BOM, otherwise treated as Latin-1 (close to PDFDocEncoding)
+fn decode_pdf_text(bytes: &[u8]) -> String {
+    match bytes {
+        [0xFE, 0xFF, rest @ ..] => {
+            let units: Vec<u16> =
+                rest.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]])).collect();
+            String::from_utf16_lossy(&units)
+        }
+        _ => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+struct PdfLexer<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl PdfLexer<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b) = self.peek() {
+            if b == b'%' {
+                while self.peek().is_some_and(|b| b != b'\n' && b != b'\r') {
+                    self.pos += 1;
+                }
+            } else if b.is_ascii_whitespace() || b == 0 {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn starts_with(&self, token: &[u8]) -> bool {
+        self.bytes[self.pos..].starts_with(token)
+    }
+
+    fn next_object(&mut self) -> Result<Option<PdfObject>, String> {
+        self.skip_whitespace();
+        let Some(b) = self.peek() else { return Ok(None) };
+
+        let object = if self.starts_with(b"<<") {
+            self.pos += 2;
+            let mut entries = Vec::new();
+            loop {
+                self.skip_whitespace();
+                if self.starts_with(b">>") {
+                    self.pos += 2;
+                    break;
+                }
+                match self.next_object()? {
+                    Some(PdfObject::Name(key)) => {
+                        let value = self.next_object()?.ok_or("Unterminated FDF dictionary")?;
+                        entries.push((key, value));
+                    }
+                    // Trailing parts of an indirect reference value (`1 0 R`)
+                    Some(PdfObject::Other) if !entries.is_empty() => {}
+                    Some(_) => return Err("FDF dictionary key is not a name".to_string()),
+                    None => return Err("Unterminated FDF dictionary".to_string()),
+                }
+            }
+            PdfObject::Dict(entries)
+        } else if b == b'[' {
+            self.pos += 1;
+            let mut items = Vec::new();
+            loop {
+                self.skip_whitespace();
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    break;
+                }
+                items.push(self.next_object()?.ok_or("Unterminated FDF array")?);
+            }
+            PdfObject::Array(items)
+        } else if b == b'(' {
+            PdfObject::Str(self.literal_string()?)
+        } else if b == b'<' {
+            PdfObject::Str(self.hex_string()?)
+        } else if b == b'/' {
+            self.pos += 1;
+            PdfObject::Name(self.name())
+        } else if b == b'>' || b == b']' || b == b')' || b == b'{' || b == b'}' {
+            // Stray delimiter; skip it rather than failing the whole file
+            self.pos += 1;
+            PdfObject::Other
+        } else {
+            while self.peek().is_some_and(|b| !b.is_ascii_whitespace() && !is_delimiter(b)) {
+                self.pos += 1;
+            }
+            PdfObject::Other
+        };
+
+        Ok(Some(object))
+    }
+
+    fn name(&mut self) -> String {
+        let mut name = Vec::new();
+        while let Some(b) = self.peek().filter(|b| !b.is_ascii_whitespace() && !is_delimiter(*b)) {
+            self.pos += 1;
+            let escaped = (b == b'#')
+                .then(|| std::str::from_utf8(self.bytes.get(self.pos..self.pos + 2)?).ok())
+                .flatten()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            match escaped {
+                Some(decoded) => {
+                    name.push(decoded);
+                    self.pos += 2;
+                }
+                None => name.push(b),
+            }
+        }
+        String::from_utf8_lossy(&name).into_owned()
+    }
+
+    fn literal_string(&mut self) -> Result<Vec<u8>, String> {
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        let mut depth = 1;
+        loop {
+            let b = self.peek().ok_or("Unterminated FDF string")?;
+            self.pos += 1;
+            match b {
+                b'\\' => {
+                    let escaped = self.peek().ok_or("Unterminated FDF string")?;
+                    self.pos += 1;
+                    match escaped {
+                        b'n' => bytes.push(b'\n'),
+                        b'r' => bytes.push(b'\r'),
+                        b't' => bytes.push(b'\t'),
+                        b'b' => bytes.push(0x08),
+                        b'f' => bytes.push(0x0C),
+                        b'0'..=b'7' => {
+                            let mut code = (escaped - b'0') as u32;
+                            for _ in 0..2 {
+                                match self.peek() {
+                                    Some(d @ b'0'..=b'7') => {
+                                        code = code * 8 + (d - b'0') as u32;
+                                        self.pos += 1;
+                                    }
+                                    _ => break,
+                                }
+                            }
+                            bytes.push(code as u8);
+                        }
+                        // Escaped line break continues the string
+                        b'\r' => {
+                            if self.peek() == Some(b'\n') {
+                                self.pos += 1;
+                            }
+                        }
+                        b'\n' => {}
+                        other => bytes.push(other),
+                    }
+                }
+                b'(' => {
+                    depth += 1;
+                    bytes.push(b);
+                }
+                b')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(bytes);
+                    }
+                    bytes.push(b);
+                }
+                _ => bytes.push(b),
+            }
+        }
+    }
+
+    fn hex_string(&mut self) -> Result<Vec<u8>, String> {
+        self.pos += 1;
+        let mut digits = Vec::new();
+        loop {
+            let b = self.peek().ok_or("Unterminated FDF hex string")?;
+            self.pos += 1;
+            match b {
+                b'>' => break,
+                _ if b.is_ascii_hexdigit() => digits.push(b),
+                _ if b.is_ascii_whitespace() => {}
+                _ => return Err("Invalid character in FDF hex string".to_string()),
+            }
+        }
+        // An odd final digit is padded with 0
+        if digits.len() % 2 == 1 {
+            digits.push(b'0');
+        }
+        Ok(digits
+            .chunks_exact(2)
+            .map(|pair| {
+                u8::from_str_radix(std::str::from_utf8(pair).unwrap_or("00"), 16).unwrap_or(0)
+            })
+            .collect())
+    }
+}
+
+fn is_delimiter(b: u8) -> bool {
+    matches!(b, b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled_fl100() -> (FormTemplate, FormData) {
+        let template = FormTemplate::fl100();
+        let mut form_data = FormData::for_template(&template);
+        form_data
+            .set("petitioner_name", FieldValue::Text("Zoë O'Brien & Co (Trustee)".to_string()))
+            .unwrap();
+        form_data.set("child_1_birthdate", FieldValue::Date("2015-03-07".to_string())).unwrap();
+        form_data.set("separate_property", FieldValue::Checked(true)).unwrap();
+        (template, form_data)
+    }
+
+    #[test]
+    fn test_round_trip_both_formats() {
+        let (template, form_data) = filled_fl100();
+        let blank = FormData::for_template(&template);
+
+        for format in [ExchangeFormat::Xfdf, ExchangeFormat::Fdf] {
+            let exported = export_fields(&template, &form_data, format);
+            assert_eq!(ExchangeFormat::detect(&exported), Some(format));
+            assert!(exported.contains("Caption_sf[0].TitlePartyName[0].Party1_ft[0]"));
+            assert!(exported.contains("03/07/2015"));
+
+            let import = import_fields(&template, &blank, &exported).unwrap();
+            assert_eq!(import.form_data, form_data);
+            assert!(import.skipped.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_xfdf_nested_names_and_merge() {
+        let (template, base) = filled_fl100();
+        let xfdf = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <xfdf xmlns="http://ns.adobe.com/xfdf/">
+              <fields>
+                <field name="FL-100[0]"><field name="Page1[0]"><field name="Caption_sf[0]">
+                  <field name="CaseNumber[0]">
+                    <field name="CaseNumber_ft[0]"><value>FL&#45;2024&#x2D;001</value></field>
+                  </field>
+                </field></field></field>
+                <field name="community_property"><value>Yes</value></field>
+                <field name="Unrelated[0]"><value>x</value></field>
+              </fields>
+            </xfdf>"#;
+
+        let import = import_fields(&template, &base, xfdf).unwrap();
+        assert_eq!(import.imported, 2);
+        assert_eq!(import.form_data.text("case_number"), "FL-2024-001");
+        assert!(import.form_data.is_checked("community_property"));
+        // Values not in the file are kept
+        assert!(import.form_data.is_checked("separate_property"));
+        assert_eq!(import.skipped.len(), 1);
+    }
+
+    #[test]
+    fn test_impossible_dates_skipped() {
+        assert_eq!(parse_date("02/29/2024").as_deref(), Some("2024-02-29"));
+        assert_eq!(parse_date("2000-02-29").as_deref(), Some("2000-02-29"));
+        assert_eq!(parse_date("02/29/2023"), None);
+        assert_eq!(parse_date("1900-02-29"), None);
+        assert_eq!(parse_date("02/31/2024"), None);
+        assert_eq!(parse_date("04/31/2024"), None);
+        assert_eq!(parse_date("12/31/2024").as_deref(), Some("2024-12-31"));
+
+        let (template, base) = filled_fl100();
+        let xfdf = r#"<xfdf xmlns="http://ns.adobe.com/xfdf/"><fields>
+                <field name="child_1_birthdate"><value>04/31/2016</value></field>
+                <field name="community_property"><value>Yes</value></field>
+            </fields></xfdf>"#;
+        let import = import_fields(&template, &base, xfdf).unwrap();
+        assert_eq!(import.imported, 1);
+        assert_eq!(import.skipped, vec!["child_1_birthdate: '04/31/2016' is not a date"]);
+        // The date already in the form is kept rather than replaced by one no input can show
+        assert_eq!(import.form_data.get("child_1_birthdate"), base.get("child_1_birthdate"));
+    }
+
+    #[test]
+    fn test_fdf_kids_and_string_escapes() {
+        let template = FormTemplate::fl100();
+        let fdf = "%FDF-1.2\n%\u{e2}\u{e3}\n1 0 obj\n<< /FDF << /Fields [\n\
+            << /T (FL-100[0].Page1[0].Caption_sf[0]) /Kids [\n\
+              << /T (TitlePartyName[0]) /Kids [ << /T (Party2_ft[0]) /V (John \\(Jack\\) Doe\\\n) >> ] >>\n\
+            ] >>\n\
+            << /T <FEFF0063006100730065005F006E0075006D006200650072> /V (12\\0613) >>\n\
+            << /T (custody_arrangement) /V /Off >>\n\
+            ] >> >>\nendobj\ntrailer\n<< /Root 1 0 R >>\n%%EOF\n";
+
+        let import = import_fields(&template, &FormData::for_template(&template), fdf).unwrap();
+        assert_eq!(import.form_data.text("respondent_name"), "John (Jack) Doe");
+        assert_eq!(import.form_data.text("case_number"), "1213");
+        assert!(!import.form_data.is_checked("custody_arrangement"));
+
+        assert!(import_fields(&template, &FormData::for_template(&template), "%FDF-1.2\n").is_err());
+    }
+
+    #[test]
+    fn test_fdf_text_that_looks_like_a_state() {
+        let (template, mut form_data) = filled_fl100();
+        form_data.set("respondent_name", FieldValue::Text("Off".to_string())).unwrap();
+        form_data.set("case_number", FieldValue::Text("1".to_string())).unwrap();
+
+        // Only checkbox states are names; text is always a string
+        let fdf = export_fields(&template, &form_data, ExchangeFormat::Fdf);
+        assert!(fdf.contains("Party2_ft[0]) /V (Off) >>"));
+        assert!(fdf.contains("/V (1) >>"));
+        assert!(fdf.contains("/V /1 >>") && fdf.contains("/V /Off >>"));
+
+        let import = import_fields(&template, &FormData::for_template(&template), &fdf).unwrap();
+        assert_eq!(import.form_data, form_data);
+    }
+}
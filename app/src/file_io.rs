@@ -0,0 +1,123 @@
+//
+// ███████╗██╗██╗     ███████╗   ██╗ ██████╗
+// ██╔════╝██║██║     ██╔════╝   ██║██╔═══██╗
+// █████╗  ██║██║     █████╗     ██║██║   ██║
+// ██╔══╝  ██║██║     ██╔══╝     ██║██║   ██║
+// ██║     ██║███████╗███████╗   ██║╚██████╔╝
+// ╚═╝     ╚═╝╚══════╝╚══════╝   ╚═╝ ╚═════╝
+//                         app/src/file_io.rs
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    Blob, BlobPropertyBag, File, HtmlAnchorElement, HtmlIFrameElement, HtmlInputElement, Url,
+};
+use yew::{Event, TargetCast};
+
+/// How long a download's object URL is kept before it is released
+const DOWNLOAD_URL_LIFETIME_MS: i32 = 60_000;
+
+/// Offer text content to the user as a file download
+pub fn download_text(filename: &str, mime_type: &str, contents: &str) -> Result<(), String> {
+    download_bytes(filename, mime_type, contents.as_bytes())
+}
+
+/// Offer binary content to the user as a file download
+pub fn download_bytes(filename: &str, mime_type: &str, contents: &[u8]) -> Result<(), String> {
+    let document = web_sys::window().and_then(|w| w.document()).ok_or("No document available")?;
+    let url = object_url(mime_type, contents)?;
+
+    let anchor = document
+        .create_element("a")
+        .map_err(|e| format!("Failed to create download link: {:?}", e))?
+        .dyn_into::<HtmlAnchorElement>()
+        .map_err(|_| "Download link is not an anchor element")?;
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.click();
+
+    // Some browsers only start reading the blob after click() returns
+    let release = Closure::once_into_js(move || {
+        if let Err(error) = Url::revoke_object_url(&url) {
+            web_sys::console::warn_1(&error);
+        }
+    });
+    web_sys::window()
+        .ok_or("No window available")?
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            release.unchecked_ref(),
+            DOWNLOAD_URL_LIFETIME_MS,
+        )
+        .map_err(|e| format!("Failed to schedule download cleanup: {:?}", e))?;
+    Ok(())
+}
+
+/// Id of the hidden frame content is printed from
+const PRINT_FRAME_ID: &str = "print-frame";
+
+/// Open the print dialog for binary content, such as a PDF, through the browser's own
+/// viewer in a hidden frame. The frame stays until the next print, since there is no
+/// reliable signal that the dialog has closed.
+pub fn print_bytes(mime_type: &str, contents: &[u8]) -> Result<(), String> {
+    let document = web_sys::window().and_then(|w| w.document()).ok_or("No document available")?;
+    let body = document.body().ok_or("No document body")?;
+
+    if let Some(previous) = document.get_element_by_id(PRINT_FRAME_ID) {
+        if let Some(src) = previous.get_attribute("src") {
+            let _ = Url::revoke_object_url(&src);
+        }
+        previous.remove();
+    }
+
+    let url = object_url(mime_type, contents)?;
+    let frame = document
+        .create_element("iframe")
+        .map_err(|e| format!("Failed to create print frame: {:?}", e))?
+        .dyn_into::<HtmlIFrameElement>()
+        .map_err(|_| "Print frame is not an iframe element")?;
+    frame.set_id(PRINT_FRAME_ID);
+    frame
+        .set_attribute("style", "position: fixed; width: 0; height: 0; border: 0;")
+        .map_err(|e| format!("Failed to hide print frame: {:?}", e))?;
+
+    let loaded = frame.clone();
+    let on_load = Closure::once_into_js(move || {
+        if let Some(Err(error)) = loaded.content_window().map(|window| window.print()) {
+            web_sys::console::warn_1(&error);
+        }
+    });
+    frame.set_onload(Some(on_load.unchecked_ref()));
+    frame.set_src(&url);
+    body.append_child(&frame).map_err(|e| format!("Failed to add print frame: {:?}", e))?;
+    Ok(())
+}
+
+/// Object URL for a blob of the given contents
+fn object_url(mime_type: &str, contents: &[u8]) -> Result<String, String> {
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(contents));
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options)
+        .map_err(|e| format!("Failed to create download: {:?}", e))?;
+    Url::create_object_url_with_blob(&blob)
+        .map_err(|e| format!("Failed to create download URL: {:?}", e))
+}
+
+/// First file chosen in an `<input type="file">` change event, clearing the input so
+/// the same file can be picked again
+pub fn take_selected_file(e: &Event) -> Option<File> {
+    let input = e.target_dyn_into::<HtmlInputElement>()?;
+    let file = input.files()?.get(0);
+    input.set_value("");
+    file
+}
+
+/// Read a user-selected file as UTF-8 text
+pub async fn read_file_text(file: File) -> Result<String, String> {
+    let text = JsFuture::from(file.text())
+        .await
+        .map_err(|e| format!("Failed to read {}: {:?}", file.name(), e))?;
+
+    text.as_string().ok_or_else(|| format!("{} is not a text file", file.name()))
+}
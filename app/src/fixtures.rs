@@ -0,0 +1,39 @@
+//
+// ███████╗██╗██╗  ██╗████████╗██╗   ██╗██████╗ ███████╗███████╗
+// ██╔════╝██║╚██╗██╔╝╚══██╔══╝██║   ██║██╔══██╗██╔════╝██╔════╝
+// █████╗  ██║ ╚███╔╝    ██║   ██║   ██║██████╔╝█████╗  ███████╗
+// ██╔══╝  ██║ ██╔██╗    ██║   ██║   ██║██╔══██╗██╔══╝  ╚════██║
+// ██║     ██║██╔╝ ██╗   ██║   ╚██████╔╝██║  ██║███████╗███████║
+// ╚═╝     ╚═╝╚═╝  ╚═╝   ╚═╝    ╚═════╝ ╚═╝  ╚═╝╚══════╝╚══════╝
+//                                           app/src/fixtures.rs
+
+use crate::coordinates::PhysicalCoord;
+use crate::form_data::{FieldValue, FormData};
+use crate::form_template::FormTemplate;
+use crate::overlay::{Overlay, OverlayType, Transform};
+
+/// FL-100 with a few fields of each kind filled in and one rotated overlay
+pub fn sample_document() -> (FormTemplate, FormData, Vec<Overlay>) {
+    let template = FormTemplate::fl100();
+    let mut form_data = FormData::for_template(&template);
+    form_data.set("petitioner_name", FieldValue::Text("Jane (Doe) Smith".to_string())).unwrap();
+    form_data.set("case_number", FieldValue::Text("FL-2024-001".to_string())).unwrap();
+    form_data.set("child_1_birthdate", FieldValue::Date("2015-03-07".to_string())).unwrap();
+    form_data.set("separate_property", FieldValue::Checked(true)).unwrap();
+    let property = "Savings account at Golden State Credit Union\n2012 Honda Civic";
+    form_data.set("property_description", FieldValue::Text(property.to_string())).unwrap();
+
+    let overlay = Overlay {
+        id: "overlay_1".to_string(),
+        page_index: 0,
+        overlay_type: OverlayType::Shape,
+        transform: Transform { translate_x: 37.795, rotation: 90.0, ..Transform::default() },
+        position: PhysicalCoord { x: 50.0, y: 50.0 },
+        size: PhysicalCoord { x: 30.0, y: 20.0 },
+        visible: true,
+        selected: false,
+        z_index: 1,
+    };
+
+    (template, form_data, vec![overlay])
+}
@@ -7,12 +7,10 @@
 // ╚═╝      ╚═════╝ ╚═╝  ╚═══╝   ╚═╝       ╚═╝     ╚═╝╚══════╝   ╚═╝   ╚═╝  ╚═╝╚═╝ ╚═════╝╚══════╝
 //                                                app/src/font_metrics.rs
 
-use crate::coordinates::{PhysicalCoord, CoordinateSystem};
+use crate::afm::{self, win_ansi_byte};
+use crate::coordinates::{PhysicalCoord, CoordinateSystem, CSS_PX_PER_MM};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use wasm_bindgen::prelude::*;
-use wasm_bindgen::JsCast;
-use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlElement};
 
 /// PDF-standard font families supported for legal documents
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -45,6 +43,92 @@ impl StandardFont {
             _ => "normal",
         }
     }
+
+    /// PostScript name of the matching PDF Standard 14 font
+    pub fn pdf_base_font(&self) -> &'static str {
+        match self {
+            StandardFont::Helvetica => "Helvetica",
+            StandardFont::HelveticaBold => "Helvetica-Bold",
+            StandardFont::Times | StandardFont::TimesRoman => "Times-Roman",
+            StandardFont::Courier => "Courier",
+            StandardFont::CourierBold => "Courier-Bold",
+        }
+    }
+
+    /// AFM ascender in 1/1000 em
+    pub fn ascent_units(&self) -> f64 {
+        match self {
+            StandardFont::Helvetica | StandardFont::HelveticaBold => 718.0,
+            StandardFont::Times | StandardFont::TimesRoman => 683.0,
+            StandardFont::Courier | StandardFont::CourierBold => 629.0,
+        }
+    }
+
+    /// AFM descender in 1/1000 em (negative, below the baseline)
+    pub fn descent_units(&self) -> f64 {
+        match self {
+            StandardFont::Helvetica | StandardFont::HelveticaBold => -207.0,
+            StandardFont::Times | StandardFont::TimesRoman => -217.0,
+            StandardFont::Courier | StandardFont::CourierBold => -157.0,
+        }
+    }
+
+    /// Baseline below the top of a box `height_mm` tall that centres the font's
+    /// ascent-to-descent span in it; every export places single-line values this way
+    pub fn centred_baseline_mm(&self, size_pt: f64, height_mm: f64) -> f64 {
+        let mm_per_pt = 25.4 / 72.0;
+        height_mm / 2.0
+            + (self.ascent_units() + self.descent_units()) / 2000.0 * size_pt * mm_per_pt
+    }
+
+    /// AFM advance widths indexed by WinAnsi code minus 32
+    fn widths(&self) -> &'static [u16; 224] {
+        match self {
+            StandardFont::Helvetica => &afm::HELVETICA_WIDTHS,
+            StandardFont::HelveticaBold => &afm::HELVETICA_BOLD_WIDTHS,
+            StandardFont::Times | StandardFont::TimesRoman => &afm::TIMES_ROMAN_WIDTHS,
+            StandardFont::Courier => &afm::COURIER_WIDTHS,
+            StandardFont::CourierBold => &afm::COURIER_BOLD_WIDTHS,
+        }
+    }
+
+    /// AFM kerning pairs; Courier is monospaced and has none
+    fn kerning_pairs(&self) -> &'static [(char, char, i16)] {
+        match self {
+            StandardFont::Helvetica => afm::HELVETICA_KERNING,
+            StandardFont::HelveticaBold => afm::HELVETICA_BOLD_KERNING,
+            StandardFont::Times | StandardFont::TimesRoman => afm::TIMES_ROMAN_KERNING,
+            StandardFont::Courier | StandardFont::CourierBold => &[],
+        }
+    }
+
+    /// Advance width of a character in 1/1000 em. Characters outside WinAnsiEncoding
+    /// are measured as the `?` the PDF exporter prints in their place.
+    pub fn char_width_units(&self, c: char) -> f64 {
+        let code = win_ansi_byte(c).unwrap_or(b'?');
+        self.widths()[code.saturating_sub(32) as usize] as f64
+    }
+
+    /// Kerning adjustment between two adjacent characters in 1/1000 em
+    pub fn kerning_units(&self, left: char, right: char) -> f64 {
+        afm::kerning(self.kerning_pairs(), left, right) as f64
+    }
+
+    /// Width of a run of text in 1/1000 em, including kerning
+    pub fn text_width_units(&self, text: &str) -> f64 {
+        let advances: f64 = text.chars().map(|c| self.char_width_units(c)).sum();
+        let kerning: f64 = text
+            .chars()
+            .zip(text.chars().skip(1))
+            .map(|(left, right)| self.kerning_units(left, right))
+            .sum();
+        advances + kerning
+    }
+
+    /// Width of a run of text in points at the given size
+    pub fn text_width_pt(&self, text: &str, size_pt: f64) -> f64 {
+        self.text_width_units(text) / 1000.0 * size_pt
+    }
 }
 
 /// Precise font metrics measurements
@@ -70,48 +154,39 @@ pub struct FontMetrics {
     pub timestamp: u64,
 }
 
-/// Font metrics calculator for precise text positioning
+/// Font metrics calculator for precise text positioning.
+///
+/// Metrics come from the built-in AFM tables rather than a browser canvas, whose
+/// results depend on which fonts the OS substitutes, so layout matches PDF export.
 pub struct FontMetricsCalculator {
-    /// Canvas context for font measurements
-    context: Option<CanvasRenderingContext2d>,
     /// Cached font metrics by font and size
     metrics_cache: HashMap<(StandardFont, u32), FontMetrics>,
+    /// Cached string widths in 1/1000 em, which don't depend on the size
+    width_cache: HashMap<StandardFont, HashMap<String, f64>>,
+}
+
+/// Strings remembered per font before the width cache starts over
+const WIDTH_CACHE_LIMIT: usize = 4096;
+
+/// Smallest size auto-fit shrinks a value to, following court practice
+pub const MIN_AUTO_FIT_SIZE_PT: f64 = 6.0;
+
+impl Default for FontMetricsCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl FontMetricsCalculator {
     /// Create new font metrics calculator
     pub fn new() -> Self {
-        Self {
-            context: Self::create_measurement_context(),
-            metrics_cache: HashMap::new(),
-        }
+        Self { metrics_cache: HashMap::new(), width_cache: HashMap::new() }
     }
 
-    /// Create hidden canvas context for font measurements
-    fn create_measurement_context() -> Option<CanvasRenderingContext2d> {
-        let window = web_sys::window()?;
-        let document = window.document()?;
-        
-        // Create hidden canvas for measurements
-        let canvas = document
-            .create_element("canvas")
-            .ok()?
-            .dyn_into::<HtmlCanvasElement>()
-            .ok()?;
-        
-        canvas.set_width(100);
-        canvas.set_height(100);
-        
-        // Cast to HtmlElement to access style
-        let element: HtmlElement = canvas.clone().dyn_into().ok()?;
-        element.style().set_property("display", "none").ok()?;
-        
-        document.body()?.append_child(&canvas).ok()?;
-        
-        canvas
-            .get_context("2d")
-            .ok()?
-            .and_then(|ctx| ctx.dyn_into::<CanvasRenderingContext2d>().ok())
+    /// Same as `new`; kept for callers that want to spell out that results are
+    /// identical on every machine
+    pub fn with_builtin_metrics() -> Self {
+        Self::new()
     }
 
     /// Measure font metrics for given font and size
@@ -124,52 +199,96 @@ impl FontMetricsCalculator {
             return Some(cached.clone());
         }
 
-        let context = self.context.as_ref()?;
-        
         // Convert points to pixels (1pt = 96/72 px at 96 DPI)
         let size_px = size_pt * 96.0 / 72.0;
-        
-        // Set font on canvas context
-        let font_string = format!("{} {}px {}", 
-            font.css_weight(), 
-            size_px, 
-            font.css_family()
-        );
-        context.set_font(&font_string);
-        
-        // Measure text metrics using canvas
-        let test_text = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
-        let text_metrics = context.measure_text(test_text).ok()?;
-        
-        // Calculate font metrics
-        let ascent = text_metrics.actual_bounding_box_ascent();
-        let descent = text_metrics.actual_bounding_box_descent();
-        let line_height = ascent + descent;
-        let baseline_offset = ascent;
-        
-        // For monospace fonts, calculate character width
-        let char_width = if matches!(font, StandardFont::Courier | StandardFont::CourierBold) {
-            Some(context.measure_text("M").ok()?.width())
-        } else {
-            None
+
+        let metrics = Self::builtin_metrics(font, size_pt, size_px);
+        self.metrics_cache.insert(cache_key, metrics.clone());
+        Some(metrics)
+    }
+
+    /// Width of `text` in CSS pixels at `size_pt`: the sum of its glyph advances plus
+    /// kerning. Widths are cached per string, so re-checking a field on every keystroke
+    /// stays cheap.
+    pub fn measure_text_px(&mut self, font: &StandardFont, size_pt: f64, text: &str) -> f64 {
+        let widths = self.width_cache.entry(font.clone()).or_default();
+        let units = match widths.get(text) {
+            Some(units) => *units,
+            None => {
+                if widths.len() >= WIDTH_CACHE_LIMIT {
+                    widths.clear();
+                }
+                let units = font.text_width_units(text);
+                widths.insert(text.to_string(), units);
+                units
+            }
         };
-        
-        let metrics = FontMetrics {
+        units / 1000.0 * size_pt * 96.0 / 72.0
+    }
+
+    /// Whether `text` at `size_pt` fits within `width_mm` × `height_mm`: its advance width
+    /// across, and the font's ascent-to-descent box down
+    pub fn text_fits(
+        &mut self,
+        font: &StandardFont,
+        size_pt: f64,
+        text: &str,
+        width_mm: f64,
+        height_mm: f64,
+    ) -> bool {
+        let line_height_em = (font.ascent_units() - font.descent_units()) / 1000.0;
+        self.measure_text_px(font, size_pt, text) / CSS_PX_PER_MM <= width_mm
+            && line_height_em * size_pt * 25.4 / 72.0 <= height_mm
+    }
+
+    /// Largest size up to `max_size_pt`, in 0.1 pt steps, at which `text` fits within
+    /// `width_mm` × `height_mm`. Never goes below [`MIN_AUTO_FIT_SIZE_PT`]; text that
+    /// still overflows at the floor is clipped like any other value.
+    pub fn fit_font_size(
+        &mut self,
+        font: &StandardFont,
+        max_size_pt: f64,
+        text: &str,
+        width_mm: f64,
+        height_mm: f64,
+    ) -> f64 {
+        let mut fits = |size_pt: f64| self.text_fits(font, size_pt, text, width_mm, height_mm);
+        if max_size_pt <= MIN_AUTO_FIT_SIZE_PT || fits(max_size_pt) {
+            return max_size_pt;
+        }
+
+        // Binary search over tenths of a point; `low` always fits or is the floor
+        let mut low = (MIN_AUTO_FIT_SIZE_PT * 10.0).round() as u32;
+        let mut high = (max_size_pt * 10.0).ceil() as u32 - 1;
+        while low < high {
+            let mid = (low + high).div_ceil(2);
+            if fits(mid as f64 / 10.0) {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        low as f64 / 10.0
+    }
+
+    /// Metrics from the font's AFM ascender and descender
+    fn builtin_metrics(font: StandardFont, size_pt: f64, size_px: f64) -> FontMetrics {
+        let ascent = font.ascent_units() / 1000.0 * size_px;
+        let descent = -font.descent_units() / 1000.0 * size_px;
+        let char_width = matches!(font, StandardFont::Courier | StandardFont::CourierBold)
+            .then(|| font.char_width_units('M') / 1000.0 * size_px);
+
+        FontMetrics {
             font_size_pt: size_pt,
             font_size_px: size_px,
             ascent_px: ascent,
             descent_px: descent,
-            line_height_px: line_height,
+            line_height_px: ascent + descent,
             char_width_px: char_width,
-            baseline_offset_px: baseline_offset,
-            font_family: font.clone(),
-            timestamp: js_sys::Date::now() as u64,
-        };
-        
-        // Cache the result
-        self.metrics_cache.insert(cache_key, metrics.clone());
-        
-        Some(metrics)
+            baseline_offset_px: ascent,
+            font_family: font,
+            timestamp: 0,
+        }
     }
 
     /// Calculate text positioning for form field alignment
@@ -185,7 +304,7 @@ impl FontMetricsCalculator {
         
         // Convert field position to screen coordinates
         let field_screen = coord_system.physical_to_screen(field_position);
-        let field_height_px = coord_system.mm_to_px(field_height_mm);
+        let field_height_px = coord_system.mm_to_px_y(field_height_mm);
         
         // Calculate vertical centering within field
         let text_center_y = field_screen.y + (field_height_px / 2.0);
@@ -208,6 +327,7 @@ impl FontMetricsCalculator {
     /// Clear metrics cache
     pub fn clear_cache(&mut self) {
         self.metrics_cache.clear();
+        self.width_cache.clear();
     }
 }
 
@@ -238,13 +358,8 @@ impl TextPosition {
 
     /// Calculate text box dimensions
     pub fn calculate_text_bounds(&self, text: &str) -> TextBounds {
-        let text_width = if let Some(char_width) = self.font_metrics.char_width_px {
-            // Monospace font - use character width
-            text.len() as f64 * char_width
-        } else {
-            // Proportional font - estimate based on font size
-            text.len() as f64 * self.font_metrics.font_size_px * 0.6
-        };
+        let text_width =
+            self.font_metrics.font_family.text_width_units(text) / 1000.0 * self.font_metrics.font_size_px;
 
         TextBounds {
             x: self.baseline_x,
@@ -296,4 +411,86 @@ mod tests {
         assert_eq!(metrics.line_height_px, 16.0);
         assert!(metrics.char_width_px.is_some());
     }
+
+    #[test]
+    fn test_afm_text_widths() {
+        let helvetica = StandardFont::Helvetica;
+        assert_eq!(helvetica.char_width_units('W'), 944.0);
+        assert_eq!(helvetica.char_width_units('i'), 222.0);
+        assert_eq!(helvetica.char_width_units('é'), 556.0);
+        // Not in WinAnsi: measured as the '?' it is printed as
+        assert_eq!(helvetica.char_width_units('ł'), 556.0);
+
+        // "AV" kerns together; "VA" by a different amount
+        assert_eq!(helvetica.text_width_units("AV"), 667.0 + 667.0 - 70.0);
+        assert_eq!(StandardFont::Times.text_width_units("VA"), 722.0 + 722.0 - 135.0);
+        assert_eq!(StandardFont::TimesRoman.text_width_units("x"), StandardFont::Times.text_width_units("x"));
+        assert_eq!(StandardFont::Courier.text_width_pt("AVATAR", 10.0), 36.0);
+
+        let mut calculator = FontMetricsCalculator::new();
+        let metrics = calculator.measure_font(StandardFont::HelveticaBold, 12.0).unwrap();
+        let position = TextPosition { baseline_x: 0.0, baseline_y: 20.0, font_metrics: metrics };
+        let bounds = position.calculate_text_bounds("Total");
+        let expected = StandardFont::HelveticaBold.text_width_pt("Total", 12.0) * 96.0 / 72.0;
+        assert!((bounds.width - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_measure_text_counts_characters() {
+        let mut calculator = FontMetricsCalculator::new();
+        // 12 characters but 14 UTF-8 bytes; each accented letter is one glyph
+        let name = "Zoë Ångström";
+        let width = calculator.measure_text_px(&StandardFont::Helvetica, 12.0, name);
+        let expected = StandardFont::Helvetica.text_width_units(name) / 1000.0 * 16.0;
+        assert!((width - expected).abs() < 1e-9);
+        let plain = calculator.measure_text_px(&StandardFont::Helvetica, 12.0, "Zoe Angstrom");
+        assert!((width - plain).abs() < 1e-9);
+
+        // Cached in em units, so another size reuses the entry
+        let doubled = calculator.measure_text_px(&StandardFont::Helvetica, 24.0, name);
+        assert!((doubled - width * 2.0).abs() < 1e-9);
+        assert_eq!(calculator.width_cache[&StandardFont::Helvetica].len(), 2);
+
+        // Wide capitals overflow where a per-character average would say they fit
+        let wide = "WWWWWWWWWW";
+        let wide_width = calculator.measure_text_px(&StandardFont::Helvetica, 12.0, wide);
+        assert!(wide_width > 10.0 * 16.0 * 0.6);
+    }
+
+    #[test]
+    fn test_text_fits_in_millimetres() {
+        let mut calculator = FontMetricsCalculator::new();
+        let font = StandardFont::Helvetica;
+        // "Jane Doe" at 12pt is about 18mm wide and 3.9mm from ascent to descent
+        let width_mm = calculator.measure_text_px(&font, 12.0, "Jane Doe") / CSS_PX_PER_MM;
+        assert!(calculator.text_fits(&font, 12.0, "Jane Doe", width_mm, 5.0));
+        assert!(!calculator.text_fits(&font, 12.0, "Jane Doe", width_mm - 0.1, 5.0));
+        assert!(!calculator.text_fits(&font, 12.0, "Jane Doe", 60.0, 3.0));
+    }
+
+    #[test]
+    fn test_fit_font_size() {
+        let mut calculator = FontMetricsCalculator::new();
+        let font = StandardFont::Helvetica;
+        let name = "Alexandra Catherine Montgomery-Wellington";
+        let width_mm = |calculator: &mut FontMetricsCalculator, size_pt: f64| {
+            calculator.measure_text_px(&font, size_pt, name) / CSS_PX_PER_MM
+        };
+
+        // Values that fit keep the requested size
+        assert_eq!(calculator.fit_font_size(&font, 12.0, "Jane Doe", 60.0, 10.0), 12.0);
+
+        // Overflowing values get the largest tenth of a point that fits
+        let size = calculator.fit_font_size(&font, 12.0, name, 80.0, 10.0);
+        assert!(size < 12.0 && size > MIN_AUTO_FIT_SIZE_PT);
+        assert!(width_mm(&mut calculator, size) <= 80.0);
+        assert!(width_mm(&mut calculator, size + 0.1) > 80.0);
+        assert_eq!(size, (size * 10.0).round() / 10.0);
+
+        // Short fields constrain by line height, and nothing goes below the floor
+        let size = calculator.fit_font_size(&font, 12.0, "Jane Doe", 60.0, 3.0);
+        assert!((font.ascent_units() - font.descent_units()) / 1000.0 * size * 25.4 / 72.0 <= 3.0);
+        let floor = calculator.fit_font_size(&font, 12.0, name, 20.0, 10.0);
+        assert_eq!(floor, MIN_AUTO_FIT_SIZE_PT);
+    }
 }
\ No newline at end of file
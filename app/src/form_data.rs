@@ -0,0 +1,217 @@
+//
+// ███████╗ ██████╗ ██████╗ ███╗   ███╗   ██████╗  █████╗ ████████╗ █████╗
+// ██╔════╝██╔═══██╗██╔══██╗████╗ ████║   ██╔══██╗██╔══██╗╚══██╔══╝██╔══██╗
+// █████╗  ██║   ██║██████╔╝██╔████╔██║   ██║  ██║███████║   ██║   ███████║
+// ██╔══╝  ██║   ██║██╔══██╗██║╚██╔╝██║   ██║  ██║██╔══██║   ██║   ██╔══██║
+// ██║     ╚██████╔╝██║  ██║██║ ╚═╝ ██║   ██████╔╝██║  ██║   ██║   ██║  ██║
+// ╚═╝      ╚═════╝ ╚═╝  ╚═╝╚═╝     ╚═╝   ╚═════╝ ╚═╝  ╚═╝   ╚═╝   ╚═╝  ╚═╝
+//                                                     app/src/form_data.rs
+
+use crate::form_template::{FieldType, FormTemplate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::mem::discriminant;
+
+/// Typed value of a single form field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FieldValue {
+    Text(String),
+    Checked(bool),
+    /// ISO 8601 date (`YYYY-MM-DD`) as produced by `<input type="date">`
+    Date(String),
+    /// Signature image as a data URL, empty when unsigned
+    Signature(String),
+}
+
+impl FieldValue {
+    /// Empty value matching a field type
+    pub fn empty_for(field_type: &FieldType) -> Self {
+        match field_type {
+            FieldType::TextInput | FieldType::MultilineText | FieldType::Comb => {
+                FieldValue::Text(String::new())
+            }
+            FieldType::Checkbox => FieldValue::Checked(false),
+            FieldType::Date => FieldValue::Date(String::new()),
+            FieldType::Signature => FieldValue::Signature(String::new()),
+        }
+    }
+
+    /// Whether the field has been left blank
+    pub fn is_empty(&self) -> bool {
+        match self {
+            FieldValue::Text(text) | FieldValue::Date(text) | FieldValue::Signature(text) => {
+                text.is_empty()
+            }
+            FieldValue::Checked(checked) => !checked,
+        }
+    }
+}
+
+/// A single field edit, emitted whenever a stored value actually changes
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field_id: String,
+    pub old_value: FieldValue,
+    pub new_value: FieldValue,
+}
+
+/// `YYYY-MM-DD` as `MM/DD/YYYY`, the form's printed date style; anything else is
+/// passed through unchanged
+pub fn format_us_date(iso: &str) -> String {
+    let parts: Vec<&str> = iso.split('-').collect();
+    match parts.as_slice() {
+        [year, month, day] if year.len() == 4 => format!("{}/{}/{}", month, day, year),
+        _ => iso.to_string(),
+    }
+}
+
+/// Field values for every page of a form, keyed by `FormFieldInfo::id`
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct FormData {
+    values: BTreeMap<String, FieldValue>,
+}
+
+impl FormData {
+    /// Empty values for every field in a template
+    pub fn for_template(template: &FormTemplate) -> Self {
+        let values = template
+            .pages
+            .iter()
+            .flat_map(|page| page.fields.iter())
+            .map(|field| (field.id.clone(), FieldValue::empty_for(&field.field_type)))
+            .collect();
+
+        Self { values }
+    }
+
+    /// Re-key the store for an updated template, keeping values whose field still exists
+    /// with the same type
+    pub fn sync_with_template(&mut self, template: &FormTemplate) {
+        let mut synced = Self::for_template(template);
+        for (field_id, slot) in synced.values.iter_mut() {
+            if let Some(existing) = self.values.get(field_id) {
+                if discriminant(existing) == discriminant(slot) {
+                    *slot = existing.clone();
+                }
+            }
+        }
+
+        self.values = synced.values;
+    }
+
+    /// Get the value of a field
+    pub fn get(&self, field_id: &str) -> Option<&FieldValue> {
+        self.values.get(field_id)
+    }
+
+    /// Text content of a text or date field, empty if unset
+    pub fn text(&self, field_id: &str) -> String {
+        match self.values.get(field_id) {
+            Some(FieldValue::Text(text)) | Some(FieldValue::Date(text)) => text.clone(),
+            _ => String::new(),
+        }
+    }
+
+    /// Whether a checkbox field is checked
+    pub fn is_checked(&self, field_id: &str) -> bool {
+        matches!(self.values.get(field_id), Some(FieldValue::Checked(true)))
+    }
+
+    /// Signature data of a signature field, if signed
+    pub fn signature(&self, field_id: &str) -> Option<String> {
+        match self.values.get(field_id) {
+            Some(FieldValue::Signature(data)) if !data.is_empty() => Some(data.clone()),
+            _ => None,
+        }
+    }
+
+    /// Store a value, returning the change if the stored value differs
+    pub fn set(
+        &mut self,
+        field_id: &str,
+        value: FieldValue,
+    ) -> Result<Option<FieldChange>, String> {
+        let slot =
+            self.values.get_mut(field_id).ok_or_else(|| format!("Unknown field '{}'", field_id))?;
+
+        if discriminant(slot) != discriminant(&value) {
+            return Err(format!(
+                "Field '{}': value {:?} does not match the field type",
+                field_id, value
+            ));
+        }
+
+        if *slot == value {
+            return Ok(None);
+        }
+
+        let old_value = std::mem::replace(slot, value.clone());
+
+        Ok(Some(FieldChange { field_id: field_id.to_string(), old_value, new_value: value }))
+    }
+
+    /// All stored values in field id order
+    pub fn values(&self) -> &BTreeMap<String, FieldValue> {
+        &self.values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_covers_all_pages() {
+        let data = FormData::for_template(&FormTemplate::fl100());
+
+        assert_eq!(data.get("petitioner_name"), Some(&FieldValue::Text(String::new())));
+        assert_eq!(data.get("custody_arrangement"), Some(&FieldValue::Checked(false)));
+        assert_eq!(data.get("petitioner_signature"), Some(&FieldValue::Signature(String::new())));
+        assert!(data.values().values().all(FieldValue::is_empty));
+    }
+
+    #[test]
+    fn test_set_reports_changes() {
+        let mut data = FormData::for_template(&FormTemplate::fl100());
+
+        let change = data.set("case_number", FieldValue::Text("FL-2026-001".to_string())).unwrap();
+        assert_eq!(
+            change,
+            Some(FieldChange {
+                field_id: "case_number".to_string(),
+                old_value: FieldValue::Text(String::new()),
+                new_value: FieldValue::Text("FL-2026-001".to_string()),
+            })
+        );
+        assert_eq!(data.text("case_number"), "FL-2026-001");
+
+        // Setting the same value again is not a change
+        assert_eq!(data.set("case_number", FieldValue::Text("FL-2026-001".to_string())), Ok(None));
+    }
+
+    #[test]
+    fn test_set_rejects_unknown_and_mistyped_fields() {
+        let mut data = FormData::for_template(&FormTemplate::fl100());
+
+        assert!(data
+            .set("no_such_field", FieldValue::Checked(true))
+            .unwrap_err()
+            .contains("no_such_field"));
+        assert!(data.set("separate_property", FieldValue::Text("yes".to_string())).is_err());
+        assert!(!data.is_checked("separate_property"));
+    }
+
+    #[test]
+    fn test_sync_keeps_matching_values() {
+        let mut template = FormTemplate::fl100();
+        let mut data = FormData::for_template(&template);
+        data.set("petitioner_name", FieldValue::Text("Jane Doe".to_string())).unwrap();
+        data.set("separate_property", FieldValue::Checked(true)).unwrap();
+
+        template.pages[2].fields[0].field_type = FieldType::TextInput;
+        data.sync_with_template(&template);
+
+        assert_eq!(data.text("petitioner_name"), "Jane Doe");
+        assert_eq!(data.get("separate_property"), Some(&FieldValue::Text(String::new())));
+    }
+}
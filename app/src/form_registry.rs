@@ -0,0 +1,124 @@
+//
+// ███████╗ ██████╗ ██████╗ ███╗   ███╗   ██████╗ ███████╗ ██████╗ ██╗███████╗████████╗██████╗ ██╗   ██╗
+// ██╔════╝██╔═══██╗██╔══██╗████╗ ████║   ██╔══██╗██╔════╝██╔════╝ ██║██╔════╝╚══██╔══╝██╔══██╗╚██╗ ██╔╝
+// █████╗  ██║   ██║██████╔╝██╔████╔██║   ██████╔╝█████╗  ██║  ███╗██║███████╗   ██║   ██████╔╝ ╚████╔╝
+// ██╔══╝  ██║   ██║██╔══██╗██║╚██╔╝██║   ██╔══██╗██╔══╝  ██║   ██║██║╚════██║   ██║   ██╔══██╗  ╚██╔╝
+// ██║     ╚██████╔╝██║  ██║██║ ╚═╝ ██║   ██║  ██║███████╗╚██████╔╝██║███████║   ██║   ██║  ██║   ██║
+// ╚═╝      ╚═════╝ ╚═╝  ╚═╝╚═╝     ╚═╝   ╚═╝  ╚═╝╚══════╝ ╚═════╝ ╚═╝╚══════╝   ╚═╝   ╚═╝  ╚═╝   ╚═╝
+//                                                                              app/src/form_registry.rs
+
+#[cfg(feature = "web")]
+use crate::form_template::fetch_text;
+use crate::form_template::FormTemplate;
+use serde::Deserialize;
+
+/// Directory the template JSON files are served from
+pub const TEMPLATE_BASE_URL: &str = "/static/templates/";
+
+/// Manifest listing the served templates, relative to `TEMPLATE_BASE_URL`
+#[cfg(feature = "web")]
+const TEMPLATE_MANIFEST_FILE: &str = "index.json";
+
+/// Compiled-in templates registered alongside FL-100
+const BUILTIN_TEMPLATE_JSON: [&str; 3] = [
+    include_str!("../static/templates/fl105.json"),
+    include_str!("../static/templates/fl110.json"),
+    include_str!("../static/templates/fl115.json"),
+];
+
+/// Form id opened when the app starts
+pub const DEFAULT_FORM_ID: &str = "FL-100";
+
+#[derive(Debug, Deserialize)]
+struct TemplateManifest {
+    templates: Vec<String>,
+}
+
+/// Collection of form templates available to the picker, in display order
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FormRegistry {
+    templates: Vec<FormTemplate>,
+}
+
+impl FormRegistry {
+    /// Registry with every compiled-in template
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        registry.register(FormTemplate::fl100());
+        for json in BUILTIN_TEMPLATE_JSON {
+            registry.register(FormTemplate::from_json(json).expect("built-in template is valid"));
+        }
+        registry
+    }
+
+    /// Add a template, replacing any existing template with the same form id
+    pub fn register(&mut self, template: FormTemplate) {
+        if let Some(existing) = self.templates.iter_mut().find(|t| t.form_id == template.form_id) {
+            *existing = template;
+        } else {
+            self.templates.push(template);
+        }
+    }
+
+    /// Look up a template by form id
+    pub fn get(&self, form_id: &str) -> Option<&FormTemplate> {
+        self.templates.iter().find(|t| t.form_id == form_id)
+    }
+
+    /// All registered templates in display order
+    pub fn templates(&self) -> &[FormTemplate] {
+        &self.templates
+    }
+}
+
+/// Parse the template manifest into absolute template URLs
+pub fn parse_manifest(json: &str) -> Result<Vec<String>, String> {
+    let manifest: TemplateManifest =
+        serde_json::from_str(json).map_err(|e| format!("Invalid template manifest: {}", e))?;
+
+    Ok(manifest
+        .templates
+        .iter()
+        .map(|file| format!("{}{}", TEMPLATE_BASE_URL, file.trim_start_matches('/')))
+        .collect())
+}
+
+/// Fetch the list of served template URLs
+#[cfg(feature = "web")]
+pub async fn fetch_manifest() -> Result<Vec<String>, String> {
+    let json = fetch_text(&format!("{}{}", TEMPLATE_BASE_URL, TEMPLATE_MANIFEST_FILE)).await?;
+    parse_manifest(&json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_registry() {
+        let registry = FormRegistry::builtin();
+        let ids: Vec<_> = registry.templates().iter().map(|t| t.form_id.as_str()).collect();
+
+        assert_eq!(ids, vec!["FL-100", "FL-105", "FL-110", "FL-115"]);
+        assert_eq!(registry.get(DEFAULT_FORM_ID).unwrap().pages.len(), 3);
+        assert!(registry.get("FL-999").is_none());
+    }
+
+    #[test]
+    fn test_register_replaces_same_form() {
+        let mut registry = FormRegistry::builtin();
+        let mut updated = FormTemplate::fl100();
+        updated.revision = "2026-07-01".to_string();
+        registry.register(updated);
+
+        assert_eq!(registry.templates().len(), 4);
+        assert_eq!(registry.templates()[0].revision, "2026-07-01");
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let urls = parse_manifest(r#"{ "templates": ["fl100.json", "/fl105.json"] }"#).unwrap();
+        assert_eq!(urls, vec!["/static/templates/fl100.json", "/static/templates/fl105.json"]);
+        assert!(parse_manifest("[]").is_err());
+    }
+}
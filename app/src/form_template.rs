@@ -0,0 +1,356 @@
+//
+// ███████╗ ██████╗ ██████╗ ███╗   ███╗   ████████╗███████╗███╗   ███╗██████╗ ██╗      █████╗ ████████╗███████╗
+// ██╔════╝██╔═══██╗██╔══██╗████╗ ████║   ╚══██╔══╝██╔════╝████╗ ████║██╔══██╗██║     ██╔══██╗╚══██╔══╝██╔════╝
+// █████╗  ██║   ██║██████╔╝██╔████╔██║      ██║   █████╗  ██╔████╔██║██████╔╝██║     ███████║   ██║   █████╗
+// ██╔══╝  ██║   ██║██╔══██╗██║╚██╔╝██║      ██║   ██╔══╝  ██║╚██╔╝██║██╔═══╝ ██║     ██╔══██║   ██║   ██╔══╝
+// ██║     ╚██████╔╝██║  ██║██║ ╚═╝ ██║      ██║   ███████╗██║ ╚═╝ ██║██║     ███████╗██║  ██║   ██║   ███████╗
+// ╚═╝      ╚═════╝ ╚═╝  ╚═╝╚═╝     ╚═╝      ╚═╝   ╚══════╝╚═╝     ╚═╝╚═╝     ╚══════╝╚═╝  ╚═╝   ╚═╝   ╚══════╝
+//                                                                                     app/src/form_template.rs
+
+use crate::coordinates::{PageFormat, PhysicalCoord};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+#[cfg(feature = "web")]
+use wasm_bindgen::JsCast;
+#[cfg(feature = "web")]
+use wasm_bindgen_futures::JsFuture;
+#[cfg(feature = "web")]
+use web_sys::Response;
+
+/// Template schema version understood by this build
+pub const TEMPLATE_SCHEMA_VERSION: u32 = 1;
+
+/// Compiled-in copy of the FL-100 template, used until the served copy loads
+const FL100_TEMPLATE_JSON: &str = include_str!("../static/templates/fl100.json");
+
+/// A complete form definition loaded from a versioned JSON template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormTemplate {
+    pub schema_version: u32,
+    pub form_id: String,
+    pub revision: String,
+    pub title: String,
+    pub pages: Vec<PageInfo>,
+    /// Official PDF field names keyed by `FormFieldInfo::id`, used for XFDF/FDF exchange
+    #[serde(default)]
+    pub pdf_field_aliases: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageInfo {
+    pub page_number: usize,
+    pub title: String,
+    /// Paper size and orientation; US Letter portrait when omitted
+    #[serde(default)]
+    pub format: PageFormat,
+    pub fields: Vec<FormFieldInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormFieldInfo {
+    pub id: String,
+    pub field_type: FieldType,
+    pub position: PhysicalCoord,
+    pub size: PhysicalCoord,
+    pub label: String,
+    /// Shrink overflowing text values to fit, down to `MIN_AUTO_FIT_SIZE_PT`
+    #[serde(default)]
+    pub auto_fit: bool,
+    /// Baseline spacing of a multi-line field, matching the form's printed lines
+    #[serde(default)]
+    pub line_pitch_mm: Option<f64>,
+    /// Lines a multi-line field holds before the rest continues on an attachment page
+    #[serde(default)]
+    pub max_lines: Option<usize>,
+    /// Number of character boxes in a comb field
+    #[serde(default)]
+    pub comb_cells: Option<usize>,
+    /// Width of each character box in a comb field
+    #[serde(default)]
+    pub comb_pitch_mm: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FieldType {
+    TextInput,
+    /// Word-wrapped text on the form's printed lines
+    MultilineText,
+    /// One character per pre-drawn box, e.g. case numbers
+    Comb,
+    Checkbox,
+    Signature,
+    Date,
+}
+
+impl FormTemplate {
+    /// Parse and validate a template from its JSON representation
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let template: FormTemplate =
+            serde_json::from_str(json).map_err(|e| format!("Invalid template JSON: {}", e))?;
+        template.validate()?;
+        Ok(template)
+    }
+
+    /// Built-in FL-100 template
+    pub fn fl100() -> Self {
+        Self::from_json(FL100_TEMPLATE_JSON).expect("built-in FL-100 template is valid")
+    }
+
+    /// Check the template for structural errors, naming the offending page or field
+    pub fn validate(&self) -> Result<(), String> {
+        if self.schema_version != TEMPLATE_SCHEMA_VERSION {
+            return Err(format!(
+                "Unsupported template schema version {} (expected {})",
+                self.schema_version, TEMPLATE_SCHEMA_VERSION
+            ));
+        }
+
+        if self.form_id.trim().is_empty() {
+            return Err("Template form_id must not be empty".to_string());
+        }
+
+        if self.pages.is_empty() {
+            return Err(format!("Template {} has no pages", self.form_id));
+        }
+
+        let mut seen_ids: HashMap<&str, usize> = HashMap::new();
+
+        for (idx, page) in self.pages.iter().enumerate() {
+            if page.page_number != idx + 1 {
+                return Err(format!(
+                    "Page {}: page_number {} is out of sequence",
+                    idx + 1,
+                    page.page_number
+                ));
+            }
+
+            page.format.validate().map_err(|e| format!("Page {}: {}", page.page_number, e))?;
+
+            for (field_idx, field) in page.fields.iter().enumerate() {
+                if field.id.trim().is_empty() {
+                    return Err(format!(
+                        "Page {}: field #{} has an empty id",
+                        page.page_number,
+                        field_idx + 1
+                    ));
+                }
+
+                if let Some(first_page) = seen_ids.insert(&field.id, page.page_number) {
+                    return Err(format!(
+                        "Field '{}' on page {}: duplicate id (first defined on page {})",
+                        field.id, page.page_number, first_page
+                    ));
+                }
+
+                Self::validate_field(page, field)?;
+            }
+        }
+
+        let mut seen_aliases = HashSet::new();
+        for (field_id, pdf_name) in &self.pdf_field_aliases {
+            if !seen_ids.contains_key(field_id.as_str()) {
+                return Err(format!(
+                    "PDF alias '{}' refers to unknown field '{}'",
+                    pdf_name, field_id
+                ));
+            }
+            if pdf_name.trim().is_empty() || !seen_aliases.insert(pdf_name.as_str()) {
+                return Err(format!(
+                    "Field '{}': PDF alias '{}' is empty or duplicated",
+                    field_id, pdf_name
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Official PDF field name for a field, falling back to its id
+    pub fn pdf_field_name<'a>(&'a self, field_id: &'a str) -> &'a str {
+        self.pdf_field_aliases.get(field_id).map(String::as_str).unwrap_or(field_id)
+    }
+
+    /// Field id for a PDF field name, accepting either the alias or the plain id
+    pub fn field_for_pdf_name(&self, pdf_name: &str) -> Option<&FormFieldInfo> {
+        let field_id = self
+            .pdf_field_aliases
+            .iter()
+            .find(|(_, alias)| alias.as_str() == pdf_name)
+            .map(|(field_id, _)| field_id.as_str())
+            .unwrap_or(pdf_name);
+
+        self.pages.iter().flat_map(|page| page.fields.iter()).find(|field| field.id == field_id)
+    }
+
+    /// Check a single field's geometry against the page
+    fn validate_field(page: &PageInfo, field: &FormFieldInfo) -> Result<(), String> {
+        let page_number = page.page_number;
+        let values = [field.position.x, field.position.y, field.size.x, field.size.y];
+        if values.iter().any(|v| !v.is_finite()) {
+            return Err(format!(
+                "Field '{}' on page {}: position and size must be finite numbers",
+                field.id, page_number
+            ));
+        }
+
+        if field.size.x <= 0.0 || field.size.y <= 0.0 {
+            return Err(format!(
+                "Field '{}' on page {}: size must be positive, got {} x {} mm",
+                field.id, page_number, field.size.x, field.size.y
+            ));
+        }
+
+        if field.position.x < 0.0
+            || field.position.y < 0.0
+            || field.position.x + field.size.x > page.format.width_mm()
+            || field.position.y + field.size.y > page.format.height_mm()
+        {
+            return Err(format!(
+                "Field '{}' on page {}: extends beyond the {} x {} mm {} page",
+                field.id,
+                page_number,
+                page.format.width_mm(),
+                page.format.height_mm(),
+                page.format
+            ));
+        }
+
+        if let Some(pitch) = field.line_pitch_mm {
+            if !pitch.is_finite() || pitch <= 0.0 || pitch > field.size.y {
+                return Err(format!(
+                    "Field '{}' on page {}: line pitch {} mm does not fit its {} mm height",
+                    field.id, page_number, pitch, field.size.y
+                ));
+            }
+            if let Some(max_lines) = field.max_lines {
+                if max_lines as f64 * pitch > field.size.y + 1e-6 {
+                    return Err(format!(
+                        "Field '{}' on page {}: {} lines at {} mm exceed its {} mm height",
+                        field.id, page_number, max_lines, pitch, field.size.y
+                    ));
+                }
+            }
+        }
+
+        if field.field_type == FieldType::Comb {
+            let (Some(cells), Some(pitch)) = (field.comb_cells, field.comb_pitch_mm) else {
+                return Err(format!(
+                    "Field '{}' on page {}: comb fields need comb_cells and comb_pitch_mm",
+                    field.id, page_number
+                ));
+            };
+            if cells == 0 || !pitch.is_finite() || pitch <= 0.0 {
+                return Err(format!(
+                    "Field '{}' on page {}: comb needs at least one cell and a positive pitch",
+                    field.id, page_number
+                ));
+            }
+            if cells as f64 * pitch > field.size.x + 1e-6 {
+                return Err(format!(
+                    "Field '{}' on page {}: {} cells at {} mm exceed its {} mm width",
+                    field.id, page_number, cells, pitch, field.size.x
+                ));
+            }
+        }
+
+        if field.max_lines == Some(0) {
+            return Err(format!(
+                "Field '{}' on page {}: max_lines must be at least 1",
+                field.id, page_number
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Fetch a text resource served alongside the app
+#[cfg(feature = "web")]
+pub async fn fetch_text(url: &str) -> Result<String, String> {
+    let window = web_sys::window().ok_or("No window object")?;
+
+    let response: Response = JsFuture::from(window.fetch_with_str(url))
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {:?}", url, e))?
+        .dyn_into()
+        .map_err(|_| "Invalid fetch response".to_string())?;
+
+    if !response.ok() {
+        return Err(format!("Failed to fetch {}: HTTP {}", url, response.status()));
+    }
+
+    JsFuture::from(response.text().map_err(|_| format!("Failed to read {}", url))?)
+        .await
+        .map_err(|e| format!("Failed to read {}: {:?}", url, e))?
+        .as_string()
+        .ok_or_else(|| format!("Response from {} is not text", url))
+}
+
+/// Fetch and validate a template served alongside the app
+#[cfg(feature = "web")]
+pub async fn fetch_template(url: &str) -> Result<FormTemplate, String> {
+    let json = fetch_text(url).await?;
+    FormTemplate::from_json(&json).map_err(|e| format!("{}: {}", url, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinates::{Orientation, PaperSize};
+
+    #[test]
+    fn test_builtin_fl100_template() {
+        let template = FormTemplate::fl100();
+
+        assert_eq!(template.form_id, "FL-100");
+        assert_eq!(template.pages.len(), 3);
+        assert_eq!(template.pages[0].fields[2].id, "case_number");
+        assert_eq!(template.pages[2].fields[2].field_type, FieldType::Signature);
+        assert_eq!(template.pages[2].fields[3].field_type, FieldType::MultilineText);
+
+        // Six 6.35 mm lines fill the property box exactly; a seventh doesn't fit
+        let mut template = template;
+        template.pages[2].fields[3].max_lines = Some(7);
+        let error = template.validate().unwrap_err();
+        assert!(error.contains("property_description") && error.contains("7 lines"));
+
+        // The case number is a comb of twelve 5 mm boxes filling its 60 mm width
+        assert_eq!(template.pages[0].fields[2].field_type, FieldType::Comb);
+        template.pages[2].fields[3].max_lines = Some(6);
+        template.pages[0].fields[2].comb_cells = Some(13);
+        let error = template.validate().unwrap_err();
+        assert!(error.contains("case_number") && error.contains("13 cells"));
+        template.pages[0].fields[2].comb_pitch_mm = None;
+        assert!(template.validate().unwrap_err().contains("comb_pitch_mm"));
+    }
+
+    #[test]
+    fn test_validation_names_bad_field() {
+        let mut template = FormTemplate::fl100();
+        template.pages[1].fields[0].size.x = 500.0;
+        let error = template.validate().unwrap_err();
+        assert!(error.contains("child_1_name"));
+
+        // The same field fits once the page is legal-size landscape
+        template.pages[1].fields[0].size.x = 300.0;
+        template.pages[1].format = PageFormat::new(PaperSize::Legal, Orientation::Landscape);
+        assert!(template.validate().is_ok());
+
+        let mut template = FormTemplate::fl100();
+        template.pages[2].fields[0].id = "petitioner_name".to_string();
+        let error = template.validate().unwrap_err();
+        assert!(error.contains("petitioner_name") && error.contains("duplicate"));
+
+        let mut template = FormTemplate::fl100();
+        template.pdf_field_aliases.insert("no_such_field".to_string(), "X[0]".to_string());
+        assert!(template.validate().unwrap_err().contains("no_such_field"));
+    }
+
+    #[test]
+    fn test_schema_version_check() {
+        let json =
+            FL100_TEMPLATE_JSON.replacen("\"schema_version\": 1", "\"schema_version\": 99", 1);
+        assert!(FormTemplate::from_json(&json).unwrap_err().contains("schema version 99"));
+        assert!(FormTemplate::from_json("{ \"form_id\": \"FL-100\" }").is_err());
+    }
+}
@@ -0,0 +1,32 @@
+//
+//  ██████╗  ██████╗ ██╗     ██████╗ ███████╗███╗   ██╗
+// ██╔════╝ ██╔═══██╗██║     ██╔══██╗██╔════╝████╗  ██║
+// ██║  ███╗██║   ██║██║     ██║  ██║█████╗  ██╔██╗ ██║
+// ██║   ██║██║   ██║██║     ██║  ██║██╔══╝  ██║╚██╗██║
+// ╚██████╔╝╚██████╔╝███████╗██████╔╝███████╗██║ ╚████║
+//  ╚═════╝  ╚═════╝ ╚══════╝╚═════╝ ╚══════╝╚═╝  ╚═══╝
+//                                    app/src/golden.rs
+
+use std::path::PathBuf;
+
+/// Compare export output with a checked-in golden file under `tests/golden/`.
+///
+/// Run with `UPDATE_GOLDEN=1` to rewrite the file after an intended output change.
+pub fn assert_golden(name: &str, actual: &[u8]) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(name);
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read(&path).unwrap_or_else(|e| {
+        panic!("missing golden file {} ({}); run with UPDATE_GOLDEN=1", path.display(), e)
+    });
+    assert!(
+        expected == actual,
+        "{} differs from its golden file; run with UPDATE_GOLDEN=1 if the change is intended",
+        name
+    );
+}
@@ -0,0 +1,310 @@
+//
+// ██╗  ██╗██╗███████╗████████╗ ██████╗ ██████╗ ██╗   ██╗
+// ██║  ██║██║██╔════╝╚══██╔══╝██╔═══██╗██╔══██╗╚██╗ ██╔╝
+// ███████║██║███████╗   ██║   ██║   ██║██████╔╝ ╚████╔╝
+// ██╔══██║██║╚════██║   ██║   ██║   ██║██╔══██╗  ╚██╔╝
+// ██║  ██║██║███████║   ██║   ╚██████╔╝██║  ██║   ██║
+// ╚═╝  ╚═╝╚═╝╚══════╝   ╚═╝    ╚═════╝ ╚═╝  ╚═╝   ╚═╝
+//                                     app/src/history.rs
+
+use crate::form_data::{FieldChange, FieldValue};
+use crate::overlay::Overlay;
+
+/// Maximum number of undoable edits kept per document
+pub const HISTORY_LIMIT: usize = 200;
+
+/// Pause in typing after which the next keystroke starts a new undo step
+pub const TYPING_PAUSE_MS: u64 = 1_000;
+
+/// A reversible edit to a document
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditCommand {
+    SetField { field_id: String, old_value: FieldValue, new_value: FieldValue },
+    UpdateOverlay { before: Overlay, after: Overlay },
+    CreateOverlay { overlay: Overlay, index: usize },
+    DeleteOverlay { overlay: Overlay, index: usize },
+}
+
+impl EditCommand {
+    /// The command that reverts this one
+    pub fn inverse(&self) -> EditCommand {
+        match self {
+            EditCommand::SetField { field_id, old_value, new_value } => EditCommand::SetField {
+                field_id: field_id.clone(),
+                old_value: new_value.clone(),
+                new_value: old_value.clone(),
+            },
+            EditCommand::UpdateOverlay { before, after } => {
+                EditCommand::UpdateOverlay { before: after.clone(), after: before.clone() }
+            }
+            EditCommand::CreateOverlay { overlay, index } => {
+                EditCommand::DeleteOverlay { overlay: overlay.clone(), index: *index }
+            }
+            EditCommand::DeleteOverlay { overlay, index } => {
+                EditCommand::CreateOverlay { overlay: overlay.clone(), index: *index }
+            }
+        }
+    }
+
+    /// Fold a follow-up command into this one when both edit the same target
+    fn merge(&mut self, next: &EditCommand) -> bool {
+        match (self, next) {
+            (
+                EditCommand::UpdateOverlay { after, .. },
+                EditCommand::UpdateOverlay { before: next_before, after: next_after },
+            ) if after.id == next_before.id => {
+                *after = next_after.clone();
+                true
+            }
+            (
+                EditCommand::SetField { field_id, new_value, .. },
+                EditCommand::SetField { field_id: next_id, new_value: next_value, .. },
+            ) if field_id == next_id && matches!(next_value, FieldValue::Text(_)) => {
+                *new_value = next_value.clone();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether `next` types the first character of a new word into the text this
+    /// command left, i.e. inserts a non-space right after a space
+    fn starts_word(&self, next: &EditCommand) -> bool {
+        let (
+            EditCommand::SetField { field_id, new_value: FieldValue::Text(before), .. },
+            EditCommand::SetField { field_id: next_id, new_value: FieldValue::Text(after), .. },
+        ) = (self, next)
+        else {
+            return false;
+        };
+        if field_id != next_id || after.chars().count() <= before.chars().count() {
+            return false;
+        }
+
+        let after: Vec<char> = after.chars().collect();
+        let common = before.chars().zip(&after).take_while(|(old, new)| old == *new).count();
+        common > 0
+            && after[common - 1].is_whitespace()
+            && after.get(common).is_some_and(|c| !c.is_whitespace())
+    }
+
+    /// Whether applying the command would leave the document unchanged
+    fn is_noop(&self) -> bool {
+        match self {
+            EditCommand::SetField { old_value, new_value, .. } => old_value == new_value,
+            EditCommand::UpdateOverlay { before, after } => before == after,
+            _ => false,
+        }
+    }
+}
+
+impl From<FieldChange> for EditCommand {
+    fn from(change: FieldChange) -> Self {
+        EditCommand::SetField {
+            field_id: change.field_id,
+            old_value: change.old_value,
+            new_value: change.new_value,
+        }
+    }
+}
+
+/// Undo/redo stacks for one document.
+///
+/// Continuous gestures (overlay drags, typing into a text field) arrive as a
+/// stream of small edits; while a gesture is open they are coalesced into the
+/// command on top of the undo stack so a single undo reverts the whole gesture.
+/// Typing is split into steps at pauses and word starts, so undo doesn't throw
+/// away a whole paragraph at once.
+#[derive(Debug, Default)]
+pub struct EditHistory {
+    undo_stack: Vec<EditCommand>,
+    redo_stack: Vec<EditCommand>,
+    gesture_open: bool,
+    /// Time of the last typed edit, in milliseconds
+    last_typed_ms: u64,
+}
+
+impl EditHistory {
+    /// Record an edit that has already been applied
+    pub fn record(&mut self, command: EditCommand) {
+        self.push(command);
+        self.gesture_open = false;
+    }
+
+    /// Record an edit that may be merged with the previous one in the same gesture
+    pub fn record_coalesced(&mut self, command: EditCommand) {
+        let merged = self.gesture_open
+            && self.undo_stack.last_mut().is_some_and(|last| last.merge(&command));
+
+        if merged {
+            self.redo_stack.clear();
+            // A gesture that returned to where it started leaves nothing to undo
+            if self.undo_stack.last().is_some_and(EditCommand::is_noop) {
+                self.undo_stack.pop();
+                self.gesture_open = false;
+                return;
+            }
+        } else {
+            self.push(command);
+        }
+        self.gesture_open = true;
+    }
+
+    /// Record a keystroke's edit to a text field at `at_ms` milliseconds. Keystrokes
+    /// coalesce like a gesture, except that one after a pause of more than
+    /// [`TYPING_PAUSE_MS`] or one starting a new word begins a new undo step.
+    pub fn record_typing(&mut self, command: EditCommand, at_ms: u64) {
+        let paused = at_ms.saturating_sub(self.last_typed_ms) > TYPING_PAUSE_MS;
+        self.last_typed_ms = at_ms;
+        if paused || self.undo_stack.last().is_some_and(|last| last.starts_word(&command)) {
+            self.gesture_open = false;
+        }
+        self.record_coalesced(command);
+    }
+
+    /// Close the current gesture so the next edit starts a new undo step
+    pub fn end_gesture(&mut self) {
+        self.gesture_open = false;
+    }
+
+    /// Pop the last edit and return the command that reverts it
+    pub fn undo(&mut self) -> Option<EditCommand> {
+        self.gesture_open = false;
+        let command = self.undo_stack.pop()?;
+        let inverse = command.inverse();
+        self.redo_stack.push(command);
+        Some(inverse)
+    }
+
+    /// Pop the last undone edit and return it for re-applying
+    pub fn redo(&mut self) -> Option<EditCommand> {
+        self.gesture_open = false;
+        let command = self.redo_stack.pop()?;
+        self.undo_stack.push(command.clone());
+        Some(command)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    fn push(&mut self, command: EditCommand) {
+        if command.is_noop() {
+            return;
+        }
+        self.redo_stack.clear();
+        self.undo_stack.push(command);
+        if self.undo_stack.len() > HISTORY_LIMIT {
+            self.undo_stack.remove(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinates::PhysicalCoord;
+    use crate::overlay::{OverlayType, Transform};
+
+    fn overlay(translate_x: f64) -> Overlay {
+        Overlay {
+            id: "overlay_1".to_string(),
+            page_index: 0,
+            overlay_type: OverlayType::TextBox,
+            transform: Transform { translate_x, ..Transform::default() },
+            position: PhysicalCoord { x: 50.0, y: 50.0 },
+            size: PhysicalCoord { x: 30.0, y: 20.0 },
+            visible: true,
+            selected: false,
+            z_index: 1,
+        }
+    }
+
+    fn moved(from: f64, to: f64) -> EditCommand {
+        EditCommand::UpdateOverlay { before: overlay(from), after: overlay(to) }
+    }
+
+    #[test]
+    fn test_drag_deltas_coalesce() {
+        let mut history = EditHistory::default();
+        history.record_coalesced(moved(0.0, 2.0));
+        history.record_coalesced(moved(2.0, 5.0));
+        history.record_coalesced(moved(5.0, 9.0));
+        history.end_gesture();
+        history.record_coalesced(moved(9.0, 12.0));
+
+        assert_eq!(history.undo(), Some(moved(12.0, 9.0)));
+        assert_eq!(history.undo(), Some(moved(9.0, 0.0)));
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.redo(), Some(moved(0.0, 9.0)));
+    }
+
+    #[test]
+    fn test_new_edit_clears_redo() {
+        let mut history = EditHistory::default();
+        history.record(EditCommand::CreateOverlay { overlay: overlay(0.0), index: 0 });
+        history.record(moved(0.0, 4.0));
+
+        assert!(history.undo().is_some());
+        assert!(history.can_redo());
+
+        history.record(moved(0.0, 1.0));
+        assert!(!history.can_redo());
+        assert_eq!(
+            history.undo_stack.first().map(EditCommand::inverse),
+            Some(EditCommand::DeleteOverlay { overlay: overlay(0.0), index: 0 })
+        );
+    }
+
+    fn typed(old_value: &str, new_value: &str) -> EditCommand {
+        EditCommand::SetField {
+            field_id: "petitioner_name".to_string(),
+            old_value: FieldValue::Text(old_value.to_string()),
+            new_value: FieldValue::Text(new_value.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_typing_splits_at_words_and_pauses() {
+        let mut history = EditHistory::default();
+        let mut text = String::new();
+        for (i, c) in "Jane Do".chars().enumerate() {
+            let next = format!("{}{}", text, c);
+            history.record_typing(typed(&text, &next), 10_000 + i as u64 * 100);
+            text = next;
+        }
+        // Finishing the word after a pause is its own step
+        history.record_typing(typed("Jane Do", "Jane Doe"), 15_000);
+
+        assert_eq!(history.undo(), Some(typed("Jane Doe", "Jane Do")));
+        assert_eq!(history.undo(), Some(typed("Jane Do", "Jane ")));
+        assert_eq!(history.undo(), Some(typed("Jane ", "")));
+        assert_eq!(history.undo(), None);
+
+        // Deleting back over a space keeps coalescing
+        let mut history = EditHistory::default();
+        history.record_typing(typed("a b", "a "), 0);
+        history.record_typing(typed("a ", "a"), 50);
+        assert_eq!(history.undo(), Some(typed("a", "a b")));
+    }
+
+    #[test]
+    fn test_round_trip_gesture_is_dropped() {
+        let mut history = EditHistory::default();
+        history.record_coalesced(moved(0.0, 3.0));
+        history.record_coalesced(moved(3.0, 0.0));
+
+        assert!(!history.can_undo());
+
+        history.record(EditCommand::SetField {
+            field_id: "case_number".to_string(),
+            old_value: FieldValue::Text(String::new()),
+            new_value: FieldValue::Text(String::new()),
+        });
+        assert!(!history.can_undo());
+    }
+}
@@ -0,0 +1,225 @@
+//
+// ██╗███╗   ██╗████████╗███████╗██████╗  ██████╗██╗  ██╗ █████╗ ███╗   ██╗ ██████╗ ███████╗
+// ██║████╗  ██║╚══██╔══╝██╔════╝██╔══██╗██╔════╝██║  ██║██╔══██╗████╗  ██║██╔════╝ ██╔════╝
+// ██║██╔██╗ ██║   ██║   █████╗  ██████╔╝██║     ███████║███████║██╔██╗ ██║██║  ███╗█████╗
+// ██║██║╚██╗██║   ██║   ██╔══╝  ██╔══██╗██║     ██╔══██║██╔══██║██║╚██╗██║██║   ██║██╔══╝
+// ██║██║ ╚████║   ██║   ███████╗██║  ██║╚██████╗██║  ██║██║  ██║██║ ╚████║╚██████╔╝███████╗
+// ╚═╝╚═╝  ╚═══╝   ╚═╝   ╚══════╝╚═╝  ╚═╝ ╚═════╝╚═╝  ╚═╝╚═╝  ╚═╝╚═╝  ╚═══╝ ╚═════╝ ╚══════╝
+//                                                                    app/src/interchange.rs
+
+use crate::form_data::{FieldValue, FormData};
+use crate::form_template::FormTemplate;
+use crate::overlay::Overlay;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// Identifies a JSON file as one of our document exports
+pub const INTERCHANGE_FORMAT: &str = "pixel-perfect-forms/document";
+
+/// Current interchange schema version; bump on any breaking change to `DocumentExport`
+pub const INTERCHANGE_SCHEMA_VERSION: u32 = 1;
+
+/// Portable snapshot of a filled form: field values and overlays, without view state.
+///
+/// Field values are keyed by `FormFieldInfo::id` and tagged by type, e.g.
+/// `{"case_number": {"Text": "FL-2024-001"}, "separate_property": {"Checked": true}}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentExport {
+    pub format: String,
+    pub schema_version: u32,
+    pub form_id: String,
+    pub form_revision: String,
+    pub fields: BTreeMap<String, FieldValue>,
+    #[serde(default)]
+    pub overlays: Vec<Overlay>,
+}
+
+impl DocumentExport {
+    /// Snapshot a document for export
+    pub fn from_document(
+        template: &FormTemplate,
+        form_data: &FormData,
+        overlays: &[Overlay],
+    ) -> Self {
+        Self {
+            format: INTERCHANGE_FORMAT.to_string(),
+            schema_version: INTERCHANGE_SCHEMA_VERSION,
+            form_id: template.form_id.clone(),
+            form_revision: template.revision.clone(),
+            fields: form_data.values().clone(),
+            overlays: overlays.to_vec(),
+        }
+    }
+
+    /// Parse an export, checking the format marker and schema version before the body
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| format!("Document is not valid JSON: {}", e))?;
+
+        let format = value.get("format").and_then(|f| f.as_str());
+        if format != Some(INTERCHANGE_FORMAT) {
+            return Err(format!(
+                "Not a form document export (format {:?})",
+                format.unwrap_or("missing")
+            ));
+        }
+
+        let schema_version = value
+            .get("schema_version")
+            .and_then(|v| v.as_u64())
+            .ok_or("Document export is missing its schema_version")?;
+        if schema_version > INTERCHANGE_SCHEMA_VERSION as u64 {
+            return Err(format!(
+                "Document schema version {} is newer than supported version {}; please update the app",
+                schema_version, INTERCHANGE_SCHEMA_VERSION
+            ));
+        }
+        if schema_version != INTERCHANGE_SCHEMA_VERSION as u64 {
+            return Err(format!("Unsupported document schema version {}", schema_version));
+        }
+
+        serde_json::from_value(value).map_err(|e| format!("Invalid document export: {}", e))
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize document: {}", e))
+    }
+
+    /// Warning for an export saved from another revision of the form than the one open;
+    /// field ids may match while positions and meanings have moved
+    pub fn revision_warning(&self, template: &FormTemplate) -> Option<String> {
+        (self.form_revision != template.revision).then(|| {
+            format!(
+                "Document was saved from {} revision {}, but revision {} is open; check the values",
+                self.form_id, self.form_revision, template.revision
+            )
+        })
+    }
+
+    /// Validate the export against a template and build the document contents.
+    ///
+    /// Fields missing from the export stay empty so partial data (e.g. intake
+    /// pre-fill) can be loaded; unknown fields and mistyped values are rejected.
+    pub fn into_document(
+        self,
+        template: &FormTemplate,
+    ) -> Result<(FormData, Vec<Overlay>), String> {
+        if self.form_id != template.form_id {
+            return Err(format!(
+                "Document is for form {}, but {} is open",
+                self.form_id, template.form_id
+            ));
+        }
+
+        let mut form_data = FormData::for_template(template);
+        for (field_id, value) in self.fields {
+            form_data.set(&field_id, value)?;
+        }
+
+        let mut overlay_ids = HashSet::new();
+        for overlay in &self.overlays {
+            if overlay.page_index >= template.pages.len() {
+                return Err(format!(
+                    "Overlay '{}' is on page {}, but {} has {} pages",
+                    overlay.id,
+                    overlay.page_index + 1,
+                    template.form_id,
+                    template.pages.len()
+                ));
+            }
+            if !overlay_ids.insert(overlay.id.as_str()) {
+                return Err(format!("Duplicate overlay id '{}'", overlay.id));
+            }
+        }
+
+        Ok((form_data, self.overlays))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinates::PhysicalCoord;
+    use crate::overlay::{OverlayType, Transform};
+
+    fn filled_export() -> DocumentExport {
+        let template = FormTemplate::fl100();
+        let mut form_data = FormData::for_template(&template);
+        form_data.set("petitioner_name", FieldValue::Text("Jane Doe".to_string())).unwrap();
+        form_data.set("separate_property", FieldValue::Checked(true)).unwrap();
+
+        let overlay = Overlay {
+            id: "overlay_3".to_string(),
+            page_index: 1,
+            overlay_type: OverlayType::Annotation,
+            transform: Transform { translate_x: 12.5, rotation: 90.0, ..Transform::default() },
+            position: PhysicalCoord { x: 40.0, y: 60.0 },
+            size: PhysicalCoord { x: 30.0, y: 20.0 },
+            visible: true,
+            selected: false,
+            z_index: 3,
+        };
+
+        DocumentExport::from_document(&template, &form_data, &[overlay])
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let export = filled_export();
+        let json = export.to_json().unwrap();
+        let imported = DocumentExport::from_json(&json).unwrap();
+        assert_eq!(imported, export);
+
+        let (form_data, overlays) = imported.into_document(&FormTemplate::fl100()).unwrap();
+        assert_eq!(form_data.text("petitioner_name"), "Jane Doe");
+        assert!(form_data.is_checked("separate_property"));
+        assert_eq!(overlays[0].transform.rotation, 90.0);
+    }
+
+    #[test]
+    fn test_schema_version_check() {
+        let mut value = serde_json::to_value(filled_export()).unwrap();
+        value["schema_version"] = serde_json::json!(INTERCHANGE_SCHEMA_VERSION + 1);
+        let error = DocumentExport::from_json(&value.to_string()).unwrap_err();
+        assert!(error.contains("newer"));
+
+        value.as_object_mut().unwrap().remove("schema_version");
+        assert!(DocumentExport::from_json(&value.to_string()).is_err());
+
+        assert!(DocumentExport::from_json(r#"{"form_id": "FL-100"}"#).is_err());
+    }
+
+    #[test]
+    fn test_rejects_mismatched_data() {
+        let template = FormTemplate::fl100();
+
+        let mut other_form = filled_export();
+        other_form.form_id = "FL-105".to_string();
+        assert!(other_form.into_document(&template).is_err());
+
+        let mut unknown_field = filled_export();
+        unknown_field.fields.insert("no_such_field".to_string(), FieldValue::Text("x".to_string()));
+        assert!(unknown_field.into_document(&template).unwrap_err().contains("no_such_field"));
+
+        let mut partial = filled_export();
+        partial.fields.retain(|id, _| id == "petitioner_name");
+        let (form_data, _) = partial.into_document(&template).unwrap();
+        assert!(!form_data.is_checked("separate_property"));
+    }
+
+    #[test]
+    fn test_revision_warning() {
+        let template = FormTemplate::fl100();
+        let export = filled_export();
+        assert_eq!(export.revision_warning(&template), None);
+
+        // Still imported, but flagged
+        let mut older = filled_export();
+        older.form_revision = "2016-01-01".to_string();
+        let warning = older.revision_warning(&template).unwrap();
+        assert!(warning.contains("revision 2016-01-01"), "{}", warning);
+        assert!(warning.contains(&template.revision), "{}", warning);
+        assert!(older.into_document(&template).is_ok());
+    }
+}
@@ -7,60 +7,143 @@
 // ╚═╝  ╚═══╝╚══════╝ ╚══╝╚══╝     ╚══════╝╚═╝╚═╝  ╚═╝     ╚══════╝╚═╝╚═╝  ╚═╝
 //                                                            app/src/lib.rs
 
-mod coordinates;
-mod calibration;
+// DOM-free core: builds and tests natively with `--no-default-features`
+pub mod coordinates;
+pub mod calibration;
+pub mod display_profiles;
+pub mod calibration_exchange;
+pub mod overlay;
+pub mod viewport;
+pub mod afm;
+pub mod font_metrics;
+pub mod text_layout;
+pub mod comb;
+pub mod form_template;
+pub mod form_registry;
+pub mod form_data;
+pub mod drafts;
+pub mod history;
+pub mod interchange;
+pub mod xml;
+pub mod field_exchange;
+pub mod pdf_export;
+pub mod svg_export;
+pub mod rendering_engine;
+pub mod printer_calibration;
+#[cfg(test)]
+mod golden;
+#[cfg(test)]
+mod fixtures;
+
+// Browser UI
+#[cfg(feature = "web")]
 mod calibration_wizard;
-mod proofing_ui;
+#[cfg(feature = "web")]
+pub mod proofing_ui;
+#[cfg(feature = "web")]
 mod text_input;
+#[cfg(feature = "web")]
+mod multiline_input;
+#[cfg(feature = "web")]
+mod comb_input;
+#[cfg(feature = "web")]
 mod checkbox;
+#[cfg(feature = "web")]
 mod signature;
-mod rendering_engine;
+#[cfg(feature = "web")]
 mod main_app;
+#[cfg(feature = "web")]
 mod document_manager;
+#[cfg(feature = "web")]
 mod overlay_manager;
-mod font_metrics;
-mod cv_alignment;
+#[cfg(feature = "web")]
+pub mod cv_alignment;
+#[cfg(feature = "web")]
+mod file_io;
+#[cfg(feature = "web")]
+mod printer_setup;
 
+#[cfg(feature = "web")]
 use wasm_bindgen::prelude::*;
+#[cfg(feature = "web")]
 use yew::prelude::*;
-use calibration::CalibrationManager;
+#[cfg(feature = "web")]
+use calibration::{CalibrationManager, LinkConflict};
+#[cfg(feature = "web")]
 use calibration_wizard::CalibrationWizard;
+#[cfg(feature = "web")]
+use display_profiles::{current_display, DisplayChange, DisplayFingerprint, DisplayProfile, DisplayWatcher};
+#[cfg(feature = "web")]
 use main_app::MainApp;
 
 // Use wee_alloc as the global allocator for smaller WASM size
+#[cfg(feature = "web")]
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 
+#[cfg(feature = "web")]
 pub struct App {
     show_calibration: bool,
     calibration_manager: CalibrationManager,
+    display: Option<DisplayFingerprint>,
+    display_watcher: Option<DisplayWatcher>,
+    /// Set when the display changed in a way the current calibration can't follow
+    recalibration_prompt: bool,
+    /// Calibration from a `?calibration=` link that would replace the display's own
+    linked_conflict: Option<LinkConflict>,
 }
 
+#[cfg(feature = "web")]
 pub enum AppMsg {
     ShowCalibration,
     HideCalibration,
     CalibrationComplete,
+    DisplayChanged,
+    DismissRecalibration,
+    UseLinkedCalibration,
+    KeepOwnCalibration,
+    ProvisionedLoaded(Result<Vec<DisplayProfile>, String>),
 }
 
+#[cfg(feature = "web")]
 impl Component for App {
     type Message = AppMsg;
     type Properties = ();
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
+        // A calibration handed over in the URL takes effect before anything is measured,
+        // unless it would replace one the user already has
+        let linked_conflict = match CalibrationManager::import_from_query() {
+            Some(Ok(conflict)) => conflict,
+            None => None,
+            Some(Err(error)) => {
+                web_sys::console::warn_1(&error.into());
+                None
+            }
+        };
+        ctx.link().send_future(async {
+            AppMsg::ProvisionedLoaded(calibration::fetch_provisioned().await)
+        });
+
         let calibration_manager = CalibrationManager::new();
         let needs_calibration = calibration_manager.needs_recalibration();
         
         Self {
             show_calibration: needs_calibration,
             calibration_manager,
+            display: current_display(),
+            display_watcher: watch_display(ctx),
+            recalibration_prompt: false,
+            linked_conflict,
         }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             AppMsg::ShowCalibration => {
                 self.show_calibration = true;
+                self.recalibration_prompt = false;
                 true
             }
             AppMsg::HideCalibration => {
@@ -70,8 +153,81 @@ impl Component for App {
             AppMsg::CalibrationComplete => {
                 self.calibration_manager = CalibrationManager::new();
                 self.show_calibration = false;
+                self.recalibration_prompt = false;
+                true
+            }
+            AppMsg::DisplayChanged => {
+                // Resizes fire constantly; only a zoom or a different display matters
+                let Some(display) = current_display() else {
+                    return false;
+                };
+                let change = match &self.display {
+                    Some(previous) => display.change_from(previous),
+                    None => DisplayChange::Other,
+                };
+                if change == DisplayChange::Unchanged {
+                    return false;
+                }
+                self.display = Some(display);
+                self.display_watcher = watch_display(ctx);
+
+                // A profile made for exactly this state wins; otherwise follow a zoom,
+                // and only ask the user when the change can't be explained by one
+                let has_calibration = self.calibration_manager.get_calibration().is_some();
+                match change {
+                    _ if CalibrationManager::current_profile().is_some() => {
+                        self.calibration_manager = CalibrationManager::new();
+                        self.recalibration_prompt = false;
+                    }
+                    DisplayChange::Zoom(zoom) if has_calibration => {
+                        self.calibration_manager.rescale(zoom);
+                    }
+                    _ if has_calibration => self.recalibration_prompt = true,
+                    _ => self.show_calibration = true,
+                }
+                true
+            }
+            AppMsg::DismissRecalibration => {
+                self.recalibration_prompt = false;
+                true
+            }
+            AppMsg::UseLinkedCalibration => {
+                let Some(conflict) = self.linked_conflict.take() else {
+                    return false;
+                };
+                match CalibrationManager::replace_profile(conflict.linked) {
+                    Ok(()) => {
+                        self.calibration_manager = CalibrationManager::new();
+                        if !self.calibration_manager.needs_recalibration() {
+                            self.show_calibration = false;
+                        }
+                    }
+                    Err(error) => web_sys::console::warn_1(&error.into()),
+                }
                 true
             }
+            AppMsg::KeepOwnCalibration => {
+                self.linked_conflict = None;
+                true
+            }
+            AppMsg::ProvisionedLoaded(provisioned) => {
+                // Provisioned profiles fill gaps; a user's own calibration always wins
+                match provisioned.and_then(CalibrationManager::install_provisioned) {
+                    Ok(0) => false,
+                    Ok(_) if self.calibration_manager.get_calibration().is_none() => {
+                        self.calibration_manager = CalibrationManager::new();
+                        if !self.calibration_manager.needs_recalibration() {
+                            self.show_calibration = false;
+                        }
+                        true
+                    }
+                    Ok(_) => false,
+                    Err(error) => {
+                        web_sys::console::warn_1(&error.into());
+                        false
+                    }
+                }
+            }
         }
     }
 
@@ -79,6 +235,7 @@ impl Component for App {
         if self.show_calibration {
             html! {
                 <main class="app calibration-mode">
+                    { self.render_linked_conflict(ctx) }
                     <CalibrationWizard 
                         on_complete={ctx.link().callback(|_| AppMsg::CalibrationComplete)}
                         on_cancel={ctx.link().callback(|_| AppMsg::HideCalibration)}
@@ -89,7 +246,7 @@ impl Component for App {
             html! {
                 <main class="app">
                     <header class="app-header">
-                        <h1>{"FL-100 Pixel‑Perfect WASM Forms"}</h1>
+                        <h1>{"Judicial Council Pixel‑Perfect WASM Forms"}</h1>
                         <div class="header-controls">
                             <button 
                                 class="calibration-button"
@@ -99,6 +256,25 @@ impl Component for App {
                             </button>
                         </div>
                     </header>
+                    { if self.recalibration_prompt {
+                        html! {
+                            <div class="recalibration-prompt">
+                                <span>
+                                    {"The display changed and sizes on screen may no longer be accurate."}
+                                </span>
+                                <button
+                                    class="calibration-button"
+                                    onclick={ctx.link().callback(|_| AppMsg::ShowCalibration)}
+                                >
+                                    {"Recalibrate"}
+                                </button>
+                                <button onclick={ctx.link().callback(|_| AppMsg::DismissRecalibration)}>
+                                    {"Dismiss"}
+                                </button>
+                            </div>
+                        }
+                    } else { html! {} }}
+                    { self.render_linked_conflict(ctx) }
                     
                     <MainApp calibration_manager={self.calibration_manager.clone()} />
                 </main>
@@ -107,10 +283,60 @@ impl Component for App {
     }
 }
 
+#[cfg(feature = "web")]
+impl App {
+    /// Choice between the display's own calibration and one from a link
+    fn render_linked_conflict(&self, ctx: &Context<Self>) -> Html {
+        let Some(LinkConflict { existing, linked }) = &self.linked_conflict else {
+            return html! {};
+        };
+        let describe = |profile: &DisplayProfile| {
+            format!(
+                "'{}' ({:.2} × {:.2} px/mm, {:.0}% confidence)",
+                profile.name,
+                profile.calibration.scale_x,
+                profile.calibration.scale_y,
+                profile.calibration.confidence * 100.0
+            )
+        };
+        html! {
+            <div class="recalibration-prompt">
+                <span>
+                    {format!(
+                        "The link you opened carries calibration {} for this display. \
+                         Replace your calibration {} with it?",
+                        describe(linked),
+                        describe(existing)
+                    )}
+                </span>
+                <button
+                    class="calibration-button"
+                    onclick={ctx.link().callback(|_| AppMsg::UseLinkedCalibration)}
+                >
+                    {"Replace"}
+                </button>
+                <button onclick={ctx.link().callback(|_| AppMsg::KeepOwnCalibration)}>
+                    {"Keep mine"}
+                </button>
+            </div>
+        }
+    }
+}
+
+/// Watch for zoom changes and the window moving to another display; recreated on each
+/// change because the pixel ratio query only fires when leaving the ratio it was created with
+#[cfg(feature = "web")]
+fn watch_display(ctx: &Context<App>) -> Option<DisplayWatcher> {
+    let link = ctx.link().clone();
+    DisplayWatcher::new(move || link.send_message(AppMsg::DisplayChanged))
+}
+
+#[cfg(feature = "web")]
 pub fn start_app() {
     yew::Renderer::<App>::new().render();
 }
 
+#[cfg(feature = "web")]
 #[wasm_bindgen(start)]  
 pub fn wasm_main() {
     console_error_panic_hook::set_once();
@@ -9,6 +9,10 @@
 
 use crate::calibration::CalibrationManager;
 use crate::document_manager::DocumentManager;
+use crate::drafts::{upsert_summary, Draft, DraftStore, DraftSummary};
+use crate::form_registry::{fetch_manifest, FormRegistry, DEFAULT_FORM_ID};
+use crate::form_template::{fetch_template, FormTemplate};
+use wasm_bindgen::JsValue;
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
@@ -17,29 +21,283 @@ pub struct MainAppProps {
     pub calibration_manager: Option<CalibrationManager>,
 }
 
+/// A form mounted in the workspace and the draft it autosaves to
+#[derive(Clone, PartialEq)]
+struct OpenDocument {
+    form_id: String,
+    draft_id: String,
+    restore: Option<Draft>,
+    /// Mount key: the draft the document was opened with, kept if it moves to a new draft
+    key: String,
+}
+
+impl OpenDocument {
+    fn fresh(form_id: &str) -> Self {
+        let draft_id = DraftStore::new_draft_id(form_id);
+        Self { form_id: form_id.to_string(), key: draft_id.clone(), draft_id, restore: None }
+    }
+
+    fn resume(draft: Draft) -> Self {
+        Self {
+            form_id: draft.form_id.clone(),
+            draft_id: draft.draft_id.clone(),
+            key: draft.draft_id.clone(),
+            restore: Some(draft),
+        }
+    }
+}
+
 pub struct MainApp {
     calibration_manager: CalibrationManager,
+    registry: FormRegistry,
+    active_form: String,
+    open_documents: Vec<OpenDocument>,
+    template_error: Option<String>,
+    show_drafts: bool,
+    drafts: Vec<DraftSummary>,
+}
+
+pub enum MainAppMsg {
+    ManifestLoaded(Result<Vec<String>, String>),
+    TemplateLoaded(Result<FormTemplate, String>),
+    SelectForm(String),
+    ToggleDrafts,
+    ResumeDraft(String),
+    DeleteDraft(String),
+    DraftSaved(DraftSummary),
 }
 
 impl Component for MainApp {
-    type Message = ();
+    type Message = MainAppMsg;
     type Properties = MainAppProps;
 
     fn create(ctx: &Context<Self>) -> Self {
-        let calibration_manager = ctx.props().calibration_manager
-            .clone()
-            .unwrap_or_else(CalibrationManager::new);
-            
+        // Without a manager from the parent, pick up the stored calibration
+        let calibration_manager = match ctx.props().calibration_manager.clone() {
+            Some(manager) => manager,
+            None => CalibrationManager::new(),
+        };
+
+        // Prefer the served templates so forms can be added or updated without a rebuild
+        ctx.link().send_future(async { MainAppMsg::ManifestLoaded(fetch_manifest().await) });
+
+        // Pick up where the user left off with their most recent draft
+        let drafts = DraftStore::list();
+        let initial = drafts
+            .first()
+            .and_then(|summary| DraftStore::load(&summary.draft_id))
+            .map(OpenDocument::resume)
+            .unwrap_or_else(|| OpenDocument::fresh(DEFAULT_FORM_ID));
+
         Self {
             calibration_manager,
+            registry: FormRegistry::builtin(),
+            active_form: initial.form_id.clone(),
+            open_documents: vec![initial],
+            template_error: None,
+            show_drafts: false,
+            drafts,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            MainAppMsg::ManifestLoaded(Ok(urls)) => {
+                for url in urls {
+                    ctx.link().send_future(async move {
+                        MainAppMsg::TemplateLoaded(fetch_template(&url).await)
+                    });
+                }
+                false
+            }
+            MainAppMsg::ManifestLoaded(Err(error)) | MainAppMsg::TemplateLoaded(Err(error)) => {
+                self.template_error = Some(error);
+                true
+            }
+            MainAppMsg::TemplateLoaded(Ok(template)) => {
+                let had_error = self.template_error.take().is_some();
+                if self.registry.get(&template.form_id) != Some(&template) {
+                    self.registry.register(template);
+                    true
+                } else {
+                    had_error
+                }
+            }
+            MainAppMsg::SelectForm(form_id) => {
+                if form_id == self.active_form {
+                    return false;
+                }
+                // Keep previously opened forms mounted so their state survives switching
+                if !self.open_documents.iter().any(|doc| doc.form_id == form_id) {
+                    self.open_documents.push(OpenDocument::fresh(&form_id));
+                }
+                self.active_form = form_id;
+                true
+            }
+            MainAppMsg::ToggleDrafts => {
+                self.show_drafts = !self.show_drafts;
+                self.drafts = DraftStore::list();
+                true
+            }
+            MainAppMsg::ResumeDraft(draft_id) => {
+                let Some(draft) = DraftStore::load(&draft_id) else {
+                    self.template_error = Some(format!("Draft {} could not be loaded", draft_id));
+                    return true;
+                };
+                let document = OpenDocument::resume(draft);
+                self.template_error = None;
+                self.active_form = document.form_id.clone();
+                self.replace_document(document);
+                self.show_drafts = false;
+                true
+            }
+            MainAppMsg::DeleteDraft(draft_id) => {
+                if let Err(error) = DraftStore::delete(&draft_id) {
+                    web_sys::console::warn_1(&error.into());
+                }
+                // An open document must not keep writing to the deleted draft, but keeps
+                // what has been entered and saves it as a new draft on the next change
+                if let Some(document) =
+                    self.open_documents.iter_mut().find(|doc| doc.draft_id == draft_id)
+                {
+                    document.draft_id = DraftStore::new_draft_id(&document.form_id);
+                    document.restore = None;
+                }
+                self.drafts = DraftStore::list();
+                true
+            }
+            MainAppMsg::DraftSaved(summary) => {
+                upsert_summary(&mut self.drafts, summary);
+                true
+            }
         }
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
+    fn view(&self, ctx: &Context<Self>) -> Html {
         html! {
             <div class="main-app">
-                <DocumentManager calibration_manager={self.calibration_manager.clone()} />
+                { self.render_form_picker(ctx) }
+                { if self.show_drafts { self.render_drafts(ctx) } else { html! {} } }
+                { if let Some(error) = &self.template_error {
+                    html! {
+                        <div class="error-message">
+                            <span class="error-icon">{"⚠️"}</span>
+                            <span class="error-text">{error}</span>
+                        </div>
+                    }
+                } else { html! {} }}
+                { for self.open_documents.iter().filter_map(|document| {
+                    let template = self.registry.get(&document.form_id)?;
+                    // Keyed by draft so resuming a different draft remounts the document
+                    Some(html! {
+                        <div
+                            key={document.key.clone()}
+                            class="form-document"
+                            hidden={document.form_id != self.active_form}
+                        >
+                            <DocumentManager
+                                calibration_manager={self.calibration_manager.clone()}
+                                template={template.clone()}
+                                draft_id={document.draft_id.clone()}
+                                draft={document.restore.clone()}
+                                on_draft_saved={ctx.link().callback(MainAppMsg::DraftSaved)}
+                            />
+                        </div>
+                    })
+                })}
             </div>
         }
     }
 }
+
+impl MainApp {
+    /// Swap in a document for its form, opening it if the form isn't open yet
+    fn replace_document(&mut self, document: OpenDocument) {
+        match self.open_documents.iter_mut().find(|doc| doc.form_id == document.form_id) {
+            Some(existing) => *existing = document,
+            None => self.open_documents.push(document),
+        }
+    }
+
+    fn render_form_picker(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <nav class="form-picker">
+                { for self.registry.templates().iter().map(|template| {
+                    let is_active = template.form_id == self.active_form;
+                    let form_id = template.form_id.clone();
+                    html! {
+                        <button
+                            class={if is_active { "form-picker-item active" } else { "form-picker-item" }}
+                            title={template.title.clone()}
+                            onclick={ctx.link().callback(move |_| MainAppMsg::SelectForm(form_id.clone()))}
+                        >
+                            <span class="form-picker-id">{&template.form_id}</span>
+                            <span class="form-picker-meta">
+                                {format!("Rev. {} · {} pages", template.revision, template.pages.len())}
+                            </span>
+                        </button>
+                    }
+                })}
+                <button
+                    class={if self.show_drafts { "form-picker-item drafts-toggle active" } else { "form-picker-item drafts-toggle" }}
+                    onclick={ctx.link().callback(|_| MainAppMsg::ToggleDrafts)}
+                >
+                    <span class="form-picker-id">{"Drafts"}</span>
+                    <span class="form-picker-meta">{format!("{} saved", self.drafts.len())}</span>
+                </button>
+            </nav>
+        }
+    }
+
+    fn render_drafts(&self, ctx: &Context<Self>) -> Html {
+        if self.drafts.is_empty() {
+            return html! {
+                <div class="drafts-panel">
+                    <p class="drafts-empty">{"No saved drafts yet. Drafts are saved automatically as you fill in a form."}</p>
+                </div>
+            };
+        }
+
+        html! {
+            <div class="drafts-panel">
+                <ul class="drafts-list">
+                    { for self.drafts.iter().map(|summary| {
+                        let resume_id = summary.draft_id.clone();
+                        let delete_id = summary.draft_id.clone();
+                        let is_open = self.open_documents.iter().any(|doc| doc.draft_id == summary.draft_id);
+                        html! {
+                            <li class="drafts-item">
+                                <span class="drafts-form">
+                                    {format!("{} (Rev. {})", summary.form_id, summary.form_revision)}
+                                </span>
+                                <span class="drafts-meta">
+                                    {format!("Saved {} · {} fields filled", format_timestamp(summary.updated_at), summary.filled_fields)}
+                                </span>
+                                <button
+                                    class="drafts-resume"
+                                    disabled={is_open}
+                                    onclick={ctx.link().callback(move |_| MainAppMsg::ResumeDraft(resume_id.clone()))}
+                                >
+                                    {if is_open { "Open" } else { "Resume" }}
+                                </button>
+                                <button
+                                    class="drafts-delete"
+                                    onclick={ctx.link().callback(move |_| MainAppMsg::DeleteDraft(delete_id.clone()))}
+                                >
+                                    {"Delete"}
+                                </button>
+                            </li>
+                        }
+                    })}
+                </ul>
+            </div>
+        }
+    }
+}
+
+/// Local date and time for a millisecond Unix timestamp
+fn format_timestamp(millis: u64) -> String {
+    js_sys::Date::new(&JsValue::from_f64(millis as f64))
+        .to_locale_string("en-US", &JsValue::UNDEFINED)
+        .into()
+}
@@ -0,0 +1,135 @@
+//
+// ███╗   ███╗██╗   ██╗██╗     ████████╗██╗██╗     ██╗███╗   ██╗███████╗   ██╗███╗   ██╗██████╗ ██╗   ██╗████████╗
+// ████╗ ████║██║   ██║██║     ╚══██╔══╝██║██║     ██║████╗  ██║██╔════╝   ██║████╗  ██║██╔══██╗██║   ██║╚══██╔══╝
+// ██╔████╔██║██║   ██║██║        ██║   ██║██║     ██║██╔██╗ ██║█████╗     ██║██╔██╗ ██║██████╔╝██║   ██║   ██║
+// ██║╚██╔╝██║██║   ██║██║        ██║   ██║██║     ██║██║╚██╗██║██╔══╝     ██║██║╚██╗██║██╔═══╝ ██║   ██║   ██║
+// ██║ ╚═╝ ██║╚██████╔╝███████╗   ██║   ██║███████╗██║██║ ╚████║███████╗   ██║██║ ╚████║██║     ╚██████╔╝   ██║
+// ╚═╝     ╚═╝ ╚═════╝ ╚══════╝   ╚═╝   ╚═╝╚══════╝╚═╝╚═╝  ╚═══╝╚══════╝   ╚═╝╚═╝  ╚═══╝╚═╝      ╚═════╝    ╚═╝
+//                                                                                      app/src/multiline_input.rs
+
+use crate::coordinates::{CoordinateSystem, PhysicalCoord, CSS_PX_PER_MM};
+use crate::font_metrics::{FontMetricsCalculator, StandardFont};
+use crate::text_layout::{layout_multiline, LineSettings};
+use std::cell::RefCell;
+use std::rc::Rc;
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct MultilineInputProps {
+    pub id: String,
+    pub label: String,
+    pub position: PhysicalCoord,
+    pub settings: LineSettings,
+    pub height_mm: f64,
+    pub on_input: Callback<String>,
+    pub value: String,
+    pub font: Option<StandardFont>,
+    pub font_size_pt: Option<f64>,
+    pub coord_system: Option<CoordinateSystem>,
+}
+
+/// Text area over a multi-line field, ruled at the form's line pitch, that reports
+/// how many lines the value wraps to and when it will continue on an attachment page
+pub struct MultilineInput {
+    font_calculator: Rc<RefCell<FontMetricsCalculator>>,
+}
+
+pub enum MultilineInputMsg {
+    Input(String),
+}
+
+impl Component for MultilineInput {
+    type Message = MultilineInputMsg;
+    type Properties = MultilineInputProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self { font_calculator: Rc::new(RefCell::new(FontMetricsCalculator::new())) }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            MultilineInputMsg::Input(value) => {
+                ctx.props().on_input.emit(value);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let settings = props.settings;
+        let font = props.font.clone().unwrap_or(StandardFont::Helvetica);
+        let font_size = props.font_size_pt.unwrap_or(12.0);
+
+        // Same wrapping the PDF export uses, so the count matches the printed lines
+        let layout = self.font_calculator.try_borrow_mut().ok().map(|mut calculator| {
+            layout_multiline(&mut calculator, &font, font_size, &props.value, &settings)
+        });
+        let status = match &layout {
+            Some(layout) if layout.overflow.is_some() => html! {
+                <span class="line-count overflow">{"Continues on attachment"}</span>
+            },
+            Some(layout) => {
+                let used = if props.value.is_empty() { 0 } else { layout.lines.len() };
+                html! {
+                    <span class="line-count">
+                        {format!("{} of {} lines", used, settings.max_lines)}
+                    </span>
+                }
+            }
+            None => html! {},
+        };
+
+        let (left, top, width_px, height_px, pitch_px) = match &props.coord_system {
+            Some(coord_system) => {
+                let screen = coord_system.physical_to_screen(props.position);
+                (
+                    screen.x,
+                    screen.y,
+                    coord_system.mm_to_px_x(settings.width_mm),
+                    coord_system.mm_to_px_y(props.height_mm),
+                    coord_system.mm_to_px_y(settings.line_pitch_mm),
+                )
+            }
+            None => (
+                props.position.x,
+                props.position.y,
+                settings.width_mm * CSS_PX_PER_MM,
+                props.height_mm * CSS_PX_PER_MM,
+                settings.line_pitch_mm * CSS_PX_PER_MM,
+            ),
+        };
+
+        let style = format!(
+            "position: absolute; left: {}px; top: {}px; width: {}px; height: {}px;",
+            left, top, width_px, height_px
+        );
+        let textarea_style = format!(
+            "font-family: {}; font-weight: {}; font-size: {}pt; line-height: {}px; \
+             height: {}px; background-size: 100% {}px;",
+            font.css_family(),
+            font.css_weight(),
+            font_size,
+            pitch_px,
+            pitch_px * settings.max_lines as f64,
+            pitch_px
+        );
+
+        html! {
+            <div class="form-field multiline-input" style={style}>
+                <label for={props.id.clone()}>{props.label.clone()}</label>
+                <textarea
+                    id={props.id.clone()}
+                    style={textarea_style}
+                    value={props.value.clone()}
+                    oninput={ctx.link().callback(|e: InputEvent| {
+                        let input: HtmlTextAreaElement = e.target_unchecked_into();
+                        MultilineInputMsg::Input(input.value())
+                    })}
+                />
+                { status }
+            </div>
+        }
+    }
+}
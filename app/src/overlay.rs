@@ -0,0 +1,137 @@
+//
+//  ██████╗ ██╗   ██╗███████╗██████╗ ██╗      █████╗ ██╗   ██╗
+// ██╔═══██╗██║   ██║██╔════╝██╔══██╗██║     ██╔══██╗╚██╗ ██╔╝
+// ██║   ██║██║   ██║█████╗  ██████╔╝██║     ███████║ ╚████╔╝
+// ██║   ██║╚██╗ ██╔╝██╔══╝  ██╔══██╗██║     ██╔══██║  ╚██╔╝
+// ╚██████╔╝ ╚████╔╝ ███████╗██║  ██║███████╗██║  ██║   ██║
+//  ╚═════╝   ╚═══╝  ╚══════╝╚═╝  ╚═╝╚══════╝╚═╝  ╚═╝   ╚═╝
+//                                          app/src/overlay.rs
+
+use crate::coordinates::{CoordinateSystem, PhysicalCoord};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Overlay {
+    pub id: String,
+    pub page_index: usize,
+    pub overlay_type: OverlayType,
+    pub transform: Transform,
+    pub position: PhysicalCoord,
+    pub size: PhysicalCoord,
+    pub visible: bool,
+    pub selected: bool,
+    pub z_index: i32,
+}
+
+impl Overlay {
+    /// Whether a page point falls inside the overlay's box after its CSS transform,
+    /// which the browser applies about the box centre
+    pub fn contains(&self, point: PhysicalCoord, coord_system: &CoordinateSystem) -> bool {
+        let transform = &self.transform;
+        let centre_x = self.size.x / 2.0;
+        let centre_y = self.size.y / 2.0;
+
+        // Undo translate, scale, rotate and skew in reverse order
+        let x =
+            point.x - (self.position.x + centre_x + coord_system.px_to_mm_x(transform.translate_x));
+        let y =
+            point.y - (self.position.y + centre_y + coord_system.px_to_mm_y(transform.translate_y));
+        if transform.scale_x == 0.0 || transform.scale_y == 0.0 {
+            return false;
+        }
+        let (x, y) = (x / transform.scale_x, y / transform.scale_y);
+
+        let (sin, cos) = transform.rotation.to_radians().sin_cos();
+        let (x, y) = (cos * x + sin * y, cos * y - sin * x);
+
+        let tan_x = transform.skew_x.to_radians().tan();
+        let tan_y = transform.skew_y.to_radians().tan();
+        let det = 1.0 - tan_x * tan_y;
+        if det.abs() < f64::EPSILON {
+            return false;
+        }
+        let local_x = (x - tan_x * y) / det + centre_x;
+        let local_y = (y - tan_y * x) / det + centre_y;
+
+        (0.0..=self.size.x).contains(&local_x) && (0.0..=self.size.y).contains(&local_y)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OverlayType {
+    TextBox,
+    Image,
+    Shape,
+    Annotation,
+    Measurement,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transform {
+    pub translate_x: f64,
+    pub translate_y: f64,
+    pub scale_x: f64,
+    pub scale_y: f64,
+    pub rotation: f64,
+    pub skew_x: f64,
+    pub skew_y: f64,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            translate_x: 0.0,
+            translate_y: 0.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            rotation: 0.0,
+            skew_x: 0.0,
+            skew_y: 0.0,
+        }
+    }
+}
+
+impl Transform {
+    pub fn to_css_string(&self) -> String {
+        format!(
+            "translate({}px, {}px) scale({}, {}) rotate({}deg) skew({}deg, {}deg)",
+            self.translate_x,
+            self.translate_y,
+            self.scale_x,
+            self.scale_y,
+            self.rotation,
+            self.skew_x,
+            self.skew_y
+        )
+    }
+
+    pub fn apply_translation(&mut self, delta_x: f64, delta_y: f64) {
+        self.translate_x += delta_x;
+        self.translate_y += delta_y;
+    }
+
+    pub fn apply_scaling(&mut self, scale_factor: f64, origin_x: f64, origin_y: f64) {
+        // Apply scaling around a specific origin point
+        let dx = origin_x - self.translate_x;
+        let dy = origin_y - self.translate_y;
+
+        self.scale_x *= scale_factor;
+        self.scale_y *= scale_factor;
+
+        // Adjust translation to maintain origin point
+        self.translate_x = origin_x - dx * scale_factor;
+        self.translate_y = origin_y - dy * scale_factor;
+    }
+
+    pub fn apply_rotation(&mut self, angle_deg: f64, _origin_x: f64, _origin_y: f64) {
+        self.rotation += angle_deg;
+
+        // Normalize rotation to 0-360 degrees
+        while self.rotation >= 360.0 {
+            self.rotation -= 360.0;
+        }
+        while self.rotation < 0.0 {
+            self.rotation += 360.0;
+        }
+    }
+}
@@ -7,103 +7,12 @@
 //  ╚═════╝   ╚═══╝  ╚══════╝╚═╝  ╚═╝╚══════╝╚═╝  ╚═╝   ╚═╝       ╚═╝     ╚═╝╚═╝  ╚═╝╚═╝  ╚═══╝╚═╝  ╚═╝ ╚═════╝ ╚══════╝╚═╝  ╚═╝
 //                                                            app/src/overlay_manager.rs
 
-use crate::coordinates::{PhysicalCoord, ScreenCoord};
+use crate::coordinates::PhysicalCoord;
 use crate::calibration::CalibrationManager;
+use crate::overlay::{Overlay, OverlayType, Transform};
+use crate::viewport::PagePoint;
 use yew::prelude::*;
-use web_sys::{MouseEvent, HtmlElement};
-use wasm_bindgen::JsCast;
-
-#[derive(Clone, PartialEq)]
-pub struct Overlay {
-    pub id: String,
-    pub page_index: usize,
-    pub overlay_type: OverlayType,
-    pub transform: Transform,
-    pub position: PhysicalCoord,
-    pub size: PhysicalCoord,
-    pub visible: bool,
-    pub selected: bool,
-    pub z_index: i32,
-}
-
-#[derive(Clone, PartialEq)]
-pub enum OverlayType {
-    TextBox,
-    Image,
-    Shape,
-    Annotation,
-    Measurement,
-}
-
-#[derive(Clone, PartialEq)]
-pub struct Transform {
-    pub translate_x: f64,
-    pub translate_y: f64,
-    pub scale_x: f64,
-    pub scale_y: f64,
-    pub rotation: f64,
-    pub skew_x: f64,
-    pub skew_y: f64,
-}
-
-impl Default for Transform {
-    fn default() -> Self {
-        Self {
-            translate_x: 0.0,
-            translate_y: 0.0,
-            scale_x: 1.0,
-            scale_y: 1.0,
-            rotation: 0.0,
-            skew_x: 0.0,
-            skew_y: 0.0,
-        }
-    }
-}
-
-impl Transform {
-    pub fn to_css_string(&self) -> String {
-        format!(
-            "translate({}px, {}px) scale({}, {}) rotate({}deg) skew({}deg, {}deg)",
-            self.translate_x,
-            self.translate_y,
-            self.scale_x,
-            self.scale_y,
-            self.rotation,
-            self.skew_x,
-            self.skew_y
-        )
-    }
-
-    pub fn apply_translation(&mut self, delta_x: f64, delta_y: f64) {
-        self.translate_x += delta_x;
-        self.translate_y += delta_y;
-    }
-
-    pub fn apply_scaling(&mut self, scale_factor: f64, origin_x: f64, origin_y: f64) {
-        // Apply scaling around a specific origin point
-        let dx = origin_x - self.translate_x;
-        let dy = origin_y - self.translate_y;
-        
-        self.scale_x *= scale_factor;
-        self.scale_y *= scale_factor;
-        
-        // Adjust translation to maintain origin point
-        self.translate_x = origin_x - dx * scale_factor;
-        self.translate_y = origin_y - dy * scale_factor;
-    }
-
-    pub fn apply_rotation(&mut self, angle_deg: f64, origin_x: f64, origin_y: f64) {
-        self.rotation += angle_deg;
-        
-        // Normalize rotation to 0-360 degrees
-        while self.rotation >= 360.0 {
-            self.rotation -= 360.0;
-        }
-        while self.rotation < 0.0 {
-            self.rotation += 360.0;
-        }
-    }
-}
+use web_sys::MouseEvent;
 
 #[derive(Properties, PartialEq)]
 pub struct OverlayManagerProps {
@@ -111,6 +20,18 @@ pub struct OverlayManagerProps {
     pub page_index: usize,
     pub overlays: Vec<Overlay>,
     pub on_overlay_change: Callback<(usize, Overlay)>,
+    /// Emitted when a drag ends, closing the undo step for its deltas
+    #[prop_or_default]
+    pub on_gesture_end: Callback<()>,
+    #[prop_or_default]
+    pub on_create: Callback<(OverlayType, PagePoint)>,
+    /// Document zoom, so pointer deltas can be unzoomed
+    #[prop_or(1.0)]
+    pub zoom: f64,
+    #[prop_or_default]
+    pub on_delete: Callback<String>,
+    #[prop_or_default]
+    pub on_duplicate: Callback<String>,
     pub snap_enabled: bool,
     pub snap_tolerance: f64,
     pub grid_size_mm: f64,
@@ -129,7 +50,6 @@ pub enum TransformMode {
     Move,
     Scale,
     Rotate,
-    None,
 }
 
 pub enum OverlayManagerMsg {
@@ -141,7 +61,7 @@ pub enum OverlayManagerMsg {
     SetTransformMode(TransformMode),
     DeleteOverlay(String),
     DuplicateOverlay(String),
-    CreateOverlay(OverlayType, PhysicalCoord),
+    CreateOverlay(OverlayType, PagePoint),
     UpdateTransform(String, Transform),
 }
 
@@ -174,8 +94,9 @@ impl Component for OverlayManager {
                     
                     let current_x = event.client_x() as f64;
                     let current_y = event.client_y() as f64;
-                    let delta_x = current_x - start_x;
-                    let delta_y = current_y - start_y;
+                    // Pointer movement is in zoomed screen pixels, transforms are in page pixels
+                    let zoom = ctx.props().zoom;
+                    let (delta_x, delta_y) = ((current_x - start_x) / zoom, (current_y - start_y) / zoom);
                     
                     // Find the overlay and update its transform
                     if let Some(overlay) = ctx.props().overlays.iter()
@@ -200,7 +121,7 @@ impl Component for OverlayManager {
                             TransformMode::Scale => {
                                 let scale_factor = 1.0 + (delta_x + delta_y) / 200.0;
                                 new_overlay.transform.apply_scaling(
-                                    scale_factor.max(0.1).min(5.0),
+                                    scale_factor.clamp(0.1, 5.0),
                                     new_overlay.position.x,
                                     new_overlay.position.y
                                 );
@@ -213,7 +134,6 @@ impl Component for OverlayManager {
                                     new_overlay.position.y
                                 );
                             }
-                            TransformMode::None => {}
                         }
                         
                         ctx.props().on_overlay_change.emit((ctx.props().page_index, new_overlay));
@@ -226,7 +146,9 @@ impl Component for OverlayManager {
                 }
             }
             OverlayManagerMsg::EndDrag => {
-                self.dragging_overlay = None;
+                if self.dragging_overlay.take().is_some() {
+                    ctx.props().on_gesture_end.emit(());
+                }
                 self.drag_start_pos = None;
                 true
             }
@@ -243,15 +165,18 @@ impl Component for OverlayManager {
                 true
             }
             OverlayManagerMsg::DeleteOverlay(overlay_id) => {
-                // This would be handled by parent component
-                false
+                if self.selected_overlay.as_ref() == Some(&overlay_id) {
+                    self.selected_overlay = None;
+                }
+                ctx.props().on_delete.emit(overlay_id);
+                true
             }
             OverlayManagerMsg::DuplicateOverlay(overlay_id) => {
-                // This would be handled by parent component
+                ctx.props().on_duplicate.emit(overlay_id);
                 false
             }
-            OverlayManagerMsg::CreateOverlay(overlay_type, position) => {
-                // This would be handled by parent component
+            OverlayManagerMsg::CreateOverlay(overlay_type, point) => {
+                ctx.props().on_create.emit((overlay_type, point));
                 false
             }
             OverlayManagerMsg::UpdateTransform(overlay_id, transform) => {
@@ -280,7 +205,13 @@ impl Component for OverlayManager {
             >
                 { self.render_transform_toolbar(ctx) }
                 
-                <div class="overlay-container">
+                <div
+                    class="overlay-container"
+                    onclick={ctx.link().batch_callback(|e: MouseEvent| {
+                        // Clicks on empty space clear the selection
+                        (e.target() == e.current_target()).then_some(OverlayManagerMsg::DeselectAll)
+                    })}
+                >
                     { if let Some(coord_sys) = coord_system {
                         self.render_overlays(ctx, coord_sys)
                     } else {
@@ -300,6 +231,9 @@ impl Component for OverlayManager {
 
 impl OverlayManager {
     fn render_transform_toolbar(&self, ctx: &Context<Self>) -> Html {
+        let page_index = ctx.props().page_index;
+        let default_point = move || PagePoint { page_index, position: PhysicalCoord { x: 50.0, y: 50.0 } };
+
         html! {
             <div class="transform-toolbar">
                 <div class="toolbar-group">
@@ -334,11 +268,8 @@ impl OverlayManager {
                     <div class="create-buttons">
                         <button 
                             class="create-button"
-                            onclick={ctx.link().callback(|_| {
-                                OverlayManagerMsg::CreateOverlay(
-                                    OverlayType::TextBox, 
-                                    PhysicalCoord { x: 50.0, y: 50.0 }
-                                )
+                            onclick={ctx.link().callback(move |_| {
+                                OverlayManagerMsg::CreateOverlay(OverlayType::TextBox, default_point())
                             })}
                             title="Text Box"
                         >
@@ -346,11 +277,8 @@ impl OverlayManager {
                         </button>
                         <button 
                             class="create-button"
-                            onclick={ctx.link().callback(|_| {
-                                OverlayManagerMsg::CreateOverlay(
-                                    OverlayType::Shape, 
-                                    PhysicalCoord { x: 50.0, y: 50.0 }
-                                )
+                            onclick={ctx.link().callback(move |_| {
+                                OverlayManagerMsg::CreateOverlay(OverlayType::Shape, default_point())
                             })}
                             title="Shape"
                         >
@@ -358,11 +286,8 @@ impl OverlayManager {
                         </button>
                         <button 
                             class="create-button"
-                            onclick={ctx.link().callback(|_| {
-                                OverlayManagerMsg::CreateOverlay(
-                                    OverlayType::Annotation, 
-                                    PhysicalCoord { x: 50.0, y: 50.0 }
-                                )
+                            onclick={ctx.link().callback(move |_| {
+                                OverlayManagerMsg::CreateOverlay(OverlayType::Annotation, default_point())
                             })}
                             title="Annotation"
                         >
@@ -381,8 +306,8 @@ impl OverlayManager {
 
         let overlay_elements = page_overlays.iter().map(|overlay| {
             let screen_pos = coord_sys.physical_to_screen(overlay.position);
-            let width_px = coord_sys.mm_to_px(overlay.size.x);
-            let height_px = coord_sys.mm_to_px(overlay.size.y);
+            let width_px = coord_sys.mm_to_px_x(overlay.size.x);
+            let height_px = coord_sys.mm_to_px_y(overlay.size.y);
             
             let is_selected = self.selected_overlay.as_ref() == Some(&overlay.id);
             let transform_style = overlay.transform.to_css_string();
@@ -558,6 +483,15 @@ impl OverlayManager {
                         >
                             {"📋 Duplicate"}
                         </button>
+                        <button 
+                            class="action-button reset"
+                            onclick={ctx.link().callback({
+                                let overlay_id = selected_id.to_string();
+                                move |_| OverlayManagerMsg::UpdateTransform(overlay_id.clone(), Transform::default())
+                            })}
+                        >
+                            {"↺ Reset"}
+                        </button>
                     </div>
                 </div>
             }
@@ -584,20 +518,20 @@ impl OverlayManager {
         calibration_manager: &CalibrationManager
     ) -> Transform {
         if let Some(coord_sys) = calibration_manager.get_coordinate_system() {
-            let tolerance_px = coord_sys.mm_to_px(tolerance_mm);
-            let grid_size_px = coord_sys.mm_to_px(grid_size_mm);
+            let tolerance = coord_sys.physical_to_screen(PhysicalCoord { x: tolerance_mm, y: tolerance_mm });
+            let grid_size = coord_sys.physical_to_screen(PhysicalCoord { x: grid_size_mm, y: grid_size_mm });
             
             let mut snapped_transform = transform.clone();
             
             // Snap X coordinate
-            let snap_x = (transform.translate_x / grid_size_px).round() * grid_size_px;
-            if (transform.translate_x - snap_x).abs() <= tolerance_px {
+            let snap_x = (transform.translate_x / grid_size.x).round() * grid_size.x;
+            if (transform.translate_x - snap_x).abs() <= tolerance.x {
                 snapped_transform.translate_x = snap_x;
             }
             
             // Snap Y coordinate  
-            let snap_y = (transform.translate_y / grid_size_px).round() * grid_size_px;
-            if (transform.translate_y - snap_y).abs() <= tolerance_px {
+            let snap_y = (transform.translate_y / grid_size.y).round() * grid_size.y;
+            if (transform.translate_y - snap_y).abs() <= tolerance.y {
                 snapped_transform.translate_y = snap_y;
             }
             
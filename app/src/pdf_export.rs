@@ -0,0 +1,948 @@
+//
+// ██████╗ ██████╗ ███████╗   ███████╗██╗  ██╗██████╗  ██████╗ ██████╗ ████████╗
+// ██╔══██╗██╔══██╗██╔════╝   ██╔════╝╚██╗██╔╝██╔══██╗██╔═══██╗██╔══██╗╚══██╔══╝
+// ██████╔╝██║  ██║█████╗     █████╗   ╚███╔╝ ██████╔╝██║   ██║██████╔╝   ██║
+// ██╔═══╝ ██║  ██║██╔══╝     ██╔══╝   ██╔██╗ ██╔═══╝ ██║   ██║██╔══██╗   ██║
+// ██║     ██████╔╝██║        ███████╗██╔╝ ██╗██║     ╚██████╔╝██║  ██║   ██║
+// ╚═╝     ╚═════╝ ╚═╝        ╚══════╝╚═╝  ╚═╝╚═╝      ╚═════╝ ╚═╝  ╚═╝   ╚═╝
+//                                                         app/src/pdf_export.rs
+
+use crate::afm::win_ansi_byte;
+use crate::comb::CombSettings;
+use crate::coordinates::{CoordinateSystem, PageFormat, PhysicalCoord};
+use crate::font_metrics::{FontMetricsCalculator, StandardFont};
+use crate::form_data::{format_us_date, FieldValue, FormData};
+use crate::form_template::{FieldType, FormFieldInfo, FormTemplate, PageInfo};
+use crate::overlay::{Overlay, OverlayType, Transform};
+use crate::printer_calibration::AffineTransform;
+use crate::text_layout::{
+    attachment_format, attachment_line_pitch_mm, attachment_pages, continuations, layout_multiline,
+    AttachmentLine, LineSettings, ATTACHMENT_MARGIN_MM,
+};
+
+/// PDF user space units (points) per millimetre
+pub const PT_PER_MM: f64 = 72.0 / 25.4;
+
+/// Printed checkbox size, matching the 15px on-screen checkbox at 96 DPI
+pub const CHECKBOX_SIZE_MM: f64 = 4.0;
+/// Gap between a checkbox and its label, matching the 10px CSS margin
+pub const CHECKBOX_LABEL_GAP_MM: f64 = 2.6;
+/// Distance of the page footer baseline from the bottom edge
+const FOOTER_BASELINE_MM: f64 = 10.0;
+const FOOTER_LEFT_MM: f64 = 12.7;
+
+/// Font resource names in every page's resource dictionary
+const VALUE_FONT: &str = "F1";
+const LABEL_FONT: &str = "F2";
+
+/// Typography shared by the PDF and SVG exporters
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+    /// Font for field values
+    pub font: StandardFont,
+    pub font_size_pt: f64,
+    /// Helvetica size for field labels and the page footer
+    pub label_size_pt: f64,
+    /// Printer correction applied to whole PDF pages, so output lands on pre-printed stock
+    pub print_correction: Option<AffineTransform>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            font: StandardFont::Helvetica,
+            font_size_pt: 12.0,
+            label_size_pt: 7.0,
+            print_correction: None,
+        }
+    }
+}
+
+/// Render a filled form as a vector PDF, each page on its template page's paper size
+/// and orientation, with any overflow attachment pages on the form's paper.
+///
+/// Everything is placed in physical millimetres converted to points, text uses
+/// the Standard 14 fonts (no embedding), and the file carries no timestamps or
+/// ids, so the same input always produces the same bytes. Overlay transforms are
+/// stored in calibrated CSS pixels and are converted with `coord_system`.
+pub fn export_pdf(
+    template: &FormTemplate,
+    form_data: &FormData,
+    overlays: &[Overlay],
+    coord_system: &CoordinateSystem,
+    options: &ExportOptions,
+) -> Vec<u8> {
+    // Fixed layout: catalog, page tree, two fonts, info, then a page and content stream per page
+    const CATALOG_ID: usize = 1;
+    const PAGES_ID: usize = 2;
+    const VALUE_FONT_ID: usize = 3;
+    const LABEL_FONT_ID: usize = 4;
+    const INFO_ID: usize = 5;
+    const FIRST_PAGE_ID: usize = 6;
+
+    // Form pages, then attachment pages continuing overflowing multi-line fields
+    let mut pages: Vec<(PageFormat, Vec<u8>)> = template
+        .pages
+        .iter()
+        .enumerate()
+        .map(|(idx, page)| {
+            let content =
+                page_content(template, idx, page, form_data, overlays, coord_system, options);
+            (page.format, content)
+        })
+        .collect();
+
+    let mut metrics = FontMetricsCalculator::new();
+    let (font, size_pt) = (&options.font, options.font_size_pt);
+    let continuations = continuations(&mut metrics, template, form_data, font, size_pt);
+    let format = attachment_format(template);
+    let attachments = attachment_pages(&mut metrics, &continuations, font, size_pt, &format);
+    for (idx, lines) in attachments.iter().enumerate() {
+        let content = attachment_content(template, &format, idx, attachments.len(), lines, options);
+        pages.push((format, content));
+    }
+
+    let page_ids: Vec<usize> = (0..pages.len()).map(|idx| FIRST_PAGE_ID + idx * 2).collect();
+
+    let mut pdf = PdfWriter::new();
+    pdf.object(CATALOG_ID, format!("<< /Type /Catalog /Pages {} 0 R >>", PAGES_ID).as_bytes());
+    pdf.object(
+        PAGES_ID,
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids.iter().map(|id| format!("{} 0 R", id)).collect::<Vec<_>>().join(" "),
+            page_ids.len()
+        )
+        .as_bytes(),
+    );
+    pdf.object(VALUE_FONT_ID, font_dictionary(&options.font).as_bytes());
+    pdf.object(LABEL_FONT_ID, font_dictionary(&StandardFont::Helvetica).as_bytes());
+
+    let mut info = b"<< /Title ".to_vec();
+    info.extend(pdf_text(&format!("{} - {}", template.form_id, template.title)));
+    info.extend(b" /Producer (Pixel-Perfect Forms) >>");
+    pdf.object(INFO_ID, &info);
+
+    for ((format, content), page_id) in pages.iter().zip(page_ids.iter().copied()) {
+        pdf.object(
+            page_id,
+            format!(
+                "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /{} {} 0 R /{} {} 0 R >> >> /Contents {} 0 R >>",
+                PAGES_ID,
+                num(format.width_mm() * PT_PER_MM),
+                num(format.height_mm() * PT_PER_MM),
+                VALUE_FONT,
+                VALUE_FONT_ID,
+                LABEL_FONT,
+                LABEL_FONT_ID,
+                page_id + 1
+            )
+            .as_bytes(),
+        );
+
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend(content);
+        stream.extend(b"\nendstream");
+        pdf.object(page_id + 1, &stream);
+    }
+
+    pdf.finish(CATALOG_ID, INFO_ID)
+}
+
+fn font_dictionary(font: &StandardFont) -> String {
+    format!(
+        "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+        font.pdf_base_font()
+    )
+}
+
+fn page_content(
+    template: &FormTemplate,
+    page_index: usize,
+    page: &PageInfo,
+    form_data: &FormData,
+    overlays: &[Overlay],
+    coord_system: &CoordinateSystem,
+    options: &ExportOptions,
+) -> Vec<u8> {
+    let mut content = ContentStream::new(&options.font);
+    let mut metrics = FontMetricsCalculator::new();
+    let page_height = page.format.height_mm();
+
+    for field in &page.fields {
+        let value = form_data.get(&field.id);
+        draw_field(&mut content, page_height, field, value, &mut metrics, options);
+    }
+
+    // Overlays sit above the form, in stacking order
+    let mut page_overlays: Vec<&Overlay> =
+        overlays.iter().filter(|o| o.page_index == page_index && o.visible).collect();
+    page_overlays.sort_by_key(|o| o.z_index);
+    for overlay in page_overlays {
+        draw_overlay(&mut content, page_height, overlay, coord_system);
+    }
+
+    let footer = format!(
+        "California Judicial Council Form {} (Rev. {}) - Page {} of {}",
+        template.form_id,
+        template.revision,
+        page_index + 1,
+        template.pages.len()
+    );
+    content.text(
+        LABEL_FONT,
+        options.label_size_pt,
+        FOOTER_LEFT_MM * PT_PER_MM,
+        FOOTER_BASELINE_MM * PT_PER_MM,
+        &footer,
+    );
+
+    match &options.print_correction {
+        Some(correction) => {
+            let mut corrected = ContentStream::new(&options.font);
+            corrected.push("q");
+            corrected.push(&format!(
+                "{} cm",
+                print_correction_matrix(correction, page_height).to_operands()
+            ));
+            corrected.bytes.extend(content.bytes);
+            corrected.push("Q");
+            corrected.into_bytes()
+        }
+        None => content.into_bytes(),
+    }
+}
+
+/// Content of one attachment page: continued text under a heading naming each field,
+/// with a footer tying the page to the form
+fn attachment_content(
+    template: &FormTemplate,
+    format: &PageFormat,
+    attachment_index: usize,
+    attachment_count: usize,
+    lines: &[AttachmentLine],
+    options: &ExportOptions,
+) -> Vec<u8> {
+    let mut content = ContentStream::new(&options.font);
+    let page_height = format.height_mm();
+    let left = ATTACHMENT_MARGIN_MM * PT_PER_MM;
+    let pitch = attachment_line_pitch_mm(options.font_size_pt);
+
+    for (idx, line) in lines.iter().enumerate() {
+        let baseline = page_y(page_height, ATTACHMENT_MARGIN_MM + (idx + 1) as f64 * pitch);
+        match line {
+            AttachmentLine::Heading(heading) => {
+                content.text(LABEL_FONT, options.font_size_pt, left, baseline, heading)
+            }
+            AttachmentLine::Text(text) => {
+                content.text(VALUE_FONT, options.font_size_pt, left, baseline, text)
+            }
+            AttachmentLine::Blank => {}
+        }
+    }
+
+    let footer = format!(
+        "Attachment to Form {} (Rev. {}) - Page {} of {}",
+        template.form_id,
+        template.revision,
+        attachment_index + 1,
+        attachment_count
+    );
+    content.text(
+        LABEL_FONT,
+        options.label_size_pt,
+        FOOTER_LEFT_MM * PT_PER_MM,
+        FOOTER_BASELINE_MM * PT_PER_MM,
+        &footer,
+    );
+    content.into_bytes()
+}
+
+/// Size a field's value is set at: the export size, or for auto-fit fields the size
+/// the value was shrunk to on screen
+pub fn field_font_size(
+    metrics: &mut FontMetricsCalculator,
+    field: &FormFieldInfo,
+    text: &str,
+    options: &ExportOptions,
+) -> f64 {
+    if !field.auto_fit {
+        return options.font_size_pt;
+    }
+    metrics.fit_font_size(&options.font, options.font_size_pt, text, field.size.x, field.size.y)
+}
+
+/// A page-millimetre correction expressed in PDF points: flip to top-left millimetres,
+/// correct, then flip back
+pub fn print_correction_matrix(correction: &AffineTransform, page_height_mm: f64) -> Matrix {
+    let to_points = Matrix([PT_PER_MM, 0.0, 0.0, -PT_PER_MM, 0.0, page_height_mm * PT_PER_MM]);
+    let to_mm = Matrix([1.0 / PT_PER_MM, 0.0, 0.0, -1.0 / PT_PER_MM, 0.0, page_height_mm]);
+    let AffineTransform { a, b, c, d, e, f } = *correction;
+    to_points.multiply(&Matrix([a, b, c, d, e, f])).multiply(&to_mm)
+}
+
+/// Render a single-page printer test sheet with a labelled crosshair at each target.
+///
+/// Printed at actual size, each crosshair centre should sit exactly at its labelled
+/// distance from the left and top paper edges; the measured misses feed
+/// `PrinterCalibration::from_offsets`.
+pub fn export_printer_test_sheet(format: &PageFormat, targets: &[PhysicalCoord]) -> Vec<u8> {
+    const CATALOG_ID: usize = 1;
+    const PAGES_ID: usize = 2;
+    const LABEL_FONT_ID: usize = 3;
+    const INFO_ID: usize = 4;
+    const PAGE_ID: usize = 5;
+    const ARM_MM: f64 = 6.0;
+
+    let page_height = format.height_mm();
+    let mut content = ContentStream::new(&StandardFont::Helvetica);
+    content.text(
+        LABEL_FONT,
+        10.0,
+        FOOTER_LEFT_MM * PT_PER_MM,
+        page_y(page_height, 10.0),
+        "Printer test sheet - print at actual size (100%), no fit to page",
+    );
+    content.text(
+        LABEL_FONT,
+        8.0,
+        FOOTER_LEFT_MM * PT_PER_MM,
+        page_y(page_height, 14.0),
+        "Measure each crosshair centre from the left and top paper edges and enter how far it missed its label.",
+    );
+
+    content.push("0.25 w 0 G");
+    for (idx, target) in targets.iter().enumerate() {
+        let x = target.x * PT_PER_MM;
+        let y = page_y(page_height, target.y);
+        let arm = ARM_MM * PT_PER_MM;
+        content.push(&format!("{} {} m {} {} l S", num(x - arm), num(y), num(x + arm), num(y)));
+        content.push(&format!("{} {} m {} {} l S", num(x), num(y - arm), num(x), num(y + arm)));
+        content.text(
+            LABEL_FONT,
+            7.0,
+            x + 1.5 * PT_PER_MM,
+            y + 1.5 * PT_PER_MM,
+            &format!("{}: {} mm, {} mm", idx + 1, num(target.x), num(target.y)),
+        );
+    }
+    let content = content.into_bytes();
+
+    let mut pdf = PdfWriter::new();
+    pdf.object(CATALOG_ID, format!("<< /Type /Catalog /Pages {} 0 R >>", PAGES_ID).as_bytes());
+    pdf.object(PAGES_ID, format!("<< /Type /Pages /Kids [{} 0 R] /Count 1 >>", PAGE_ID).as_bytes());
+    pdf.object(LABEL_FONT_ID, font_dictionary(&StandardFont::Helvetica).as_bytes());
+    pdf.object(INFO_ID, b"<< /Title (Printer test sheet) /Producer (Pixel-Perfect Forms) >>");
+    pdf.object(
+        PAGE_ID,
+        format!(
+            "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /{} {} 0 R >> >> /Contents {} 0 R >>",
+            PAGES_ID,
+            num(format.width_mm() * PT_PER_MM),
+            num(page_height * PT_PER_MM),
+            LABEL_FONT,
+            LABEL_FONT_ID,
+            PAGE_ID + 1
+        )
+        .as_bytes(),
+    );
+
+    let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+    stream.extend(&content);
+    stream.extend(b"\nendstream");
+    pdf.object(PAGE_ID + 1, &stream);
+
+    pdf.finish(CATALOG_ID, INFO_ID)
+}
+
+fn draw_field(
+    content: &mut ContentStream,
+    page_height_mm: f64,
+    field: &FormFieldInfo,
+    value: Option<&FieldValue>,
+    metrics: &mut FontMetricsCalculator,
+    options: &ExportOptions,
+) {
+    let left = field.position.x * PT_PER_MM;
+    let top = page_y(page_height_mm, field.position.y);
+    let width = field.size.x * PT_PER_MM;
+    let height = field.size.y * PT_PER_MM;
+    let bottom = top - height;
+    let label_baseline = top + options.label_size_pt * 0.3;
+
+    match field.field_type {
+        FieldType::TextInput | FieldType::Date => {
+            content.text(LABEL_FONT, options.label_size_pt, left, label_baseline, &field.label);
+
+            let text = match value {
+                Some(FieldValue::Text(text)) => text.clone(),
+                Some(FieldValue::Date(date)) => format_us_date(date),
+                _ => String::new(),
+            };
+            if !text.is_empty() {
+                let size_pt = field_font_size(metrics, field, &text, options);
+                let baseline =
+                    top - options.font.centred_baseline_mm(size_pt, field.size.y) * PT_PER_MM;
+                content.push("q");
+                content.push(&format!(
+                    "{} {} {} {} re W n",
+                    num(left),
+                    num(bottom),
+                    num(width),
+                    num(height)
+                ));
+                content.text(VALUE_FONT, size_pt, left, baseline, &text);
+                content.push("Q");
+            }
+        }
+        FieldType::MultilineText => {
+            content.text(LABEL_FONT, options.label_size_pt, left, label_baseline, &field.label);
+
+            if let Some(FieldValue::Text(text)) = value.filter(|v| !v.is_empty()) {
+                // Wrapped onto the form's printed lines; overflow goes to an attachment page
+                let (font, size_pt) = (&options.font, options.font_size_pt);
+                let settings = LineSettings::for_field(field, size_pt);
+                let layout = layout_multiline(metrics, font, size_pt, text, &settings);
+                content.push("q");
+                content.push(&format!(
+                    "{} {} {} {} re W n",
+                    num(left),
+                    num(bottom),
+                    num(width),
+                    num(height)
+                ));
+                for (idx, line) in layout.lines.iter().enumerate() {
+                    let baseline = top - settings.baseline_mm(idx, font, size_pt) * PT_PER_MM;
+                    content.text(VALUE_FONT, size_pt, left, baseline, line);
+                }
+                content.push("Q");
+            }
+        }
+        FieldType::Comb => {
+            content.text(LABEL_FONT, options.label_size_pt, left, label_baseline, &field.label);
+            let Some(comb) = CombSettings::for_field(field) else {
+                return;
+            };
+
+            // The boxes, then each character centred in its own box
+            let pitch = comb.pitch_mm * PT_PER_MM;
+            content.push("0.5 w 0 G");
+            for cell in 0..comb.cells {
+                content.push(&format!(
+                    "{} {} {} {} re S",
+                    num(left + cell as f64 * pitch),
+                    num(bottom),
+                    num(pitch),
+                    num(height)
+                ));
+            }
+
+            if let Some(FieldValue::Text(text)) = value {
+                let (font, size_pt) = (&options.font, options.font_size_pt);
+                let baseline =
+                    top - CombSettings::baseline_mm(font, size_pt, field.size.y) * PT_PER_MM;
+                for (c, offset) in comb.glyph_offsets(metrics, font, size_pt, text) {
+                    let x = left + offset * PT_PER_MM;
+                    content.text(VALUE_FONT, size_pt, x, baseline, &c.to_string());
+                }
+                // A value longer than the boxes is printed whole beneath them
+                if let Some(note) = comb.overflow_note(text) {
+                    let note_baseline = bottom - options.label_size_pt * 1.2;
+                    content.text(LABEL_FONT, options.label_size_pt, left, note_baseline, &note);
+                }
+            }
+        }
+        FieldType::Checkbox => {
+            let size = CHECKBOX_SIZE_MM * PT_PER_MM;
+            let box_bottom = bottom + (height - size) / 2.0;
+            content.push("0.75 w 0 G");
+            content.push(&format!(
+                "{} {} {} {} re S",
+                num(left),
+                num(box_bottom),
+                num(size),
+                num(size)
+            ));
+
+            if matches!(value, Some(FieldValue::Checked(true))) {
+                let inset = size * 0.2;
+                let (x0, y0) = (left + inset, box_bottom + inset);
+                let (x1, y1) = (left + size - inset, box_bottom + size - inset);
+                content.push(&format!("{} {} m {} {} l S", num(x0), num(y0), num(x1), num(y1)));
+                content.push(&format!("{} {} m {} {} l S", num(x0), num(y1), num(x1), num(y0)));
+            }
+
+            let label_x = left + size + CHECKBOX_LABEL_GAP_MM * PT_PER_MM;
+            let label_baseline = box_bottom + size / 2.0 - options.label_size_pt * 0.35;
+            content.text(LABEL_FONT, options.label_size_pt, label_x, label_baseline, &field.label);
+        }
+        FieldType::Signature => {
+            content.text(LABEL_FONT, options.label_size_pt, left, label_baseline, &field.label);
+            content.push("0.75 w 0 G");
+            content.push(&format!(
+                "{} {} m {} {} l S",
+                num(left),
+                num(bottom),
+                num(left + width),
+                num(bottom)
+            ));
+
+            // Signature images aren't embedded; mark the line as signed instead
+            if matches!(value, Some(FieldValue::Signature(data)) if !data.is_empty()) {
+                let baseline = bottom + options.label_size_pt * 0.5;
+                content.text(
+                    LABEL_FONT,
+                    options.label_size_pt,
+                    left,
+                    baseline,
+                    "(signature on file)",
+                );
+            }
+        }
+    }
+}
+
+fn draw_overlay(
+    content: &mut ContentStream,
+    page_height_mm: f64,
+    overlay: &Overlay,
+    coord_system: &CoordinateSystem,
+) {
+    let width = overlay.size.x;
+    let height = overlay.size.y;
+
+    // Page flip from top-left millimetres to bottom-left points, then the CSS transform
+    let page = Matrix([PT_PER_MM, 0.0, 0.0, -PT_PER_MM, 0.0, page_height_mm * PT_PER_MM]);
+    let matrix = page.multiply(&css_transform_matrix(overlay, coord_system));
+
+    content.push("q");
+    content.push(&format!("{} cm", matrix.to_operands()));
+    // Line widths are in overlay millimetres from here on
+    content.push("0.2 w 0.2 0.4 0.8 RG");
+
+    let rect = format!("0 0 {} {} re", num(width), num(height));
+    match overlay.overlay_type {
+        OverlayType::Shape => content.push(&format!("0.9 g {} B", rect)),
+        OverlayType::Annotation => content.push(&format!("[1 0.5] 0 d {} S", rect)),
+        OverlayType::Measurement => {
+            content.push(&format!(
+                "0 {} m {} {} l S",
+                num(height / 2.0),
+                num(width),
+                num(height / 2.0)
+            ));
+        }
+        OverlayType::TextBox | OverlayType::Image => content.push(&format!("{} S", rect)),
+    }
+
+    content.push("Q");
+}
+
+/// Matrix placing an overlay's local millimetre box on the page, matching the
+/// browser: `transform` applied about the box centre (the CSS default origin)
+pub fn css_transform_matrix(overlay: &Overlay, coord_system: &CoordinateSystem) -> Matrix {
+    let transform: &Transform = &overlay.transform;
+    let centre_x = overlay.size.x / 2.0;
+    let centre_y = overlay.size.y / 2.0;
+    let rotation = transform.rotation.to_radians();
+
+    Matrix::translate(
+        overlay.position.x + centre_x + coord_system.px_to_mm_x(transform.translate_x),
+        overlay.position.y + centre_y + coord_system.px_to_mm_y(transform.translate_y),
+    )
+    .multiply(&Matrix([transform.scale_x, 0.0, 0.0, transform.scale_y, 0.0, 0.0]))
+    .multiply(&Matrix([rotation.cos(), rotation.sin(), -rotation.sin(), rotation.cos(), 0.0, 0.0]))
+    .multiply(&Matrix([
+        1.0,
+        transform.skew_y.to_radians().tan(),
+        transform.skew_x.to_radians().tan(),
+        1.0,
+        0.0,
+        0.0,
+    ]))
+    .multiply(&Matrix::translate(-centre_x, -centre_y))
+}
+
+/// 2D affine matrix `[a b c d e f]` in PDF/CSS order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix(pub [f64; 6]);
+
+impl Matrix {
+    pub fn translate(x: f64, y: f64) -> Self {
+        Matrix([1.0, 0.0, 0.0, 1.0, x, y])
+    }
+
+    /// `self · other`: apply `other` first, then `self`
+    pub fn multiply(&self, other: &Matrix) -> Matrix {
+        let [a1, b1, c1, d1, e1, f1] = self.0;
+        let [a2, b2, c2, d2, e2, f2] = other.0;
+        Matrix([
+            a1 * a2 + c1 * b2,
+            b1 * a2 + d1 * b2,
+            a1 * c2 + c1 * d2,
+            b1 * c2 + d1 * d2,
+            a1 * e2 + c1 * f2 + e1,
+            b1 * e2 + d1 * f2 + f1,
+        ])
+    }
+
+    pub fn to_operands(self) -> String {
+        self.0.iter().map(|v| num_with_precision(*v, 5)).collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// Millimetres from the top edge to points from the bottom edge
+fn page_y(page_height_mm: f64, mm_from_top: f64) -> f64 {
+    (page_height_mm - mm_from_top) * PT_PER_MM
+}
+
+/// Fixed-precision number without trailing zeros, so output is stable across platforms
+pub fn num(value: f64) -> String {
+    num_with_precision(value, 3)
+}
+
+/// Like `num` with a chosen number of decimals, for matrix terms where 0.001 is too coarse
+pub fn num_with_precision(value: f64, decimals: usize) -> String {
+    let formatted = format!("{:.*}", decimals, value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "-0" | "" => "0".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// PDF literal string in WinAnsiEncoding; unsupported characters become `?`
+fn pdf_text(text: &str) -> Vec<u8> {
+    let mut bytes = vec![b'('];
+    for c in text.chars() {
+        match win_ansi_byte(c).unwrap_or(b'?') {
+            b @ (b'(' | b')' | b'\\') => bytes.extend([b'\\', b]),
+            b @ 0x20..=0x7E => bytes.push(b),
+            // Octal escapes keep the file 7-bit clean
+            b => bytes.extend(format!("\\{:03o}", b).into_bytes()),
+        }
+    }
+    bytes.push(b')');
+    bytes
+}
+
+/// Page content under construction. Text is kerned with the AFM pairs of the font
+/// behind each resource, the same pairs `FontMetricsCalculator` measures with.
+struct ContentStream {
+    bytes: Vec<u8>,
+    /// Font behind `VALUE_FONT`; `LABEL_FONT` is always Helvetica
+    value_font: StandardFont,
+}
+
+impl ContentStream {
+    fn new(value_font: &StandardFont) -> Self {
+        Self { bytes: Vec::new(), value_font: value_font.clone() }
+    }
+
+    fn push(&mut self, operation: &str) {
+        self.bytes.extend(operation.as_bytes());
+        self.bytes.push(b'\n');
+    }
+
+    fn text(&mut self, font: &str, size_pt: f64, x: f64, y: f64, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.push(&format!("BT /{} {} Tf 0 g {} {} Td", font, num(size_pt), num(x), num(y)));
+
+        // Split the text at kerned pairs; TJ numbers are in 1/1000 em, subtracted from
+        // the advance, so a negative AFM adjustment is written as a positive number
+        let standard_font =
+            if font == VALUE_FONT { self.value_font.clone() } else { StandardFont::Helvetica };
+        let chars: Vec<char> = text.chars().collect();
+        let mut runs = Vec::new();
+        let mut run = String::new();
+        for (idx, &c) in chars.iter().enumerate() {
+            run.push(c);
+            if let Some(&next) = chars.get(idx + 1) {
+                let kerning = standard_font.kerning_units(c, next);
+                if kerning != 0.0 {
+                    runs.push((std::mem::take(&mut run), kerning));
+                }
+            }
+        }
+
+        if runs.is_empty() {
+            self.bytes.extend(pdf_text(text));
+            self.push(" Tj ET");
+            return;
+        }
+        self.bytes.push(b'[');
+        for (run, kerning) in runs {
+            self.bytes.extend(pdf_text(&run));
+            self.bytes.extend(format!(" {} ", num(-kerning)).into_bytes());
+        }
+        self.bytes.extend(pdf_text(&run));
+        self.push("] TJ ET");
+    }
+
+    fn into_bytes(mut self) -> Vec<u8> {
+        // Drop the final newline; the stream wrapper adds its own
+        self.bytes.pop();
+        self.bytes
+    }
+}
+
+/// Sequential object writer that tracks offsets for the cross-reference table
+struct PdfWriter {
+    bytes: Vec<u8>,
+    offsets: Vec<usize>,
+}
+
+impl PdfWriter {
+    fn new() -> Self {
+        Self { bytes: b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec(), offsets: Vec::new() }
+    }
+
+    /// Write object `id`; objects must be added in id order starting at 1
+    fn object(&mut self, id: usize, body: &[u8]) {
+        debug_assert_eq!(id, self.offsets.len() + 1, "PDF objects must be written in order");
+        self.offsets.push(self.bytes.len());
+        self.bytes.extend(format!("{} 0 obj\n", id).into_bytes());
+        self.bytes.extend(body);
+        self.bytes.extend(b"\nendobj\n");
+    }
+
+    fn finish(mut self, root_id: usize, info_id: usize) -> Vec<u8> {
+        let xref_offset = self.bytes.len();
+        let size = self.offsets.len() + 1;
+
+        self.bytes.extend(format!("xref\n0 {}\n0000000000 65535 f \n", size).into_bytes());
+        for offset in &self.offsets {
+            self.bytes.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+        }
+        self.bytes.extend(
+            format!(
+                "trailer\n<< /Size {} /Root {} 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+                size, root_id, info_id, xref_offset
+            )
+            .into_bytes(),
+        );
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comb::CombSettings;
+    use crate::coordinates::{DeviceCalibration, Orientation, PaperSize};
+    use crate::fixtures::sample_document;
+    use crate::golden::assert_golden;
+    use crate::printer_calibration::test_sheet_targets;
+    use crate::text_layout::LineSettings;
+
+    fn coord_system() -> CoordinateSystem {
+        CoordinateSystem::new(DeviceCalibration::default())
+    }
+
+    #[test]
+    fn test_output_is_deterministic() {
+        let (template, form_data, overlays) = sample_document();
+        let options = ExportOptions::default();
+        let first = export_pdf(&template, &form_data, &overlays, &coord_system(), &options);
+        let second = export_pdf(&template, &form_data, &overlays, &coord_system(), &options);
+        assert_eq!(first, second);
+
+        assert_golden("fl100_filled.pdf", &first);
+    }
+
+    #[test]
+    fn test_structure_and_positions() {
+        let (template, form_data, overlays) = sample_document();
+        let pdf = export_pdf(
+            &template,
+            &form_data,
+            &overlays,
+            &coord_system(),
+            &ExportOptions::default(),
+        );
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.contains("/MediaBox [0 0 612 792]"));
+        assert!(text.contains("/Count 3"));
+        assert!(text.contains("(Jane \\(Doe\\) Smith) Tj"));
+        assert!(text.contains("(03/07/2015) Tj"));
+
+        // Every xref entry points at its object header
+        let xref = text.rfind("xref\n").unwrap();
+        for (idx, line) in
+            text[xref..].lines().skip(3).take(5 + template.pages.len() * 2).enumerate()
+        {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(text[offset..].starts_with(&format!("{} 0 obj", idx + 1)));
+        }
+
+        // Attachments can be legal-size landscape; the page box follows the template
+        let mut template = template;
+        template.pages[2].format = PageFormat::new(PaperSize::Legal, Orientation::Landscape);
+        let pdf = export_pdf(
+            &template,
+            &form_data,
+            &overlays,
+            &coord_system(),
+            &ExportOptions::default(),
+        );
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/MediaBox [0 0 612 792]"));
+        assert!(text.contains("/MediaBox [0 0 1008 612]"));
+    }
+
+    #[test]
+    fn test_overlay_matrix_matches_css() {
+        let (_, _, overlays) = sample_document();
+        let matrix = css_transform_matrix(&overlays[0], &coord_system());
+
+        // Rotating 90° about the centre (65, 60) and shifting 10mm right
+        let [.., x, y] = matrix.0;
+        assert!((x - 85.0).abs() < 1e-3 && (y - 45.0).abs() < 1e-3, "({}, {})", x, y);
+
+        assert_eq!(num(-0.0001), "0");
+        assert_eq!(num(612.0), "612");
+    }
+
+    #[test]
+    fn test_print_correction() {
+        let (template, form_data, overlays) = sample_document();
+        let plain = export_pdf(
+            &template,
+            &form_data,
+            &overlays,
+            &coord_system(),
+            &ExportOptions::default(),
+        );
+
+        // Shift everything 2mm left and 1mm down on the paper
+        let correction = AffineTransform { e: -2.0, f: 1.0, ..AffineTransform::IDENTITY };
+        let options =
+            ExportOptions { print_correction: Some(correction), ..ExportOptions::default() };
+        let corrected = export_pdf(&template, &form_data, &overlays, &coord_system(), &options);
+        let text = String::from_utf8_lossy(&corrected);
+        assert_ne!(plain, corrected);
+        assert!(text.contains("stream\nq\n1 0 0 1 -5.66929 -2.83465 cm\n"));
+        assert!(text.contains("TJ ET\nQ\nendstream"));
+
+        let format = PageFormat::default();
+        let sheet = export_printer_test_sheet(&format, &test_sheet_targets(&format));
+        let text = String::from_utf8_lossy(&sheet);
+        assert!(text.contains("/Count 1"));
+        assert!(text.contains("(5: 107.95 mm, 139.7 mm) Tj"));
+    }
+
+    #[test]
+    fn test_multiline_overflow_attachment() {
+        let (template, mut form_data, overlays) = sample_document();
+        let options = ExportOptions::default();
+        let pdf = export_pdf(&template, &form_data, &overlays, &coord_system(), &options);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("(2012 Honda Civic) Tj"));
+        assert!(!text.contains("Attachment to Form"));
+
+        // Baselines follow the 6.35 mm printed lines
+        let font = &options.font;
+        let settings = LineSettings::for_field(&template.pages[2].fields[3], 12.0);
+        let second = page_y(279.4, 90.0 + settings.baseline_mm(1, font, 12.0));
+        assert!(text.contains(&format!("{} Td\n(2012 Honda Civic)", num(second))));
+
+        let request = "Respondent to pay the petitioner's attorney fees and costs of suit. ";
+        form_data.set("other_requests", FieldValue::Text(request.repeat(12))).unwrap();
+        let pdf = export_pdf(&template, &form_data, &overlays, &coord_system(), &options);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Count 4"));
+        assert!(text.contains("(\\(Continued on attachment\\)) Tj"));
+        assert!(text.contains("(Continued from page 3: Other Requests \\(specify\\)) Tj"));
+        let footer =
+            "[(Attachment to F) 30 (orm FL-100 \\(Rev) 80 (. 2024-01-01\\) - P) 40 (age 1 of 1)] TJ";
+        assert!(text.contains(footer));
+
+        // Attachments are printed on the form's paper
+        let mut a4 = template.clone();
+        for page in &mut a4.pages {
+            page.format = PageFormat::new(PaperSize::A4, Orientation::Portrait);
+        }
+        let pdf = export_pdf(&a4, &form_data, &overlays, &coord_system(), &options);
+        let text = String::from_utf8_lossy(&pdf);
+        assert_eq!(text.matches("/MediaBox [0 0 595.276 841.89]").count(), 4);
+    }
+
+    #[test]
+    fn test_text_kerned_as_measured() {
+        let (template, mut form_data, overlays) = sample_document();
+        form_data.set("petitioner_name", FieldValue::Text("WAVE Trust".to_string())).unwrap();
+        let options = ExportOptions::default();
+        let pdf = export_pdf(&template, &form_data, &overlays, &coord_system(), &options);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("[(W) 50 (A) 70 (VE T) 120 (rust)] TJ"));
+
+        // Advances less the TJ adjustments are the width measurement reports
+        let font = &options.font;
+        let advances: f64 = "WAVE Trust".chars().map(|c| font.char_width_units(c)).sum();
+        let printed_pt = (advances - (50.0 + 70.0 + 120.0)) / 1000.0 * 12.0;
+        let measured_px = FontMetricsCalculator::new().measure_text_px(font, 12.0, "WAVE Trust");
+        assert!((printed_pt * 96.0 / 72.0 - measured_px).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_auto_fit_size() {
+        let (template, mut form_data, overlays) = sample_document();
+        let name = "Maximiliana Wilhelmina Alexandra Bartholomew Montgomery-Wellington-Fitzgerald";
+        form_data.set("petitioner_name", FieldValue::Text(name.to_string())).unwrap();
+
+        // The size the on-screen input shrinks to is the size written to the PDF
+        let field = template.pages[0].fields.iter().find(|f| f.id == "petitioner_name").unwrap();
+        let size = FontMetricsCalculator::new().fit_font_size(
+            &StandardFont::Helvetica,
+            12.0,
+            name,
+            field.size.x,
+            field.size.y,
+        );
+        assert!(size < 12.0);
+
+        let options = ExportOptions::default();
+        let pdf = export_pdf(&template, &form_data, &overlays, &coord_system(), &options);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains(&format!("BT /F1 {} Tf", num(size))));
+    }
+
+    #[test]
+    fn test_comb_cells() {
+        let (template, mut form_data, overlays) = sample_document();
+        form_data.set("case_number", FieldValue::Text("FL-2024-00123".to_string())).unwrap();
+        let options = ExportOptions::default();
+        let pdf = export_pdf(&template, &form_data, &overlays, &coord_system(), &options);
+        let text = String::from_utf8_lossy(&pdf);
+
+        // Twelve 5 mm boxes, each character centred in its own box at the export size
+        let field = &template.pages[0].fields[2];
+        let comb = CombSettings::for_field(field).unwrap();
+        let cell = format!(" {} {} re S", num(5.0 * PT_PER_MM), num(15.0 * PT_PER_MM));
+        assert_eq!(text.matches(&cell).count(), 12);
+        let glyphs = comb.glyph_offsets(
+            &mut FontMetricsCalculator::new(),
+            &options.font,
+            12.0,
+            "FL-2024-00123",
+        );
+        assert_eq!(glyphs.len(), 12);
+        let baseline = page_y(279.4, 25.0 + CombSettings::baseline_mm(&options.font, 12.0, 15.0));
+        for (c, offset) in [glyphs[0], glyphs[11]] {
+            let x = (150.0 + offset) * PT_PER_MM;
+            assert!(text.contains(&format!("{} {} Td\n({}) Tj", num(x), num(baseline), c)));
+        }
+
+        // The thirteenth character has no box, so the whole value is printed as a warning
+        assert!(!text.contains("Td\n(3) Tj"));
+        assert!(text.contains("(\\(Does not fit 12 boxes: FL-2024-00123\\)) Tj"));
+        form_data.set("case_number", FieldValue::Text("FL-2024-0012".to_string())).unwrap();
+        let pdf = export_pdf(&template, &form_data, &overlays, &coord_system(), &options);
+        assert!(!String::from_utf8_lossy(&pdf).contains("Does not fit"));
+    }
+}
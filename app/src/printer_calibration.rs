@@ -0,0 +1,338 @@
+//
+// ██████╗ ██████╗ ██╗███╗   ██╗████████╗███████╗██████╗ ███████╗
+// ██╔══██╗██╔══██╗██║████╗  ██║╚══██╔══╝██╔════╝██╔══██╗██╔════╝
+// ██████╔╝██████╔╝██║██╔██╗ ██║   ██║   █████╗  ██████╔╝███████╗
+// ██╔═══╝ ██╔══██╗██║██║╚██╗██║   ██║   ██╔══╝  ██╔══██╗╚════██║
+// ██║     ██║  ██║██║██║ ╚████║   ██║   ███████╗██║  ██║███████║
+// ╚═╝     ╚═╝  ╚═╝╚═╝╚═╝  ╚═══╝   ╚═╝   ╚══════╝╚═╝  ╚═╝╚══════╝
+//                                 app/src/printer_calibration.rs
+
+use crate::coordinates::{PageFormat, PhysicalCoord};
+#[cfg(feature = "web")]
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "web")]
+const PRINTER_STORAGE_KEY: &str = "fl100_printer_calibrations";
+/// Distance of the corner crosshairs from the paper edges
+pub const TEST_SHEET_INSET_MM: f64 = 20.0;
+
+/// Affine map of page millimetres (origin top-left, y down):
+/// `x' = a·x + c·y + e`, `y' = b·x + d·y + f`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AffineTransform {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl AffineTransform {
+    pub const IDENTITY: AffineTransform =
+        AffineTransform { a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: 0.0, f: 0.0 };
+
+    pub fn apply(&self, point: PhysicalCoord) -> PhysicalCoord {
+        PhysicalCoord {
+            x: self.a * point.x + self.c * point.y + self.e,
+            y: self.b * point.x + self.d * point.y + self.f,
+        }
+    }
+
+    /// Inverse map, or `None` if the transform collapses the page
+    pub fn inverse(&self) -> Option<AffineTransform> {
+        let det = self.a * self.d - self.b * self.c;
+        if det.abs() < 1e-9 {
+            return None;
+        }
+        Some(AffineTransform {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            e: (self.c * self.f - self.d * self.e) / det,
+            f: (self.b * self.e - self.a * self.f) / det,
+        })
+    }
+}
+
+/// Correction for one printer: where to draw so that output lands where intended
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrinterCalibration {
+    pub name: String,
+    /// Maps intended page positions to the positions to draw at
+    pub correction: AffineTransform,
+    /// RMS distance between the measured crosshairs and the fitted model, in mm
+    pub residual_mm: f64,
+    pub timestamp: u64, // Unix timestamp (ms)
+}
+
+impl PrinterCalibration {
+    /// Fit a correction from crosshair offsets measured on a printed test sheet.
+    ///
+    /// `offsets[i]` is how far crosshair `i` landed from `targets[i]` (positive is right
+    /// and down). The printer's distortion is fitted as an affine map by least squares
+    /// and inverted.
+    pub fn from_offsets(
+        name: &str,
+        targets: &[PhysicalCoord],
+        offsets: &[PhysicalCoord],
+        timestamp: u64,
+    ) -> Result<Self, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Printer name cannot be empty".to_string());
+        }
+        if targets.len() != offsets.len() {
+            return Err(format!("Expected {} offsets, got {}", targets.len(), offsets.len()));
+        }
+        if targets.len() < 3 {
+            return Err("At least three crosshairs are needed".to_string());
+        }
+
+        let printed: Vec<PhysicalCoord> = targets
+            .iter()
+            .zip(offsets)
+            .map(|(target, offset)| PhysicalCoord {
+                x: target.x + offset.x,
+                y: target.y + offset.y,
+            })
+            .collect();
+        let xs: Vec<f64> = printed.iter().map(|p| p.x).collect();
+        let ys: Vec<f64> = printed.iter().map(|p| p.y).collect();
+        let ([a, c, e], [b, d, f]) = match (fit_plane(targets, &xs), fit_plane(targets, &ys)) {
+            (Some(x_terms), Some(y_terms)) => (x_terms, y_terms),
+            _ => return Err("Crosshairs must not lie on one line".to_string()),
+        };
+        let printer = AffineTransform { a, b, c, d, e, f };
+
+        let residual_mm = (targets
+            .iter()
+            .zip(&printed)
+            .map(|(target, actual)| {
+                let model = printer.apply(*target);
+                (model.x - actual.x).powi(2) + (model.y - actual.y).powi(2)
+            })
+            .sum::<f64>()
+            / targets.len() as f64)
+            .sqrt();
+
+        Ok(Self {
+            name: name.to_string(),
+            correction: printer.inverse().ok_or("Measured offsets collapse the page")?,
+            residual_mm,
+            timestamp,
+        })
+    }
+}
+
+/// Crosshair positions on the test sheet: the four corners inset from the edges,
+/// then the centre
+pub fn test_sheet_targets(format: &PageFormat) -> Vec<PhysicalCoord> {
+    let size = format.size_mm();
+    let (left, top) = (TEST_SHEET_INSET_MM, TEST_SHEET_INSET_MM);
+    let (right, bottom) = (size.x - TEST_SHEET_INSET_MM, size.y - TEST_SHEET_INSET_MM);
+    vec![
+        PhysicalCoord { x: left, y: top },
+        PhysicalCoord { x: right, y: top },
+        PhysicalCoord { x: left, y: bottom },
+        PhysicalCoord { x: right, y: bottom },
+        PhysicalCoord { x: size.x / 2.0, y: size.y / 2.0 },
+    ]
+}
+
+/// Smallest determinant of the normalised normal equations accepted as non-degenerate:
+/// points spread evenly over a square give 1/4, points on one line give 0
+const MIN_SPREAD: f64 = 1e-6;
+
+/// Least-squares `(u, v, w)` for `value ≈ u·x + v·y + w`, or `None` for (nearly)
+/// collinear points
+fn fit_plane(points: &[PhysicalCoord], values: &[f64]) -> Option<[f64; 3]> {
+    // Centre the points and scale them to unit RMS distance, so how degenerate they are
+    // doesn't depend on where they sit on the page or how far apart they are
+    let n = points.len() as f64;
+    let cx = points.iter().map(|p| p.x).sum::<f64>() / n;
+    let cy = points.iter().map(|p| p.y).sum::<f64>() / n;
+    let spread =
+        (points.iter().map(|p| (p.x - cx).powi(2) + (p.y - cy).powi(2)).sum::<f64>() / n).sqrt();
+    if spread == 0.0 || !spread.is_finite() {
+        return None;
+    }
+
+    // Normal equations: (Aᵀ·A)·p = Aᵀ·values with rows [x, y, 1]
+    let mut ata = [[0.0; 3]; 3];
+    let mut atb = [0.0; 3];
+    for (point, value) in points.iter().zip(values) {
+        let row = [(point.x - cx) / spread, (point.y - cy) / spread, 1.0];
+        for i in 0..3 {
+            for j in 0..3 {
+                ata[i][j] += row[i] * row[j];
+            }
+            atb[i] += row[i] * value;
+        }
+    }
+
+    // Each entry grows with the number of points; divide that out before comparing
+    let det = det3(&ata);
+    if det / n.powi(3) < MIN_SPREAD {
+        return None;
+    }
+    // Cramer's rule
+    let mut solution = [0.0; 3];
+    for (col, term) in solution.iter_mut().enumerate() {
+        let mut replaced = ata;
+        for (row, rhs) in replaced.iter_mut().zip(atb) {
+            row[col] = rhs;
+        }
+        *term = det3(&replaced) / det;
+    }
+
+    // Back from normalised coordinates
+    let [u, v, w] = solution;
+    let (u, v) = (u / spread, v / spread);
+    Some([u, v, w - u * cx - v * cy])
+}
+
+fn det3(m: &[[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+/// Calibrated printers and the one print output is corrected for
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PrinterSettings {
+    pub printers: Vec<PrinterCalibration>,
+    pub active: Option<String>,
+}
+
+impl PrinterSettings {
+    /// Correction for the selected printer, if any
+    pub fn active_correction(&self) -> Option<AffineTransform> {
+        let active = self.active.as_ref()?;
+        self.printers
+            .iter()
+            .find(|printer| &printer.name == active)
+            .map(|printer| printer.correction)
+    }
+
+    /// Add or replace a printer by name and select it
+    pub fn upsert(&mut self, calibration: PrinterCalibration) {
+        self.printers.retain(|printer| printer.name != calibration.name);
+        self.active = Some(calibration.name.clone());
+        self.printers.push(calibration);
+        self.printers.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    /// Forget a printer, clearing the selection if it was selected
+    pub fn remove(&mut self, name: &str) {
+        self.printers.retain(|printer| printer.name != name);
+        if self.active.as_deref() == Some(name) {
+            self.active = None;
+        }
+    }
+}
+
+/// Persists printer calibrations in localStorage
+#[cfg(feature = "web")]
+pub struct PrinterStore;
+
+#[cfg(feature = "web")]
+impl PrinterStore {
+    pub fn load() -> PrinterSettings {
+        LocalStorage::get(PRINTER_STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(settings: &PrinterSettings) -> Result<(), String> {
+        LocalStorage::set(PRINTER_STORAGE_KEY, settings)
+            .map_err(|e| format!("Failed to save printer calibrations: {:?}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: PhysicalCoord, b: PhysicalCoord) -> bool {
+        (a.x - b.x).abs() < 1e-6 && (a.y - b.y).abs() < 1e-6
+    }
+
+    #[test]
+    fn test_fit_recovers_printer_distortion() {
+        let targets = test_sheet_targets(&PageFormat::default());
+        assert_eq!(targets.len(), 5);
+        assert_eq!(targets[3], PhysicalCoord { x: 195.9, y: 259.4 });
+
+        // Shifted 1.2mm right and 0.8mm up, 0.5% short vertically, slightly rotated
+        let printer = AffineTransform { a: 1.0, b: 0.002, c: -0.002, d: 0.995, e: 1.2, f: -0.8 };
+        let offsets: Vec<PhysicalCoord> = targets
+            .iter()
+            .map(|t| {
+                let p = printer.apply(*t);
+                PhysicalCoord { x: p.x - t.x, y: p.y - t.y }
+            })
+            .collect();
+
+        let calibration =
+            PrinterCalibration::from_offsets(" Office ", &targets, &offsets, 7).unwrap();
+        assert_eq!(calibration.name, "Office");
+        assert!(calibration.residual_mm < 1e-9);
+
+        // Drawing at the corrected position puts ink where the form expects it
+        for target in &targets {
+            let printed = printer.apply(calibration.correction.apply(*target));
+            assert!(close(printed, *target), "{:?} printed at {:?}", target, printed);
+        }
+    }
+
+    #[test]
+    fn test_fit_rejects_bad_input() {
+        let targets = test_sheet_targets(&PageFormat::default());
+        let zero = vec![PhysicalCoord { x: 0.0, y: 0.0 }; targets.len()];
+        let identity = PrinterCalibration::from_offsets("Lab", &targets, &zero, 0).unwrap();
+        assert!(close(identity.correction.apply(targets[0]), targets[0]));
+
+        assert!(PrinterCalibration::from_offsets("", &targets, &zero, 0).is_err());
+        assert!(PrinterCalibration::from_offsets("Lab", &targets, &zero[..4], 0).is_err());
+
+        let line: Vec<PhysicalCoord> =
+            (0..3).map(|i| PhysicalCoord { x: i as f64, y: i as f64 }).collect();
+        assert!(PrinterCalibration::from_offsets("Lab", &line, &zero[..3], 0).is_err());
+
+        // Off a straight line by a hair across a whole page: large sums, still degenerate
+        let nearly_line = [
+            PhysicalCoord { x: 20.0, y: 20.0 },
+            PhysicalCoord { x: 195.9, y: 20.0 },
+            PhysicalCoord { x: 107.95, y: 20.0001 },
+        ];
+        assert!(PrinterCalibration::from_offsets("Lab", &nearly_line, &zero[..3], 0).is_err());
+
+        // A tiny but well-spread triangle is fine
+        let tiny = [
+            PhysicalCoord { x: 0.0, y: 0.0 },
+            PhysicalCoord { x: 0.01, y: 0.0 },
+            PhysicalCoord { x: 0.0, y: 0.01 },
+        ];
+        assert!(PrinterCalibration::from_offsets("Lab", &tiny, &zero[..3], 0).is_ok());
+    }
+
+    #[test]
+    fn test_settings_selection() {
+        let targets = test_sheet_targets(&PageFormat::default());
+        let shift = vec![PhysicalCoord { x: 1.0, y: 0.0 }; targets.len()];
+        let mut settings = PrinterSettings::default();
+        assert_eq!(settings.active_correction(), None);
+
+        settings.upsert(PrinterCalibration::from_offsets("Office", &targets, &shift, 1).unwrap());
+        let correction = settings.active_correction().unwrap();
+        assert!(close(
+            correction.apply(PhysicalCoord { x: 10.0, y: 10.0 }),
+            PhysicalCoord { x: 9.0, y: 10.0 }
+        ));
+
+        settings.remove("Office");
+        assert!(settings.printers.is_empty());
+        assert_eq!(settings.active, None);
+    }
+}
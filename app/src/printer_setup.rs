@@ -0,0 +1,273 @@
+//
+// ██████╗ ██████╗ ██╗███╗   ██╗████████╗███████╗██████╗    ███████╗███████╗████████╗██╗   ██╗██████╗
+// ██╔══██╗██╔══██╗██║████╗  ██║╚══██╔══╝██╔════╝██╔══██╗   ██╔════╝██╔════╝╚══██╔══╝██║   ██║██╔══██╗
+// ██████╔╝██████╔╝██║██╔██╗ ██║   ██║   █████╗  ██████╔╝   ███████╗█████╗     ██║   ██║   ██║██████╔╝
+// ██╔═══╝ ██╔══██╗██║██║╚██╗██║   ██║   ██╔══╝  ██╔══██╗   ╚════██║██╔══╝     ██║   ██║   ██║██╔═══╝
+// ██║     ██║  ██║██║██║ ╚████║   ██║   ███████╗██║  ██║   ███████║███████╗   ██║   ╚██████╔╝██║
+// ╚═╝     ╚═╝  ╚═╝╚═╝╚═╝  ╚═══╝   ╚═╝   ╚══════╝╚═╝  ╚═╝   ╚══════╝╚══════╝   ╚═╝    ╚═════╝ ╚═╝
+//                                                                            app/src/printer_setup.rs
+
+use crate::coordinates::{PageFormat, PhysicalCoord};
+use crate::file_io::download_bytes;
+use crate::pdf_export::export_printer_test_sheet;
+use crate::printer_calibration::{
+    test_sheet_targets, PrinterCalibration, PrinterSettings, PrinterStore,
+};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct PrinterSetupProps {
+    /// Paper the test sheet is printed on
+    pub format: PageFormat,
+    pub on_close: Callback<()>,
+}
+
+/// Panel for calibrating printers from a measured test sheet and choosing the
+/// one PDF export and printing correct for
+pub struct PrinterSetup {
+    settings: PrinterSettings,
+    targets: Vec<PhysicalCoord>,
+    printer_name: String,
+    /// Entered (dx, dy) per crosshair, kept as typed
+    offsets: Vec<(String, String)>,
+    status: Option<Result<String, String>>,
+}
+
+pub enum PrinterSetupMsg {
+    SelectPrinter(Option<String>),
+    ForgetPrinter(String),
+    DownloadTestSheet,
+    SetName(String),
+    SetOffset(usize, bool, String),
+    SavePrinter,
+}
+
+impl Component for PrinterSetup {
+    type Message = PrinterSetupMsg;
+    type Properties = PrinterSetupProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let targets = test_sheet_targets(&ctx.props().format);
+        Self {
+            settings: PrinterStore::load(),
+            offsets: vec![(String::new(), String::new()); targets.len()],
+            targets,
+            printer_name: String::new(),
+            status: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            PrinterSetupMsg::SelectPrinter(name) => {
+                self.settings.active = name;
+                self.save_settings();
+            }
+            PrinterSetupMsg::ForgetPrinter(name) => {
+                self.settings.remove(&name);
+                self.save_settings();
+            }
+            PrinterSetupMsg::DownloadTestSheet => {
+                let pdf = export_printer_test_sheet(&ctx.props().format, &self.targets);
+                self.status = Some(
+                    download_bytes("printer-test-sheet.pdf", "application/pdf", &pdf)
+                        .map(|()| "Print the sheet at 100% and measure each crosshair".to_string()),
+                );
+            }
+            PrinterSetupMsg::SetName(name) => {
+                self.printer_name = name;
+                return false;
+            }
+            PrinterSetupMsg::SetOffset(idx, vertical, value) => {
+                if let Some((dx, dy)) = self.offsets.get_mut(idx) {
+                    *if vertical { dy } else { dx } = value;
+                }
+                return false;
+            }
+            PrinterSetupMsg::SavePrinter => {
+                self.status = Some(self.save_printer());
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div class="printer-setup">
+                <div class="printer-setup-header">
+                    <h3>{"Printer calibration"}</h3>
+                    <button
+                        class="nav-button"
+                        onclick={ctx.props().on_close.reform(|_| ())}
+                    >
+                        {"Close"}
+                    </button>
+                </div>
+
+                <label class="printer-select">
+                    {"Correct PDF output for "}
+                    <select onchange={ctx.link().callback(|e: Event| {
+                        let select: HtmlSelectElement = e.target_unchecked_into();
+                        let value = select.value();
+                        PrinterSetupMsg::SelectPrinter((!value.is_empty()).then_some(value))
+                    })}>
+                        <option value="" selected={self.settings.active.is_none()}>
+                            {"No printer (uncorrected)"}
+                        </option>
+                        { for self.settings.printers.iter().map(|printer| html! {
+                            <option
+                                value={printer.name.clone()}
+                                selected={self.settings.active.as_ref() == Some(&printer.name)}
+                            >
+                                {&printer.name}
+                            </option>
+                        })}
+                    </select>
+                </label>
+
+                { if self.settings.printers.is_empty() { html! {} } else { html! {
+                    <ul class="printer-list">
+                        { for self.settings.printers.iter().map(|printer| {
+                            let name = printer.name.clone();
+                            html! {
+                                <li class="printer-entry">
+                                    <span class="printer-name">{&printer.name}</span>
+                                    <span class="printer-meta">
+                                        {format!("±{:.2} mm residual", printer.residual_mm)}
+                                    </span>
+                                    <button
+                                        class="nav-button"
+                                        onclick={ctx.link().callback(move |_| PrinterSetupMsg::ForgetPrinter(name.clone()))}
+                                    >
+                                        {"Forget"}
+                                    </button>
+                                </li>
+                            }
+                        })}
+                    </ul>
+                }}}
+
+                <ol class="printer-steps">
+                    <li>
+                        <button
+                            class="nav-button"
+                            onclick={ctx.link().callback(|_| PrinterSetupMsg::DownloadTestSheet)}
+                        >
+                            {"Download test sheet"}
+                        </button>
+                        {" and print it at actual size (100%)."}
+                    </li>
+                    <li>
+                        {"For each crosshair, enter how far its centre landed from the labelled \
+                          position, in mm (positive is right and down)."}
+                        <table class="printer-offsets">
+                            <thead>
+                                <tr><th>{"#"}</th><th>{"Target"}</th><th>{"Δx"}</th><th>{"Δy"}</th></tr>
+                            </thead>
+                            <tbody>
+                                { for self.targets.iter().zip(&self.offsets).enumerate().map(|(idx, (target, (dx, dy)))| html! {
+                                    <tr>
+                                        <td>{idx + 1}</td>
+                                        <td>{format!("{:.1}, {:.1} mm", target.x, target.y)}</td>
+                                        <td>{ self.render_offset_input(ctx, idx, false, dx) }</td>
+                                        <td>{ self.render_offset_input(ctx, idx, true, dy) }</td>
+                                    </tr>
+                                })}
+                            </tbody>
+                        </table>
+                    </li>
+                    <li>
+                        <input
+                            type="text"
+                            placeholder="Printer name"
+                            value={self.printer_name.clone()}
+                            oninput={ctx.link().callback(|e: InputEvent| {
+                                let input: HtmlInputElement = e.target_unchecked_into();
+                                PrinterSetupMsg::SetName(input.value())
+                            })}
+                        />
+                        <button
+                            class="nav-button"
+                            onclick={ctx.link().callback(|_| PrinterSetupMsg::SavePrinter)}
+                        >
+                            {"Save printer"}
+                        </button>
+                    </li>
+                </ol>
+
+                { match &self.status {
+                    Some(Ok(message)) => html! { <span class="data-status status-good">{message}</span> },
+                    Some(Err(error)) => html! { <span class="data-status status-warning">{error}</span> },
+                    None => html! {},
+                }}
+            </div>
+        }
+    }
+}
+
+impl PrinterSetup {
+    fn render_offset_input(
+        &self,
+        ctx: &Context<Self>,
+        idx: usize,
+        vertical: bool,
+        value: &str,
+    ) -> Html {
+        html! {
+            <input
+                type="number"
+                step="0.1"
+                placeholder="0"
+                value={value.to_string()}
+                oninput={ctx.link().callback(move |e: InputEvent| {
+                    let input: HtmlInputElement = e.target_unchecked_into();
+                    PrinterSetupMsg::SetOffset(idx, vertical, input.value())
+                })}
+            />
+        }
+    }
+
+    /// Fit the entered offsets and store the printer as the active one
+    fn save_printer(&mut self) -> Result<String, String> {
+        let offsets = self
+            .offsets
+            .iter()
+            .enumerate()
+            .map(|(idx, (dx, dy))| {
+                Ok(PhysicalCoord { x: parse_offset(idx, dx)?, y: parse_offset(idx, dy)? })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let calibration = PrinterCalibration::from_offsets(
+            &self.printer_name,
+            &self.targets,
+            &offsets,
+            js_sys::Date::now() as u64,
+        )?;
+        let message =
+            format!("Saved {} (±{:.2} mm residual)", calibration.name, calibration.residual_mm);
+        self.settings.upsert(calibration);
+        PrinterStore::save(&self.settings)?;
+        Ok(message)
+    }
+
+    fn save_settings(&mut self) {
+        if let Err(error) = PrinterStore::save(&self.settings) {
+            self.status = Some(Err(error));
+        }
+    }
+}
+
+/// Offset in mm; a blank field means the crosshair printed on target
+fn parse_offset(idx: usize, value: &str) -> Result<f64, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(0.0);
+    }
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|offset| offset.is_finite())
+        .ok_or_else(|| format!("Crosshair {}: '{}' is not a number", idx + 1, value))
+}
@@ -8,12 +8,15 @@
 //                                                     app/src/proofing_ui.rs
 
 use crate::calibration::CalibrationManager;
-use crate::coordinates::{PhysicalCoord, US_LETTER_WIDTH_MM, US_LETTER_HEIGHT_MM};
+use crate::coordinates::PageFormat;
+use crate::form_template::{FormFieldInfo, FormTemplate};
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
 pub struct ProofingUIProps {
     pub calibration_manager: CalibrationManager,
+    #[prop_or_else(FormTemplate::fl100)]
+    pub template: FormTemplate,
 }
 
 
@@ -77,8 +80,20 @@ impl Component for ProofingUI {
         }
     }
 
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().template != old_props.template {
+            let pages = ctx.props().template.pages.len();
+            self.current_page = self.current_page.min(pages.saturating_sub(1));
+        }
+        true
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         let coord_system = ctx.props().calibration_manager.get_coordinate_system();
+        let format = ctx.props().template.pages
+            .get(self.current_page)
+            .map(|page| page.format)
+            .unwrap_or_default();
         
         html! {
             <div class="proofing-ui">
@@ -86,7 +101,7 @@ impl Component for ProofingUI {
                 
                 <div class="proofing-workspace">
                     { if self.show_rulers {
-                        self.render_rulers(coord_system)
+                        self.render_rulers(coord_system, &format)
                     } else { html! {} }}
                     
                     <div 
@@ -97,7 +112,7 @@ impl Component for ProofingUI {
                         { self.render_page(ctx, coord_system) }
                         
                         { if self.show_grid {
-                            self.render_grid(coord_system)
+                            self.render_grid(coord_system, &format)
                         } else { html! {} }}
                     </div>
                 </div>
@@ -115,7 +130,7 @@ impl ProofingUI {
                 <div class="toolbar-section">
                     <h3>{"Pages"}</h3>
                     <div class="page-controls">
-                        { for (0..3).map(|i| {
+                        { for (0..ctx.props().template.pages.len()).map(|i| {
                             let is_active = i == self.current_page;
                             let set_page = ctx.link().callback(move |_| ProofingMsg::SetPage(i));
                             html! {
@@ -174,15 +189,12 @@ impl ProofingUI {
         }
     }
 
-    fn render_rulers(&self, coord_system: Option<&crate::coordinates::CoordinateSystem>) -> Html {
+    fn render_rulers(&self, coord_system: Option<&crate::coordinates::CoordinateSystem>, format: &PageFormat) -> Html {
         if let Some(coord_sys) = coord_system {
-            let width_px = coord_sys.mm_to_px(US_LETTER_WIDTH_MM);
-            let height_px = coord_sys.mm_to_px(US_LETTER_HEIGHT_MM);
-            
             html! {
                 <div class="rulers">
-                    { self.render_horizontal_ruler(coord_sys, width_px) }
-                    { self.render_vertical_ruler(coord_sys, height_px) }
+                    { self.render_horizontal_ruler(coord_sys, format.width_mm()) }
+                    { self.render_vertical_ruler(coord_sys, format.height_mm()) }
                 </div>
             }
         } else {
@@ -194,10 +206,11 @@ impl ProofingUI {
         }
     }
 
-    fn render_horizontal_ruler(&self, coord_sys: &crate::coordinates::CoordinateSystem, width_px: f64) -> Html {
-        let major_marks = (0..=(US_LETTER_WIDTH_MM as u32 / 10)).map(|cm| {
+    fn render_horizontal_ruler(&self, coord_sys: &crate::coordinates::CoordinateSystem, width_mm: f64) -> Html {
+        let width_px = coord_sys.mm_to_px_x(width_mm);
+        let major_marks = (0..=(width_mm as u32 / 10)).map(|cm| {
             let mm = cm as f64 * 10.0;
-            let px = coord_sys.mm_to_px(mm);
+            let px = coord_sys.mm_to_px_x(mm);
             html! {
                 <div 
                     class="ruler-mark major"
@@ -215,10 +228,11 @@ impl ProofingUI {
         }
     }
 
-    fn render_vertical_ruler(&self, coord_sys: &crate::coordinates::CoordinateSystem, height_px: f64) -> Html {
-        let major_marks = (0..=(US_LETTER_HEIGHT_MM as u32 / 10)).map(|cm| {
+    fn render_vertical_ruler(&self, coord_sys: &crate::coordinates::CoordinateSystem, height_mm: f64) -> Html {
+        let height_px = coord_sys.mm_to_px_y(height_mm);
+        let major_marks = (0..=(height_mm as u32 / 10)).map(|cm| {
             let mm = cm as f64 * 10.0;
-            let px = coord_sys.mm_to_px(mm);
+            let px = coord_sys.mm_to_px_y(mm);
             html! {
                 <div 
                     class="ruler-mark major"
@@ -236,10 +250,12 @@ impl ProofingUI {
         }
     }
 
-    fn render_page(&self, _ctx: &Context<Self>, coord_system: Option<&crate::coordinates::CoordinateSystem>) -> Html {
-        if let Some(coord_sys) = coord_system {
-            let width_px = coord_sys.mm_to_px(US_LETTER_WIDTH_MM);
-            let height_px = coord_sys.mm_to_px(US_LETTER_HEIGHT_MM);
+    fn render_page(&self, ctx: &Context<Self>, coord_system: Option<&crate::coordinates::CoordinateSystem>) -> Html {
+        let template = &ctx.props().template;
+
+        if let (Some(coord_sys), Some(page)) = (coord_system, template.pages.get(self.current_page)) {
+            let width_px = coord_sys.mm_to_px_x(page.format.width_mm());
+            let height_px = coord_sys.mm_to_px_y(page.format.height_mm());
             
             html! {
                 <div 
@@ -247,15 +263,15 @@ impl ProofingUI {
                     style={format!("width: {}px; height: {}px", width_px, height_px)}
                 >
                     <div class="page-header">
-                        {format!("FL-100 Form - Page {}", self.current_page + 1)}
+                        {&page.title}
                     </div>
                     
                     <div class="form-content">
-                        { self.render_form_fields(coord_sys) }
+                        { self.render_form_fields(coord_sys, &page.fields) }
                     </div>
                     
                     <div class="page-footer">
-                        {"California Judicial Council Form FL-100"}
+                        {format!("California Judicial Council Form {}", template.form_id)}
                     </div>
                 </div>
             }
@@ -271,29 +287,22 @@ impl ProofingUI {
         }
     }
 
-    fn render_form_fields(&self, coord_sys: &crate::coordinates::CoordinateSystem) -> Html {
-        // Sample form fields with physical coordinates
-        let sample_fields = vec![
-            ("petitioner_name", PhysicalCoord { x: 25.0, y: 45.0 }, "Petitioner Name"),
-            ("respondent_name", PhysicalCoord { x: 25.0, y: 75.0 }, "Respondent Name"),
-            ("case_number", PhysicalCoord { x: 150.0, y: 25.0 }, "Case Number"),
-            ("county", PhysicalCoord { x: 25.0, y: 105.0 }, "County"),
-        ];
-
-        let field_elements = sample_fields.iter().map(|(id, coord, label)| {
-            let screen_coord = coord_sys.physical_to_screen(*coord);
+    fn render_form_fields(&self, coord_sys: &crate::coordinates::CoordinateSystem, fields: &[FormFieldInfo]) -> Html {
+        let field_elements = fields.iter().map(|field| {
+            let screen_coord = coord_sys.physical_to_screen(field.position);
             html! {
                 <div 
                     class="form-field"
-                    id={id.to_string()}
+                    id={field.id.clone()}
                     style={format!(
-                        "position: absolute; left: {}px; top: {}px; width: 120px; height: 20px",
-                        screen_coord.x, screen_coord.y
+                        "position: absolute; left: {}px; top: {}px; width: {}px; height: {}px",
+                        screen_coord.x, screen_coord.y,
+                        coord_sys.mm_to_px_x(field.size.x), coord_sys.mm_to_px_y(field.size.y)
                     )}
                 >
                     <input 
                         type="text" 
-                        placeholder={label.to_string()}
+                        placeholder={field.label.clone()}
                         class="field-input"
                     />
                     <div class="field-overlay"></div>
@@ -308,19 +317,20 @@ impl ProofingUI {
         }
     }
 
-    fn render_grid(&self, coord_system: Option<&crate::coordinates::CoordinateSystem>) -> Html {
+    fn render_grid(&self, coord_system: Option<&crate::coordinates::CoordinateSystem>, format: &PageFormat) -> Html {
         if let Some(coord_sys) = coord_system {
             let grid_size_mm = 5.0; // 5mm grid
-            let width_px = coord_sys.mm_to_px(US_LETTER_WIDTH_MM);
-            let height_px = coord_sys.mm_to_px(US_LETTER_HEIGHT_MM);
-            let grid_size_px = coord_sys.mm_to_px(grid_size_mm);
+            let width_px = coord_sys.mm_to_px_x(format.width_mm());
+            let height_px = coord_sys.mm_to_px_y(format.height_mm());
+            let grid_x_px = coord_sys.mm_to_px_x(grid_size_mm);
+            let grid_y_px = coord_sys.mm_to_px_y(grid_size_mm);
 
             html! {
                 <div 
                     class="grid-overlay"
                     style={format!(
                         "background-size: {}px {}px; width: {}px; height: {}px",
-                        grid_size_px, grid_size_px, width_px, height_px
+                        grid_x_px, grid_y_px, width_px, height_px
                     )}
                 ></div>
             }
@@ -346,14 +356,14 @@ impl ProofingUI {
                     html! {
                         <div class="status-section">
                             <span class="status-label">{"DPI:"}</span>
-                            <span class="status-value">{format!("{:.1}", calibration.scale_factor * 25.4)}</span>
+                            <span class="status-value">{format!("{:.1} × {:.1}", calibration.scale_x * 25.4, calibration.scale_y * 25.4)}</span>
                         </div>
                     }
                 } else { html! {} }}
                 
                 <div class="status-section">
                     <span class="status-label">{"Page:"}</span>
-                    <span class="status-value">{format!("{} of 3", self.current_page + 1)}</span>
+                    <span class="status-value">{format!("{} of {}", self.current_page + 1, ctx.props().template.pages.len())}</span>
                 </div>
                 
                 <div class="status-section">
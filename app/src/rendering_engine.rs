@@ -9,6 +9,7 @@
 
 use crate::coordinates::{PhysicalCoord, ScreenCoord};
 use crate::calibration::CalibrationManager;
+use crate::coordinates::DeviceCalibration;
 use crate::font_metrics::{FontMetricsCalculator, StandardFont, TextPosition};
 use std::rc::Rc;
 use std::cell::RefCell;
@@ -19,31 +20,46 @@ pub struct RenderingEngine {
     font_calculator: Rc<RefCell<FontMetricsCalculator>>,
 }
 
+#[cfg(feature = "web")]
+impl Default for RenderingEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "web")]
 impl RenderingEngine {
-    /// Create a new rendering engine
+    /// Create a new rendering engine with the current display's stored calibration
     pub fn new() -> Self {
+        Self::with_manager(CalibrationManager::new())
+    }
+}
+
+impl RenderingEngine {
+    /// Rendering engine for a known calibration, without touching storage
+    pub fn with_calibration(calibration: DeviceCalibration) -> Self {
+        Self::with_manager(CalibrationManager::with_calibration(calibration))
+    }
+
+    fn with_manager(calibration_manager: CalibrationManager) -> Self {
         Self {
-            calibration_manager: CalibrationManager::new(),
+            calibration_manager,
             font_calculator: Rc::new(RefCell::new(FontMetricsCalculator::new())),
         }
     }
 
     /// Convert physical coordinates to screen coordinates using calibration data
     pub fn physical_to_screen(&self, physical_coord: PhysicalCoord) -> Option<ScreenCoord> {
-        if let Some(coord_system) = self.calibration_manager.get_coordinate_system() {
-            Some(coord_system.physical_to_screen(physical_coord))
-        } else {
-            None
-        }
+        self.calibration_manager
+            .get_coordinate_system()
+            .map(|coord_system| coord_system.physical_to_screen(physical_coord))
     }
 
-    /// Get the current calibration scale factor
+    /// Get the current mean calibration scale factor
     pub fn get_scale_factor(&self) -> Option<f64> {
-        if let Some(coord_system) = self.calibration_manager.get_coordinate_system() {
-            Some(coord_system.get_calibration().scale_factor)
-        } else {
-            None
-        }
+        self.calibration_manager
+            .get_coordinate_system()
+            .map(|coord_system| coord_system.get_calibration().scale_factor())
     }
 
     /// Check if the device is calibrated
@@ -66,7 +82,7 @@ impl RenderingEngine {
                     font_size_pt,
                     field_position,
                     field_height_mm,
-                    &coord_system,
+                    coord_system,
                 )
             } else {
                 None
@@ -90,7 +106,8 @@ impl RenderingEngine {
         self.font_calculator.clone()
     }
 
-    /// Validate that text will fit within specified field bounds
+    /// Validate that text will fit within specified field bounds. Both are physical
+    /// sizes, so the display's calibration doesn't come into it.
     pub fn validate_text_fit(
         &self,
         text: &str,
@@ -99,30 +116,39 @@ impl RenderingEngine {
         field_width_mm: f64,
         field_height_mm: f64,
     ) -> bool {
-        if let Some(coord_system) = self.calibration_manager.get_coordinate_system() {
-            if let Ok(mut calculator) = self.font_calculator.try_borrow_mut() {
-                if let Some(metrics) = calculator.measure_font(font, font_size_pt) {
-                    // Convert field dimensions to pixels
-                    let field_width_px = coord_system.mm_to_px(field_width_mm);
-                    let field_height_px = coord_system.mm_to_px(field_height_mm);
-                    
-                    // Estimate text width
-                    let text_width = if let Some(char_width) = metrics.char_width_px {
-                        text.len() as f64 * char_width
-                    } else {
-                        text.len() as f64 * metrics.font_size_px * 0.6 // Approximation for proportional fonts
-                    };
-                    
-                    // Check if text fits within bounds
-                    text_width <= field_width_px && metrics.line_height_px <= field_height_px
-                } else {
-                    false
-                }
-            } else {
-                false
+        match self.font_calculator.try_borrow_mut() {
+            Ok(mut calculator) => {
+                calculator.text_fits(&font, font_size_pt, text, field_width_mm, field_height_mm)
             }
-        } else {
-            false
+            Err(_) => false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headless_layout() {
+        let calibration = DeviceCalibration { scale_x: 4.0, scale_y: 4.0, ..Default::default() };
+        let engine = RenderingEngine::with_calibration(calibration);
+        assert!(engine.is_calibrated());
+
+        let position = PhysicalCoord { x: 25.0, y: 45.0 };
+        let screen = engine.physical_to_screen(position).unwrap();
+        assert_eq!((screen.x, screen.y), (100.0, 180.0));
+        let text = engine
+            .calculate_text_position(StandardFont::Helvetica, 12.0, position, 10.0)
+            .unwrap();
+        assert_eq!(text.baseline_x, 100.0);
+        assert!(text.baseline_y > 180.0 + 20.0);
+
+        // "Jane Smith" at 12pt is about 22mm wide in Helvetica
+        let fits = |width_mm| {
+            engine.validate_text_fit("Jane Smith", StandardFont::Helvetica, 12.0, width_mm, 10.0)
+        };
+        assert!(fits(30.0));
+        assert!(!fits(15.0));
+    }
+}
@@ -9,6 +9,8 @@
 
 use yew::prelude::*;
 use crate::coordinates::PhysicalCoord;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
 
 #[derive(Properties, PartialEq)]
 pub struct SignatureProps {
@@ -17,14 +19,15 @@ pub struct SignatureProps {
     pub position: PhysicalCoord,
     pub width: f64,
     pub height: f64,
-    pub on_sign: Callback<String>, // Base64 encoded signature data
+    pub on_sign: Callback<String>, // PNG data URL of the signature
     pub signature_data: Option<String>,
 }
 
 pub struct Signature {
-    node_ref: NodeRef,
     canvas_ref: NodeRef,
     is_drawing: bool,
+    /// Whether the canvas holds strokes drawn since mount
+    has_strokes: bool,
 }
 
 pub enum SignatureMsg {
@@ -34,43 +37,72 @@ pub enum SignatureMsg {
     Clear,
 }
 
+impl Signature {
+    /// 2D context of the drawing canvas, if it is mounted
+    fn drawing_context(&self) -> Option<CanvasRenderingContext2d> {
+        self.canvas_ref
+            .cast::<HtmlCanvasElement>()?
+            .get_context("2d")
+            .ok()??
+            .dyn_into::<CanvasRenderingContext2d>()
+            .ok()
+    }
+}
+
 impl Component for Signature {
     type Message = SignatureMsg;
     type Properties = SignatureProps;
 
     fn create(_ctx: &Context<Self>) -> Self {
         Self {
-            node_ref: NodeRef::default(),
             canvas_ref: NodeRef::default(),
             is_drawing: false,
+            has_strokes: false,
         }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             SignatureMsg::StartDrawing(e) => {
-                self.is_drawing = true;
-                // In a real implementation, we would capture the starting point here
-                // For now, we'll just acknowledge the event
+                if let Some(context) = self.drawing_context() {
+                    context.begin_path();
+                    context.move_to(e.offset_x() as f64, e.offset_y() as f64);
+                    self.is_drawing = true;
+                    self.has_strokes = true;
+                }
                 e.prevent_default();
-                true
+                false
             }
             SignatureMsg::Draw(e) => {
                 if self.is_drawing {
-                    // In a real implementation, we would draw on the canvas here
-                    // For now, we'll just acknowledge the event
+                    if let Some(context) = self.drawing_context() {
+                        context.line_to(e.offset_x() as f64, e.offset_y() as f64);
+                        context.stroke();
+                    }
                     e.prevent_default();
                 }
-                true
+                false
             }
             SignatureMsg::StopDrawing => {
+                if !self.is_drawing {
+                    return false;
+                }
                 self.is_drawing = false;
-                // In a real implementation, we would capture the signature data here
-                // and emit it via the on_sign callback
-                true
+
+                // Hand the finished stroke to the form data store
+                if let Some(canvas) = self.canvas_ref.cast::<HtmlCanvasElement>() {
+                    if let Ok(data_url) = canvas.to_data_url() {
+                        ctx.props().on_sign.emit(data_url);
+                    }
+                }
+                false
             }
             SignatureMsg::Clear => {
-                // In a real implementation, we would clear the canvas here
+                if let (Some(canvas), Some(context)) =
+                    (self.canvas_ref.cast::<HtmlCanvasElement>(), self.drawing_context()) {
+                    context.clear_rect(0.0, 0.0, canvas.width() as f64, canvas.height() as f64);
+                }
+                self.has_strokes = false;
                 ctx.props().on_sign.emit(String::new());
                 true
             }
@@ -90,16 +122,31 @@ impl Component for Signature {
         html! {
             <div class="form-field signature-field" style={style}>
                 <label for={ctx.props().id.clone()}>{ctx.props().label.clone()}</label>
-                <canvas
-                    ref={self.canvas_ref.clone()}
-                    id={ctx.props().id.clone()}
-                    width={width.to_string()}
-                    height={height.to_string()}
-                    onmousedown={ctx.link().callback(|e: MouseEvent| SignatureMsg::StartDrawing(e))}
-                    onmousemove={ctx.link().callback(|e: MouseEvent| SignatureMsg::Draw(e))}
-                    onmouseup={ctx.link().callback(|_| SignatureMsg::StopDrawing)}
-                    onmouseleave={ctx.link().callback(|_| SignatureMsg::StopDrawing)}
-                />
+                { if let Some(data_url) = ctx.props().signature_data.as_ref().filter(|d| !d.is_empty() && !self.has_strokes) {
+                    // Stored signature, e.g. restored data; Clear returns to the drawing canvas
+                    html! {
+                        <img
+                            class="signature-image"
+                            src={data_url.clone()}
+                            alt={ctx.props().label.clone()}
+                            width={width.to_string()}
+                            height={height.to_string()}
+                        />
+                    }
+                } else {
+                    html! {
+                        <canvas
+                            ref={self.canvas_ref.clone()}
+                            id={ctx.props().id.clone()}
+                            width={width.to_string()}
+                            height={height.to_string()}
+                            onmousedown={ctx.link().callback(|e: MouseEvent| SignatureMsg::StartDrawing(e))}
+                            onmousemove={ctx.link().callback(|e: MouseEvent| SignatureMsg::Draw(e))}
+                            onmouseup={ctx.link().callback(|_| SignatureMsg::StopDrawing)}
+                            onmouseleave={ctx.link().callback(|_| SignatureMsg::StopDrawing)}
+                        />
+                    }
+                }}
                 <button onclick={ctx.link().callback(|_| SignatureMsg::Clear)}>
                     {"Clear"}
                 </button>
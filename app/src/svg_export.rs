@@ -0,0 +1,359 @@
+//
+// ███████╗██╗   ██╗ ██████╗    ███████╗██╗  ██╗██████╗  ██████╗ ██████╗ ████████╗
+// ██╔════╝██║   ██║██╔════╝    ██╔════╝╚██╗██╔╝██╔══██╗██╔═══██╗██╔══██╗╚══██╔══╝
+// ███████╗██║   ██║██║  ███╗   █████╗   ╚███╔╝ ██████╔╝██║   ██║██████╔╝   ██║
+// ╚════██║╚██╗ ██╔╝██║   ██║   ██╔══╝   ██╔██╗ ██╔═══╝ ██║   ██║██╔══██╗   ██║
+// ███████║ ╚████╔╝ ╚██████╔╝   ███████╗██╔╝ ██╗██║     ╚██████╔╝██║  ██║   ██║
+// ╚══════╝  ╚═══╝   ╚═════╝    ╚══════╝╚═╝  ╚═╝╚═╝      ╚═════╝ ╚═╝  ╚═╝   ╚═╝
+//                                                           app/src/svg_export.rs
+
+use crate::comb::CombSettings;
+use crate::coordinates::{CoordinateSystem, ScreenCoord};
+use crate::font_metrics::{FontMetricsCalculator, StandardFont};
+use crate::form_data::{format_us_date, FieldValue, FormData};
+use crate::form_template::{FieldType, FormFieldInfo, FormTemplate};
+use crate::overlay::{Overlay, OverlayType};
+use crate::pdf_export::{
+    field_font_size, num, ExportOptions, CHECKBOX_LABEL_GAP_MM, CHECKBOX_SIZE_MM,
+};
+use crate::text_layout::{layout_multiline, LineSettings};
+use crate::xml::escape_xml;
+
+/// Millimetres per typographic point
+const MM_PER_PT: f64 = 25.4 / 72.0;
+
+/// Render one page as a standalone SVG whose user units are millimetres.
+///
+/// Values sit on the same baselines as in PDF export, from the font's AFM ascent and
+/// descent; pass a calculator built with `with_builtin_metrics` so auto-shrunk sizes are
+/// identical on every machine. Overlays
+/// keep their CSS transform, applied in a pixel-space group scaled to millimetres.
+pub fn export_page_svg(
+    template: &FormTemplate,
+    page_index: usize,
+    form_data: &FormData,
+    overlays: &[Overlay],
+    coord_system: &CoordinateSystem,
+    metrics: &mut FontMetricsCalculator,
+    options: &ExportOptions,
+) -> Result<String, String> {
+    let page = template
+        .pages
+        .get(page_index)
+        .ok_or_else(|| format!("{} has no page {}", template.form_id, page_index + 1))?;
+
+    let mut svg = String::new();
+    svg.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    svg.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" \
+         width=\"{w}mm\" height=\"{h}mm\" viewBox=\"0 0 {w} {h}\">\n",
+        w = num(page.format.width_mm()),
+        h = num(page.format.height_mm())
+    ));
+    svg.push_str(&format!(
+        "  <title>{}</title>\n",
+        escape_xml(&format!("{} (Rev. {}) - {}", template.form_id, template.revision, page.title))
+    ));
+    svg.push_str(&format!(
+        "  <rect class=\"page\" width=\"{}\" height=\"{}\" fill=\"#fff\"/>\n",
+        num(page.format.width_mm()),
+        num(page.format.height_mm())
+    ));
+
+    svg.push_str(&format!(
+        "  <g class=\"fields\" font-family=\"{}\" font-weight=\"{}\" fill=\"#000\">\n",
+        escape_xml(options.font.css_family()),
+        options.font.css_weight()
+    ));
+    for field in &page.fields {
+        render_field(&mut svg, field, form_data.get(&field.id), metrics, options);
+    }
+    svg.push_str("  </g>\n");
+
+    let mut page_overlays: Vec<&Overlay> =
+        overlays.iter().filter(|o| o.page_index == page_index && o.visible).collect();
+    page_overlays.sort_by_key(|o| o.z_index);
+    if !page_overlays.is_empty() {
+        svg.push_str("  <g class=\"overlays\">\n");
+        for overlay in page_overlays {
+            render_overlay(&mut svg, overlay, coord_system);
+        }
+        svg.push_str("  </g>\n");
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+fn render_field(
+    svg: &mut String,
+    field: &FormFieldInfo,
+    value: Option<&FieldValue>,
+    metrics: &mut FontMetricsCalculator,
+    options: &ExportOptions,
+) {
+    let x = field.position.x;
+    let y = field.position.y;
+    let label_size = options.label_size_pt * MM_PER_PT;
+    let label_baseline = y - label_size * 0.3;
+    let field_id = escape_xml(&field.id);
+
+    match field.field_type {
+        FieldType::TextInput | FieldType::Date => {
+            push_label(svg, x, label_baseline, label_size, &field.label);
+
+            let text = match value {
+                Some(FieldValue::Text(text)) => text.clone(),
+                Some(FieldValue::Date(date)) => format_us_date(date),
+                _ => String::new(),
+            };
+            if text.is_empty() {
+                return;
+            }
+
+            let size_pt = field_font_size(metrics, field, &text, options);
+            let baseline = y + options.font.centred_baseline_mm(size_pt, field.size.y);
+
+            svg.push_str(&format!(
+                "    <clipPath id=\"clip-{id}\"><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/></clipPath>\n",
+                num(x),
+                num(y),
+                num(field.size.x),
+                num(field.size.y),
+                id = field_id
+            ));
+            svg.push_str(&format!(
+                "    <text class=\"field-value\" data-field=\"{id}\" clip-path=\"url(#clip-{id})\" \
+                 x=\"{}\" y=\"{}\" font-size=\"{}\">{}</text>\n",
+                num(x),
+                num(baseline),
+                num(size_pt * MM_PER_PT),
+                escape_xml(&text),
+                id = field_id
+            ));
+        }
+        FieldType::MultilineText => {
+            push_label(svg, x, label_baseline, label_size, &field.label);
+
+            if let Some(FieldValue::Text(text)) = value.filter(|v| !v.is_empty()) {
+                let (font, size_pt) = (&options.font, options.font_size_pt);
+                let settings = LineSettings::for_field(field, size_pt);
+                let layout = layout_multiline(metrics, font, size_pt, text, &settings);
+
+                svg.push_str(&format!(
+                    "    <clipPath id=\"clip-{id}\"><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/></clipPath>\n",
+                    num(x),
+                    num(y),
+                    num(field.size.x),
+                    num(field.size.y),
+                    id = field_id
+                ));
+                svg.push_str(&format!(
+                    "    <text class=\"field-value\" data-field=\"{id}\" clip-path=\"url(#clip-{id})\" font-size=\"{}\">\n",
+                    num(size_pt * MM_PER_PT),
+                    id = field_id
+                ));
+                for (idx, line) in layout.lines.iter().enumerate() {
+                    svg.push_str(&format!(
+                        "      <tspan x=\"{}\" y=\"{}\">{}</tspan>\n",
+                        num(x),
+                        num(y + settings.baseline_mm(idx, font, size_pt)),
+                        escape_xml(line)
+                    ));
+                }
+                svg.push_str("    </text>\n");
+            }
+        }
+        FieldType::Comb => {
+            push_label(svg, x, label_baseline, label_size, &field.label);
+            let Some(comb) = CombSettings::for_field(field) else {
+                return;
+            };
+
+            for cell in 0..comb.cells {
+                svg.push_str(&format!(
+                    "    <rect class=\"field-comb-cell\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+                     fill=\"none\" stroke=\"#000\" stroke-width=\"0.18\"/>\n",
+                    num(x + cell as f64 * comb.pitch_mm),
+                    num(y),
+                    num(comb.pitch_mm),
+                    num(field.size.y)
+                ));
+            }
+
+            let text = match value {
+                Some(FieldValue::Text(text)) if !text.is_empty() => text,
+                _ => return,
+            };
+            let (font, size_pt) = (&options.font, options.font_size_pt);
+            let glyphs = comb.glyph_offsets(metrics, font, size_pt, text);
+            // Per-glyph x positions keep each character centred in its box
+            let xs: Vec<String> = glyphs.iter().map(|(_, offset)| num(x + offset)).collect();
+            let shown: String = glyphs.iter().map(|(c, _)| *c).collect();
+            svg.push_str(&format!(
+                "    <text class=\"field-value\" data-field=\"{}\" x=\"{}\" y=\"{}\" font-size=\"{}\">{}</text>\n",
+                field_id,
+                xs.join(" "),
+                num(y + CombSettings::baseline_mm(font, size_pt, field.size.y)),
+                num(size_pt * MM_PER_PT),
+                escape_xml(&shown)
+            ));
+            if let Some(note) = comb.overflow_note(text) {
+                let note_baseline = y + field.size.y + label_size * 1.2;
+                push_label(svg, x, note_baseline, label_size, &note);
+            }
+        }
+        FieldType::Checkbox => {
+            let box_y = y + (field.size.y - CHECKBOX_SIZE_MM) / 2.0;
+            let checked = matches!(value, Some(FieldValue::Checked(true)));
+            svg.push_str(&format!(
+                "    <rect class=\"field-checkbox\" data-field=\"{}\" x=\"{}\" y=\"{}\" width=\"{s}\" height=\"{s}\" \
+                 fill=\"none\" stroke=\"#000\" stroke-width=\"0.26\"/>\n",
+                field_id,
+                num(x),
+                num(box_y),
+                s = num(CHECKBOX_SIZE_MM)
+            ));
+            if checked {
+                let inset = CHECKBOX_SIZE_MM * 0.2;
+                let (x0, y0) = (x + inset, box_y + inset);
+                let (x1, y1) = (x + CHECKBOX_SIZE_MM - inset, box_y + CHECKBOX_SIZE_MM - inset);
+                svg.push_str(&format!(
+                    "    <path class=\"field-check\" d=\"M{} {}L{} {}M{} {}L{} {}\" stroke=\"#000\" stroke-width=\"0.26\"/>\n",
+                    num(x0), num(y0), num(x1), num(y1), num(x0), num(y1), num(x1), num(y0)
+                ));
+            }
+
+            let label_x = x + CHECKBOX_SIZE_MM + CHECKBOX_LABEL_GAP_MM;
+            let label_baseline = box_y + CHECKBOX_SIZE_MM / 2.0 + label_size * 0.35;
+            push_label(svg, label_x, label_baseline, label_size, &field.label);
+        }
+        FieldType::Signature => {
+            push_label(svg, x, label_baseline, label_size, &field.label);
+            let bottom = y + field.size.y;
+            svg.push_str(&format!(
+                "    <path class=\"field-signature-line\" d=\"M{} {}H{}\" stroke=\"#000\" stroke-width=\"0.26\"/>\n",
+                num(x),
+                num(bottom),
+                num(x + field.size.x)
+            ));
+
+            if let Some(FieldValue::Signature(data_url)) = value.filter(|v| !v.is_empty()) {
+                let data_url = escape_xml(data_url);
+                svg.push_str(&format!(
+                    "    <image class=\"field-signature\" data-field=\"{}\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+                     preserveAspectRatio=\"xMidYMid meet\" href=\"{url}\" xlink:href=\"{url}\"/>\n",
+                    field_id,
+                    num(x),
+                    num(y),
+                    num(field.size.x),
+                    num(field.size.y),
+                    url = data_url
+                ));
+            }
+        }
+    }
+}
+
+fn push_label(svg: &mut String, x: f64, baseline: f64, size_mm: f64, label: &str) {
+    svg.push_str(&format!(
+        "    <text class=\"field-label\" x=\"{}\" y=\"{}\" font-family=\"{}\" font-size=\"{}\">{}</text>\n",
+        num(x),
+        num(baseline),
+        escape_xml(StandardFont::Helvetica.css_family()),
+        num(size_mm),
+        escape_xml(label)
+    ));
+}
+
+/// Overlay drawn in CSS pixel space with its `Transform::to_css_string` transform about
+/// the box centre, as the browser does, inside a group that maps pixels to millimetres
+fn render_overlay(svg: &mut String, overlay: &Overlay, coord_system: &CoordinateSystem) {
+    let width_px = coord_system.mm_to_px_x(overlay.size.x);
+    let height_px = coord_system.mm_to_px_y(overlay.size.y);
+    let px_to_mm = coord_system.screen_to_physical(ScreenCoord { x: 1.0, y: 1.0 });
+
+    svg.push_str(&format!(
+        "    <g data-overlay=\"{}\" transform=\"translate({} {}) scale({} {})\">\n",
+        escape_xml(&overlay.id),
+        num(overlay.position.x),
+        num(overlay.position.y),
+        crate::pdf_export::num_with_precision(px_to_mm.x, 6),
+        crate::pdf_export::num_with_precision(px_to_mm.y, 6)
+    ));
+    svg.push_str(&format!(
+        "      <g style=\"transform: {}; transform-origin: {}px {}px\">\n",
+        overlay.transform.to_css_string(),
+        num(width_px / 2.0),
+        num(height_px / 2.0)
+    ));
+
+    let stroke = "stroke=\"#3366cc\" stroke-width=\"1\"";
+    let shape = match overlay.overlay_type {
+        OverlayType::Shape => format!(
+            "<rect width=\"{}\" height=\"{}\" fill=\"#e6e6e6\" {}/>",
+            num(width_px),
+            num(height_px),
+            stroke
+        ),
+        OverlayType::Annotation => format!(
+            "<rect width=\"{}\" height=\"{}\" fill=\"none\" stroke-dasharray=\"4 2\" {}/>",
+            num(width_px),
+            num(height_px),
+            stroke
+        ),
+        OverlayType::Measurement => {
+            format!("<path d=\"M0 {y}H{}\" {}/>", num(width_px), stroke, y = num(height_px / 2.0))
+        }
+        OverlayType::TextBox | OverlayType::Image => format!(
+            "<rect width=\"{}\" height=\"{}\" fill=\"none\" {}/>",
+            num(width_px),
+            num(height_px),
+            stroke
+        ),
+    };
+    svg.push_str(&format!("        {}\n", shape));
+    svg.push_str("      </g>\n");
+    svg.push_str("    </g>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinates::DeviceCalibration;
+    use crate::fixtures::sample_document;
+    use crate::golden::assert_golden;
+
+    fn export(page_index: usize) -> Result<String, String> {
+        let (template, form_data, overlays) = sample_document();
+        export_page_svg(
+            &template,
+            page_index,
+            &form_data,
+            &overlays,
+            &CoordinateSystem::new(DeviceCalibration::default()),
+            &mut FontMetricsCalculator::with_builtin_metrics(),
+            &ExportOptions::default(),
+        )
+    }
+
+    #[test]
+    fn test_page_golden() {
+        let svg = export(0).unwrap();
+        assert!(svg.contains("viewBox=\"0 0 215.9 279.4\""));
+        assert!(svg.contains("rotate(90deg)"));
+        assert_golden("fl100_page1.svg", svg.as_bytes());
+        assert!(export(3).is_err());
+    }
+
+    #[test]
+    fn test_baseline_matches_pdf() {
+        let svg = export(0).unwrap();
+
+        // petitioner_name: 20mm high at y = 45mm, Helvetica 12pt, baseline centring the
+        // font's ascent-to-descent span exactly as PDF export does
+        let expected_mm = 45.0 + 10.0 + (718.0 - 207.0) / 2000.0 * 12.0 * MM_PER_PT;
+        let expected = format!("y=\"{}\" font-size=\"4.233\">Jane (Doe) Smith", num(expected_mm));
+        assert!(svg.contains(&expected), "missing {}", expected);
+    }
+}
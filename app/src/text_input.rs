@@ -25,6 +25,8 @@ pub struct TextInputProps {
     pub value: String,
     pub font: Option<StandardFont>,
     pub font_size_pt: Option<f64>,
+    /// Shrink the value to fit the field, as print and PDF export will
+    pub auto_fit: bool,
     pub coord_system: Option<CoordinateSystem>,
 }
 
@@ -60,23 +62,34 @@ impl Component for TextInput {
     fn view(&self, ctx: &Context<Self>) -> Html {
         let props = ctx.props();
         
+        let font = props.font.clone().unwrap_or(StandardFont::Helvetica);
+        let max_font_size = props.font_size_pt.unwrap_or(12.0);
+        let font_size = match self.font_calculator.try_borrow_mut() {
+            Ok(mut calculator) if props.auto_fit => calculator.fit_font_size(
+                &font,
+                max_font_size,
+                &props.value,
+                props.width_mm,
+                props.height_mm,
+            ),
+            _ => max_font_size,
+        };
+
         // Use font metrics for precise positioning if coordinate system is available
         let style = if let Some(coord_system) = &props.coord_system {
-            let font = props.font.clone().unwrap_or(StandardFont::Helvetica);
-            let font_size = props.font_size_pt.unwrap_or(12.0);
-            
+
             // Calculate precise text positioning
             if let Ok(mut calculator) = self.font_calculator.try_borrow_mut() {
                 if let Some(text_pos) = calculator.calculate_text_position(
-                    font,
+                    font.clone(),
                     font_size,
                     props.position,
                     props.height_mm,
                     coord_system,
                 ) {
                     // Convert field dimensions to screen coordinates
-                    let field_width_px = coord_system.mm_to_px(props.width_mm);
-                    let field_height_px = coord_system.mm_to_px(props.height_mm);
+                    let field_width_px = coord_system.mm_to_px_x(props.width_mm);
+                    let field_height_px = coord_system.mm_to_px_y(props.height_mm);
                     let field_screen = coord_system.physical_to_screen(props.position);
                     
                     format!(
@@ -111,11 +124,21 @@ impl Component for TextInput {
                     type="text"
                     id={props.id.clone()}
                     value={props.value.clone()}
+                    // Inline so it also wins over the print stylesheet
+                    style={props.auto_fit.then(|| format!("font-size: {}pt;", font_size))}
                     oninput={ctx.link().callback(|e: InputEvent| {
                         let input: HtmlInputElement = e.target_unchecked_into();
                         TextInputMsg::Input(input.value())
                     })}
                 />
+                { if font_size < max_font_size {
+                    // Same size print and PDF export use, so the user sees what will be filed
+                    html! {
+                        <span class="fit-size" title="Shrunk to fit the field">
+                            {format!("{} pt", font_size)}
+                        </span>
+                    }
+                } else { html! {} }}
             </div>
         }
     }
@@ -132,8 +155,8 @@ impl TextInput {
 
     /// Fallback styling without font metrics
     fn get_fallback_style(&self, coord_system: &CoordinateSystem, props: &TextInputProps) -> String {
-        let field_width_px = coord_system.mm_to_px(props.width_mm);
-        let field_height_px = coord_system.mm_to_px(props.height_mm);
+        let field_width_px = coord_system.mm_to_px_x(props.width_mm);
+        let field_height_px = coord_system.mm_to_px_y(props.height_mm);
         let field_screen = coord_system.physical_to_screen(props.position);
         
         format!(
@@ -0,0 +1,397 @@
+//
+// ████████╗███████╗██╗  ██╗████████╗   ██╗      █████╗ ██╗   ██╗ ██████╗ ██╗   ██╗████████╗
+// ╚══██╔══╝██╔════╝╚██╗██╔╝╚══██╔══╝   ██║     ██╔══██╗╚██╗ ██╔╝██╔═══██╗██║   ██║╚══██╔══╝
+//    ██║   █████╗   ╚███╔╝    ██║      ██║     ███████║ ╚████╔╝ ██║   ██║██║   ██║   ██║
+//    ██║   ██╔══╝   ██╔██╗    ██║      ██║     ██╔══██║  ╚██╔╝  ██║   ██║██║   ██║   ██║
+//    ██║   ███████╗██╔╝ ██╗   ██║      ███████╗██║  ██║   ██║   ╚██████╔╝╚██████╔╝   ██║
+//    ╚═╝   ╚══════╝╚═╝  ╚═╝   ╚═╝      ╚══════╝╚═╝  ╚═╝   ╚═╝    ╚═════╝  ╚═════╝    ╚═╝
+//                                                                    app/src/text_layout.rs
+
+use crate::coordinates::{Orientation, PageFormat, CSS_PX_PER_MM};
+use crate::font_metrics::{FontMetricsCalculator, StandardFont};
+use crate::form_data::{FieldValue, FormData};
+use crate::form_template::{FieldType, FormFieldInfo, FormTemplate};
+
+/// Last line of a multi-line field whose text continues on an attachment page
+pub const CONTINUED_MARKER: &str = "(Continued on attachment)";
+
+/// Baseline spacing as a multiple of the font size when a field doesn't set one
+const DEFAULT_LINE_SPACING: f64 = 1.2;
+
+/// Margins of generated attachment pages; the footer sits below the bottom margin
+pub const ATTACHMENT_MARGIN_MM: f64 = 25.4;
+
+const MM_PER_PT: f64 = 25.4 / 72.0;
+
+/// How a multi-line field lays out its text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineSettings {
+    pub width_mm: f64,
+    /// Distance between baselines, matching the printed lines of the form
+    pub line_pitch_mm: f64,
+    pub max_lines: usize,
+}
+
+impl LineSettings {
+    /// Settings from a field's template, filling in spacing from the font size and the
+    /// line limit from the field height
+    pub fn for_field(field: &FormFieldInfo, size_pt: f64) -> Self {
+        let line_pitch_mm =
+            field.line_pitch_mm.unwrap_or(size_pt * DEFAULT_LINE_SPACING * MM_PER_PT);
+        let max_lines = field
+            .max_lines
+            .unwrap_or_else(|| ((field.size.y / line_pitch_mm + 1e-6).floor() as usize).max(1));
+
+        Self { width_mm: field.size.x, line_pitch_mm, max_lines }
+    }
+
+    /// Baseline of `line` below the top of the field: descenders rest on the printed line
+    pub fn baseline_mm(&self, line: usize, font: &StandardFont, size_pt: f64) -> f64 {
+        (line + 1) as f64 * self.line_pitch_mm + font.descent_units() / 1000.0 * size_pt * MM_PER_PT
+    }
+}
+
+/// A multi-line value laid out within its line limit
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultilineLayout {
+    /// Lines shown in the field, top to bottom
+    pub lines: Vec<String>,
+    /// Text past the line limit, to be continued on an attachment page
+    pub overflow: Option<String>,
+}
+
+/// A wrapped line and what separated it from the next
+struct WrappedLine {
+    text: String,
+    end: LineEnd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LineEnd {
+    /// Wrapped at a space between words
+    Space,
+    /// Wrapped inside a word too wide for the line
+    MidWord,
+    /// The paragraph ends here
+    Paragraph,
+}
+
+impl LineEnd {
+    /// Text that joins this line to the next when the lines are put back together
+    fn separator(&self) -> &'static str {
+        match self {
+            LineEnd::Space => " ",
+            LineEnd::MidWord => "",
+            LineEnd::Paragraph => "\n",
+        }
+    }
+}
+
+/// Break `text` into lines no wider than `width_mm`, measured from glyph widths.
+///
+/// Newlines start a new paragraph, words wrap at spaces, and a word wider than the
+/// whole line is broken between characters.
+pub fn wrap_text(
+    metrics: &mut FontMetricsCalculator,
+    font: &StandardFont,
+    size_pt: f64,
+    text: &str,
+    width_mm: f64,
+) -> Vec<String> {
+    wrap_lines(metrics, font, size_pt, text, width_mm).into_iter().map(|line| line.text).collect()
+}
+
+fn wrap_lines(
+    metrics: &mut FontMetricsCalculator,
+    font: &StandardFont,
+    size_pt: f64,
+    text: &str,
+    width_mm: f64,
+) -> Vec<WrappedLine> {
+    let mut fits =
+        |line: &str| metrics.measure_text_px(font, size_pt, line) / CSS_PX_PER_MM <= width_mm;
+    let mut lines = Vec::new();
+
+    for paragraph in text.trim_end_matches(['\r', '\n']).split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate =
+                if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+            if fits(&candidate) {
+                line = candidate;
+                continue;
+            }
+
+            if !line.is_empty() {
+                lines.push(WrappedLine { text: std::mem::take(&mut line), end: LineEnd::Space });
+            }
+            for c in word.chars() {
+                line.push(c);
+                if !fits(&line) && line.chars().count() > 1 {
+                    line.pop();
+                    let text = std::mem::take(&mut line);
+                    lines.push(WrappedLine { text, end: LineEnd::MidWord });
+                    line.push(c);
+                }
+            }
+        }
+        lines.push(WrappedLine { text: line, end: LineEnd::Paragraph });
+    }
+
+    lines
+}
+
+/// Wrap `text` into a field, replacing the last line with [`CONTINUED_MARKER`] and
+/// returning the rest as overflow when it needs more than `settings.max_lines`
+pub fn layout_multiline(
+    metrics: &mut FontMetricsCalculator,
+    font: &StandardFont,
+    size_pt: f64,
+    text: &str,
+    settings: &LineSettings,
+) -> MultilineLayout {
+    let mut wrapped = wrap_lines(metrics, font, size_pt, text, settings.width_mm);
+    if wrapped.len() <= settings.max_lines {
+        return MultilineLayout {
+            lines: wrapped.into_iter().map(|line| line.text).collect(),
+            overflow: None,
+        };
+    }
+
+    let rest = wrapped.split_off(settings.max_lines.saturating_sub(1));
+    let mut overflow = String::new();
+    for line in rest {
+        overflow.push_str(&line.text);
+        overflow.push_str(line.end.separator());
+    }
+
+    let mut lines: Vec<String> = wrapped.into_iter().map(|line| line.text).collect();
+    lines.push(CONTINUED_MARKER.to_string());
+    MultilineLayout { lines, overflow: Some(overflow.trim_end().to_string()) }
+}
+
+/// Overflowing text of one multi-line field
+#[derive(Debug, Clone, PartialEq)]
+pub struct Continuation {
+    pub field_id: String,
+    pub label: String,
+    pub page_number: usize,
+    pub text: String,
+}
+
+/// Every multi-line field in the document whose text runs past its line limit
+pub fn continuations(
+    metrics: &mut FontMetricsCalculator,
+    template: &FormTemplate,
+    form_data: &FormData,
+    font: &StandardFont,
+    size_pt: f64,
+) -> Vec<Continuation> {
+    let mut continuations = Vec::new();
+    for page in &template.pages {
+        for field in page.fields.iter().filter(|f| f.field_type == FieldType::MultilineText) {
+            let Some(FieldValue::Text(text)) = form_data.get(&field.id) else {
+                continue;
+            };
+            let settings = LineSettings::for_field(field, size_pt);
+            if let Some(text) = layout_multiline(metrics, font, size_pt, text, &settings).overflow {
+                continuations.push(Continuation {
+                    field_id: field.id.clone(),
+                    label: field.label.clone(),
+                    page_number: page.page_number,
+                    text,
+                });
+            }
+        }
+    }
+    continuations
+}
+
+/// One line of an attachment page
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttachmentLine {
+    /// Names the field a continuation belongs to
+    Heading(String),
+    Text(String),
+    Blank,
+}
+
+/// Paper of attachment pages: the form's own paper, upright since they only hold text
+pub fn attachment_format(template: &FormTemplate) -> PageFormat {
+    let paper = template.pages.first().map(|page| page.format.paper).unwrap_or_default();
+    PageFormat::new(paper, Orientation::Portrait)
+}
+
+/// Lay out continuations on as many attachment pages as they need, with a heading
+/// before each field's text and a blank line between fields
+pub fn attachment_pages(
+    metrics: &mut FontMetricsCalculator,
+    continuations: &[Continuation],
+    font: &StandardFont,
+    size_pt: f64,
+    format: &PageFormat,
+) -> Vec<Vec<AttachmentLine>> {
+    let width_mm = format.width_mm() - 2.0 * ATTACHMENT_MARGIN_MM;
+    let lines_per_page = (((format.height_mm() - 2.0 * ATTACHMENT_MARGIN_MM)
+        / attachment_line_pitch_mm(size_pt))
+    .floor() as usize)
+        .max(2);
+
+    let mut lines = Vec::new();
+    for continuation in continuations {
+        if !lines.is_empty() {
+            lines.push(AttachmentLine::Blank);
+        }
+        lines.push(AttachmentLine::Heading(format!(
+            "Continued from page {}: {}",
+            continuation.page_number, continuation.label
+        )));
+        lines.extend(
+            wrap_text(metrics, font, size_pt, &continuation.text, width_mm)
+                .into_iter()
+                .map(AttachmentLine::Text),
+        );
+    }
+
+    let mut pages: Vec<Vec<AttachmentLine>> = Vec::new();
+    for line in lines {
+        match pages.last_mut() {
+            Some(page) if page.len() < lines_per_page => {
+                // A heading never ends a page, and a page never starts with a blank line
+                if matches!(line, AttachmentLine::Heading(_)) && page.len() + 1 == lines_per_page {
+                    pages.push(vec![line]);
+                } else {
+                    page.push(line);
+                }
+            }
+            _ if line == AttachmentLine::Blank => {}
+            _ => pages.push(vec![line]),
+        }
+    }
+    pages
+}
+
+/// Baseline spacing on attachment pages
+pub fn attachment_line_pitch_mm(size_pt: f64) -> f64 {
+    size_pt * DEFAULT_LINE_SPACING * MM_PER_PT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinates::{PaperSize, PhysicalCoord};
+
+    fn field(max_lines: Option<usize>) -> FormFieldInfo {
+        FormFieldInfo {
+            id: "other_requests".to_string(),
+            field_type: FieldType::MultilineText,
+            position: PhysicalCoord { x: 25.0, y: 140.0 },
+            size: PhysicalCoord { x: 60.0, y: 32.0 },
+            label: "Other Requests".to_string(),
+            auto_fit: false,
+            line_pitch_mm: Some(6.35),
+            max_lines,
+            comb_cells: None,
+            comb_pitch_mm: None,
+        }
+    }
+
+    #[test]
+    fn test_wrap_by_measured_width() {
+        let mut metrics = FontMetricsCalculator::new();
+        let font = StandardFont::Helvetica;
+        let text = "The family residence at 1200 Elm Street and its furnishings\n\nSavings";
+        let lines = wrap_text(&mut metrics, &font, 12.0, text, 60.0);
+
+        assert!(lines.len() > 3);
+        for line in &lines {
+            assert!(metrics.measure_text_px(&font, 12.0, line) / CSS_PX_PER_MM <= 60.0);
+        }
+        // Paragraph breaks are kept, including the blank line
+        assert_eq!(lines[lines.len() - 2], "");
+        assert_eq!(lines[lines.len() - 1], "Savings");
+        assert_eq!(lines[..lines.len() - 2].join(" "), text.lines().next().unwrap());
+
+        // A word wider than the line is broken between characters
+        let lines = wrap_text(&mut metrics, &font, 12.0, &"W".repeat(30), 60.0);
+        assert!(lines.len() > 1);
+        assert_eq!(lines.concat(), "W".repeat(30));
+    }
+
+    #[test]
+    fn test_line_settings() {
+        let settings = LineSettings::for_field(&field(None), 12.0);
+        assert_eq!(settings.max_lines, 5);
+        assert_eq!(LineSettings::for_field(&field(Some(3)), 12.0).max_lines, 3);
+
+        // Descenders of the first line rest on the first printed line
+        let font = StandardFont::Helvetica;
+        let baseline = settings.baseline_mm(0, &font, 12.0);
+        let descent = -font.descent_units() / 1000.0 * 12.0 * MM_PER_PT;
+        assert!((baseline + descent - 6.35).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_overflow_spills_to_attachment() {
+        let mut metrics = FontMetricsCalculator::new();
+        let font = StandardFont::Helvetica;
+        let text = "Pension from employment with the County of Sacramento, \
+                    accrued before the date of marriage.\nVehicle: 2012 Honda Civic";
+        let settings = LineSettings::for_field(&field(Some(3)), 12.0);
+        let layout = layout_multiline(&mut metrics, &font, 12.0, text, &settings);
+
+        assert_eq!(layout.lines.len(), 3);
+        assert_eq!(layout.lines[2], CONTINUED_MARKER);
+        let overflow = layout.overflow.unwrap();
+        assert!(
+            overflow.ends_with("accrued before the date of marriage.\nVehicle: 2012 Honda Civic")
+        );
+        assert_eq!(format!("{} {}", layout.lines[..2].join(" "), overflow), text);
+
+        let continuation = Continuation {
+            field_id: "other_requests".to_string(),
+            label: "Other Requests".to_string(),
+            page_number: 3,
+            text: overflow,
+        };
+        let pages = attachment_pages(
+            &mut metrics,
+            &[continuation.clone(), continuation],
+            &font,
+            12.0,
+            &PageFormat::default(),
+        );
+        assert_eq!(pages.len(), 1);
+        assert_eq!(
+            pages[0][0],
+            AttachmentLine::Heading("Continued from page 3: Other Requests".into())
+        );
+        assert!(pages[0].contains(&AttachmentLine::Blank));
+    }
+
+    #[test]
+    fn test_overflow_rejoins_broken_words() {
+        let mut metrics = FontMetricsCalculator::new();
+        let font = StandardFont::Helvetica;
+        // A long account number is broken between characters to fit the line
+        let number = "0".repeat(40);
+        let text = format!("Account {} at Golden State Bank", number);
+        let settings = LineSettings { width_mm: 60.0, line_pitch_mm: 6.35, max_lines: 2 };
+        let layout = layout_multiline(&mut metrics, &font, 12.0, &text, &settings);
+
+        assert_eq!(layout.lines, vec!["Account".to_string(), CONTINUED_MARKER.to_string()]);
+        assert_eq!(layout.overflow.unwrap(), format!("{} at Golden State Bank", number));
+    }
+
+    #[test]
+    fn test_attachment_format_follows_form() {
+        let mut template = FormTemplate::fl100();
+        assert_eq!(attachment_format(&template), PageFormat::default());
+
+        for page in &mut template.pages {
+            page.format = PageFormat::new(PaperSize::A4, Orientation::Landscape);
+        }
+        let a4 = PageFormat::new(PaperSize::A4, Orientation::Portrait);
+        assert_eq!(attachment_format(&template), a4);
+    }
+}
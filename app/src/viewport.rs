@@ -0,0 +1,235 @@
+//
+// ██╗   ██╗██╗███████╗██╗    ██╗██████╗  ██████╗ ██████╗ ████████╗
+// ██║   ██║██║██╔════╝██║    ██║██╔══██╗██╔═══██╗██╔══██╗╚══██╔══╝
+// ██║   ██║██║█████╗  ██║ █╗ ██║██████╔╝██║   ██║██████╔╝   ██║
+// ╚██╗ ██╔╝██║██╔══╝  ██║███╗██║██╔═══╝ ██║   ██║██╔══██╗   ██║
+//  ╚████╔╝ ██║███████╗╚███╔███╔╝██║     ╚██████╔╝██║  ██║   ██║
+//   ╚═══╝  ╚═╝╚══════╝ ╚══╝╚══╝ ╚═╝      ╚═════╝ ╚═╝  ╚═╝   ╚═╝
+//                                              app/src/viewport.rs
+
+use crate::coordinates::{CoordinateSystem, PhysicalCoord, ScreenCoord};
+use crate::form_template::{FormFieldInfo, PageInfo};
+use crate::overlay::Overlay;
+
+/// Where a page's millimetre origin sits inside the document, before zoom
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageLayout {
+    /// Top-left of the page's field area in unzoomed document pixels
+    pub origin: ScreenCoord,
+    /// Page extent in millimetres
+    pub size: PhysicalCoord,
+}
+
+/// A millimetre position on a specific page
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PagePoint {
+    pub page_index: usize,
+    pub position: PhysicalCoord,
+}
+
+/// Screen placement of a scrolled, zoomed document: maps pointer positions
+/// (client pixels) to millimetres on a page and back
+#[derive(Debug, Clone, PartialEq)]
+pub struct Viewport {
+    /// Client position of the scroll container's top-left corner
+    pub origin: ScreenCoord,
+    /// Scroll offset of the container in CSS pixels
+    pub scroll: ScreenCoord,
+    /// Scale applied to the document about its top-left corner
+    pub zoom: f64,
+    /// Layout of each page, indexed like the template's pages
+    pub pages: Vec<PageLayout>,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self {
+            origin: ScreenCoord { x: 0.0, y: 0.0 },
+            scroll: ScreenCoord { x: 0.0, y: 0.0 },
+            zoom: 1.0,
+            pages: Vec::new(),
+        }
+    }
+}
+
+impl Viewport {
+    /// Client position of a point on a page
+    pub fn physical_to_screen(
+        &self,
+        coord_system: &CoordinateSystem,
+        point: PagePoint,
+    ) -> Option<ScreenCoord> {
+        let page = self.pages.get(point.page_index)?;
+        let local = coord_system.physical_to_screen(point.position);
+        Some(ScreenCoord {
+            x: self.origin.x - self.scroll.x + self.zoom * (page.origin.x + local.x),
+            y: self.origin.y - self.scroll.y + self.zoom * (page.origin.y + local.y),
+        })
+    }
+
+    /// Page and millimetre position under a client position, or `None` between pages
+    pub fn screen_to_physical(
+        &self,
+        coord_system: &CoordinateSystem,
+        client: ScreenCoord,
+    ) -> Option<PagePoint> {
+        (0..self.pages.len()).find_map(|page_index| {
+            let position = self.screen_to_page(coord_system, page_index, client)?;
+            let size = self.pages[page_index].size;
+            let on_page =
+                (0.0..=size.x).contains(&position.x) && (0.0..=size.y).contains(&position.y);
+            on_page.then_some(PagePoint { page_index, position })
+        })
+    }
+
+    /// Millimetre position relative to a given page, even outside its edges,
+    /// so drags keep tracking when the pointer leaves the page
+    pub fn screen_to_page(
+        &self,
+        coord_system: &CoordinateSystem,
+        page_index: usize,
+        client: ScreenCoord,
+    ) -> Option<PhysicalCoord> {
+        let page = self.pages.get(page_index)?;
+        let document = self.screen_to_document(client);
+        Some(coord_system.screen_to_physical(ScreenCoord {
+            x: document.x - page.origin.x,
+            y: document.y - page.origin.y,
+        }))
+    }
+
+    /// Unzoomed document pixels for a pointer movement in client pixels
+    pub fn screen_delta_to_px(&self, delta_x: f64, delta_y: f64) -> (f64, f64) {
+        (delta_x / self.zoom, delta_y / self.zoom)
+    }
+
+    fn screen_to_document(&self, client: ScreenCoord) -> ScreenCoord {
+        ScreenCoord {
+            x: (client.x - self.origin.x + self.scroll.x) / self.zoom,
+            y: (client.y - self.origin.y + self.scroll.y) / self.zoom,
+        }
+    }
+}
+
+/// Topmost visible overlay under a page point
+pub fn overlay_at<'a>(
+    overlays: &'a [Overlay],
+    point: PagePoint,
+    coord_system: &CoordinateSystem,
+) -> Option<&'a Overlay> {
+    overlays
+        .iter()
+        .filter(|overlay| overlay.page_index == point.page_index && overlay.visible)
+        .filter(|overlay| overlay.contains(point.position, coord_system))
+        .max_by_key(|overlay| overlay.z_index)
+}
+
+/// Form field under a point on a page
+pub fn field_at(page: &PageInfo, position: PhysicalCoord) -> Option<&FormFieldInfo> {
+    page.fields.iter().find(|field| {
+        (field.position.x..=field.position.x + field.size.x).contains(&position.x)
+            && (field.position.y..=field.position.y + field.size.y).contains(&position.y)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinates::DeviceCalibration;
+    use crate::form_template::FormTemplate;
+    use crate::overlay::{OverlayType, Transform};
+    use crate::pdf_export::css_transform_matrix;
+
+    fn coord_system() -> CoordinateSystem {
+        CoordinateSystem::new(DeviceCalibration {
+            scale_x: 4.0,
+            scale_y: 4.0,
+            ..DeviceCalibration::default()
+        })
+    }
+
+    /// Two letter pages stacked 60px from the top-left, 32px apart, scrolled and zoomed
+    fn viewport() -> Viewport {
+        let size = PhysicalCoord { x: 215.9, y: 279.4 };
+        Viewport {
+            origin: ScreenCoord { x: 100.0, y: 50.0 },
+            scroll: ScreenCoord { x: 0.0, y: 400.0 },
+            zoom: 1.5,
+            pages: vec![
+                PageLayout { origin: ScreenCoord { x: 60.0, y: 60.0 }, size },
+                PageLayout { origin: ScreenCoord { x: 60.0, y: 60.0 + 1117.6 + 32.0 }, size },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_screen_round_trip() {
+        let coord_system = coord_system();
+        let viewport = viewport();
+        let point = PagePoint { page_index: 1, position: PhysicalCoord { x: 25.0, y: 40.0 } };
+
+        let client = viewport.physical_to_screen(&coord_system, point).unwrap();
+        // 100 + 1.5 * (60 + 100) and 50 - 400 + 1.5 * (1209.6 + 160)
+        assert!((client.x - 340.0).abs() < 1e-9 && (client.y - 1704.4).abs() < 1e-9);
+
+        let back = viewport.screen_to_physical(&coord_system, client).unwrap();
+        assert_eq!(back.page_index, 1);
+        assert!((back.position.x - 25.0).abs() < 1e-9 && (back.position.y - 40.0).abs() < 1e-9);
+
+        // The gap between pages belongs to neither
+        let gap = ScreenCoord { x: 340.0, y: 50.0 - 400.0 + 1.5 * (60.0 + 1117.6 + 16.0) };
+        assert_eq!(viewport.screen_to_physical(&coord_system, gap), None);
+        assert_eq!(viewport.screen_delta_to_px(30.0, -15.0), (20.0, -10.0));
+    }
+
+    #[test]
+    fn test_overlay_hit_testing() {
+        let coord_system = coord_system();
+        let mut overlay = Overlay {
+            id: "overlay_1".to_string(),
+            page_index: 0,
+            overlay_type: OverlayType::Shape,
+            transform: Transform { translate_x: 40.0, rotation: 90.0, ..Transform::default() },
+            position: PhysicalCoord { x: 50.0, y: 50.0 },
+            size: PhysicalCoord { x: 30.0, y: 20.0 },
+            visible: true,
+            selected: false,
+            z_index: 1,
+        };
+
+        // Rotated 90° about its centre (65, 60) and shifted 10mm: now 20 wide, 30 tall at x=75
+        let at = |x, y| PagePoint { page_index: 0, position: PhysicalCoord { x, y } };
+        assert!(overlay.contains(PhysicalCoord { x: 75.0, y: 72.0 }, &coord_system));
+        assert!(!overlay.contains(PhysicalCoord { x: 90.0, y: 60.0 }, &coord_system));
+
+        // Agrees with the matrix the exporters use, skew included
+        overlay.transform.skew_x = 15.0;
+        overlay.transform.scale_y = 1.5;
+        let [a, b, c, d, e, f] = css_transform_matrix(&overlay, &coord_system).0;
+        let (local_x, local_y) = (29.0, 1.0);
+        let corner =
+            PhysicalCoord { x: a * local_x + c * local_y + e, y: b * local_x + d * local_y + f };
+        assert!(overlay.contains(corner, &coord_system));
+
+        let mut top = overlay.clone();
+        top.id = "overlay_2".to_string();
+        top.z_index = 2;
+        let overlays = vec![overlay, top];
+        assert_eq!(
+            overlay_at(&overlays, at(corner.x, corner.y), &coord_system).unwrap().id,
+            "overlay_2"
+        );
+        assert!(overlay_at(&overlays, at(5.0, 5.0), &coord_system).is_none());
+    }
+
+    #[test]
+    fn test_field_hit_testing() {
+        let template = FormTemplate::fl100();
+        let page = &template.pages[0];
+        let field = &page.fields[0];
+        let inside = PhysicalCoord { x: field.position.x + 1.0, y: field.position.y + 1.0 };
+
+        assert_eq!(field_at(page, inside).unwrap().id, field.id);
+        assert!(field_at(page, PhysicalCoord { x: 1.0, y: 1.0 }).is_none());
+    }
+}
@@ -0,0 +1,27 @@
+//
+// ██╗  ██╗███╗   ███╗██╗
+// ╚██╗██╔╝████╗ ████║██║
+//  ╚███╔╝ ██╔████╔██║██║
+//  ██╔██╗ ██║╚██╔╝██║██║
+// ██╔╝ ██╗██║ ╚═╝ ██║███████╗
+// ╚═╝  ╚═╝╚═╝     ╚═╝╚══════╝
+//              app/src/xml.rs
+
+/// Escape text for use in XML character data and quoted attribute values; line breaks
+/// become character references so attribute values keep them
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
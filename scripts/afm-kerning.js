@@ -0,0 +1,131 @@
+/*
+ █████╗ ███████╗███╗   ███╗   ██╗  ██╗███████╗██████╗ ███╗   ██╗██╗███╗   ██╗ ██████╗
+██╔══██╗██╔════╝████╗ ████║   ██║ ██╔╝██╔════╝██╔══██╗████╗  ██║██║████╗  ██║██╔════╝
+███████║█████╗  ██╔████╔██║   █████╔╝ █████╗  ██████╔╝██╔██╗ ██║██║██╔██╗ ██║██║  ███╗
+██╔══██║██╔══╝  ██║╚██╔╝██║   ██╔═██╗ ██╔══╝  ██╔══██╗██║╚██╗██║██║██║╚██╗██║██║   ██║
+██║  ██║██║     ██║ ╚═╝ ██║   ██║  ██╗███████╗██║  ██║██║ ╚████║██║██║ ╚████║╚██████╔╝
+╚═╝  ╚═╝╚═╝     ╚═╝     ╚═╝   ╚═╝  ╚═╝╚══════╝╚═╝  ╚═╝╚═╝  ╚═══╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+                                                                scripts/afm-kerning.js
+*/
+
+import fs from 'fs';
+import path from 'path';
+import { fileURLToPath } from 'url';
+
+/**
+ * Regenerate the kerning tables in app/src/afm.rs from Adobe's Core 14 AFM files.
+ *
+ * Usage: node scripts/afm-kerning.js <directory with Helvetica.afm, Helvetica-Bold.afm
+ * and Times-Roman.afm>
+ *
+ * Every KPX pair whose glyphs are both in WinAnsiEncoding is kept, so measurement and
+ * PDF export kern exactly the pairs the fonts define.
+ */
+
+const AFM_RS = path.join(path.dirname(fileURLToPath(import.meta.url)), '../app/src/afm.rs');
+
+const TABLES = [
+    ['Helvetica.afm', 'HELVETICA_KERNING'],
+    ['Helvetica-Bold.afm', 'HELVETICA_BOLD_KERNING'],
+    ['Times-Roman.afm', 'TIMES_ROMAN_KERNING'],
+];
+
+// AFM glyph names of WinAnsiEncoding, with the Unicode character each one prints
+const WIN_ANSI_GLYPHS = {
+    space: 0x20, exclam: 0x21, quotedbl: 0x22, numbersign: 0x23, dollar: 0x24,
+    percent: 0x25, ampersand: 0x26, quotesingle: 0x27, parenleft: 0x28, parenright: 0x29,
+    asterisk: 0x2a, plus: 0x2b, comma: 0x2c, hyphen: 0x2d, period: 0x2e, slash: 0x2f,
+    zero: 0x30, one: 0x31, two: 0x32, three: 0x33, four: 0x34, five: 0x35, six: 0x36,
+    seven: 0x37, eight: 0x38, nine: 0x39, colon: 0x3a, semicolon: 0x3b, less: 0x3c,
+    equal: 0x3d, greater: 0x3e, question: 0x3f, at: 0x40, bracketleft: 0x5b,
+    backslash: 0x5c, bracketright: 0x5d, asciicircum: 0x5e, underscore: 0x5f, grave: 0x60,
+    braceleft: 0x7b, bar: 0x7c, braceright: 0x7d, asciitilde: 0x7e,
+    Euro: 0x20ac, quotesinglbase: 0x201a, florin: 0x192, quotedblbase: 0x201e,
+    ellipsis: 0x2026, dagger: 0x2020, daggerdbl: 0x2021, circumflex: 0x2c6,
+    perthousand: 0x2030, Scaron: 0x160, guilsinglleft: 0x2039, OE: 0x152, Zcaron: 0x17d,
+    quoteleft: 0x2018, quoteright: 0x2019, quotedblleft: 0x201c, quotedblright: 0x201d,
+    bullet: 0x2022, endash: 0x2013, emdash: 0x2014, tilde: 0x2dc, trademark: 0x2122,
+    scaron: 0x161, guilsinglright: 0x203a, oe: 0x153, zcaron: 0x17e, Ydieresis: 0x178,
+    exclamdown: 0xa1, cent: 0xa2, sterling: 0xa3, currency: 0xa4, yen: 0xa5,
+    brokenbar: 0xa6, section: 0xa7, dieresis: 0xa8, copyright: 0xa9, ordfeminine: 0xaa,
+    guillemotleft: 0xab, logicalnot: 0xac, registered: 0xae, macron: 0xaf, degree: 0xb0,
+    plusminus: 0xb1, twosuperior: 0xb2, threesuperior: 0xb3, acute: 0xb4, mu: 0xb5,
+    paragraph: 0xb6, periodcentered: 0xb7, cedilla: 0xb8, onesuperior: 0xb9,
+    ordmasculine: 0xba, guillemotright: 0xbb, onequarter: 0xbc, onehalf: 0xbd,
+    threequarters: 0xbe, questiondown: 0xbf, Agrave: 0xc0, Aacute: 0xc1, Acircumflex: 0xc2,
+    Atilde: 0xc3, Adieresis: 0xc4, Aring: 0xc5, AE: 0xc6, Ccedilla: 0xc7, Egrave: 0xc8,
+    Eacute: 0xc9, Ecircumflex: 0xca, Edieresis: 0xcb, Igrave: 0xcc, Iacute: 0xcd,
+    Icircumflex: 0xce, Idieresis: 0xcf, Eth: 0xd0, Ntilde: 0xd1, Ograve: 0xd2, Oacute: 0xd3,
+    Ocircumflex: 0xd4, Otilde: 0xd5, Odieresis: 0xd6, multiply: 0xd7, Oslash: 0xd8,
+    Ugrave: 0xd9, Uacute: 0xda, Ucircumflex: 0xdb, Udieresis: 0xdc, Yacute: 0xdd,
+    Thorn: 0xde, germandbls: 0xdf, agrave: 0xe0, aacute: 0xe1, acircumflex: 0xe2,
+    atilde: 0xe3, adieresis: 0xe4, aring: 0xe5, ae: 0xe6, ccedilla: 0xe7, egrave: 0xe8,
+    eacute: 0xe9, ecircumflex: 0xea, edieresis: 0xeb, igrave: 0xec, iacute: 0xed,
+    icircumflex: 0xee, idieresis: 0xef, eth: 0xf0, ntilde: 0xf1, ograve: 0xf2, oacute: 0xf3,
+    ocircumflex: 0xf4, otilde: 0xf5, odieresis: 0xf6, divide: 0xf7, oslash: 0xf8,
+    ugrave: 0xf9, uacute: 0xfa, ucircumflex: 0xfb, udieresis: 0xfc, yacute: 0xfd,
+    thorn: 0xfe, ydieresis: 0xff,
+};
+for (let code = 0x41; code <= 0x5a; code++) {
+    WIN_ANSI_GLYPHS[String.fromCharCode(code)] = code;
+    WIN_ANSI_GLYPHS[String.fromCharCode(code + 0x20)] = code + 0x20;
+}
+
+/** KPX pairs of one AFM file as [left code point, right code point, adjustment] */
+function readKerning(afmPath) {
+    const pairs = [];
+    for (const line of fs.readFileSync(afmPath, 'latin1').split(/\r?\n/)) {
+        const [keyword, left, right, amount] = line.trim().split(/\s+/);
+        if (keyword !== 'KPX') continue;
+        if (!(left in WIN_ANSI_GLYPHS) || !(right in WIN_ANSI_GLYPHS)) continue;
+        pairs.push([WIN_ANSI_GLYPHS[left], WIN_ANSI_GLYPHS[right], parseInt(amount, 10)]);
+    }
+    return pairs.sort((a, b) => a[0] - b[0] || a[1] - b[1]);
+}
+
+function rustChar(code) {
+    if (code === 0x27) return "'\\''";
+    if (code === 0x5c) return "'\\\\'";
+    if (code < 0x7f) return `'${String.fromCharCode(code)}'`;
+    return `'\\u{${code.toString(16).toUpperCase()}}'`;
+}
+
+/** Table body: one group of lines per left character, five pairs to a line */
+function rustTable(pairs) {
+    const lines = [];
+    let group = [];
+    const flush = () => {
+        for (let i = 0; i < group.length; i += 5) {
+            lines.push('    ' + group.slice(i, i + 5).join(' '));
+        }
+        group = [];
+    };
+    pairs.forEach(([left, right, amount], idx) => {
+        if (idx > 0 && pairs[idx - 1][0] !== left) flush();
+        group.push(`(${rustChar(left)}, ${rustChar(right)}, ${amount}),`);
+    });
+    flush();
+    return lines.join('\n');
+}
+
+function main() {
+    const afmDir = process.argv[2];
+    if (!afmDir) {
+        console.error('Usage: node scripts/afm-kerning.js <AFM directory>');
+        process.exit(1);
+    }
+
+    let source = fs.readFileSync(AFM_RS, 'utf8');
+    for (const [file, name] of TABLES) {
+        const pairs = readKerning(path.join(afmDir, file));
+        const table = new RegExp(`(pub const ${name}: &\\[\\(char, char, i16\\)\\] = &\\[\\n)[\\s\\S]*?(\\n\\];)`);
+        if (!table.test(source)) {
+            throw new Error(`${name} not found in ${AFM_RS}`);
+        }
+        source = source.replace(table, (_, head, tail) => head + rustTable(pairs) + tail);
+        console.log(`${name}: ${pairs.length} pairs from ${file}`);
+    }
+    fs.writeFileSync(AFM_RS, source);
+}
+
+main();